@@ -205,6 +205,7 @@ macro_rules! gate_doc { ($($name:ident => $feature:ident)*) => {
                     cfg_hide => doc_cfg_hide
                     masked => doc_masked
                     notable_trait => doc_notable_trait
+                    since => doc_since
                 );
 
                 if nested_meta.has_name(sym::keyword) {
@@ -391,6 +391,8 @@ pub fn set(&self, features: &mut Features, span: Span) {
     (active, doc_cfg_hide, "1.57.0", Some(43781), None),
     /// Allows `#[doc(masked)]`.
     (active, doc_masked, "1.21.0", Some(44027), None),
+    /// Allows `#[doc(since = "...")]`.
+    (active, doc_since, "CURRENT_RUSTC_VERSION", None, None),
     /// Allows `dyn* Trait` objects.
     (incomplete, dyn_star, "1.65.0", Some(102425), None),
     // Uses generic effect parameters for ~const bounds
@@ -513,6 +515,9 @@ pub fn set(&self, features: &mut Features, span: Span) {
     (incomplete, return_type_notation, "1.70.0", Some(109417), None),
     /// Allows `extern "rust-cold"`.
     (active, rust_cold_cc, "1.63.0", Some(97544), None),
+    /// Allows using the lints of the `semver_hazards` group (`exhaustive_public_structs`,
+    /// `exhaustive_public_enums`, `auto_traits_from_private_fields`).
+    (active, semver_hazard_lints, "CURRENT_RUSTC_VERSION", None, None),
     /// Allows the use of SIMD types in functions declared in `extern` blocks.
     (active, simd_ffi, "1.0.0", Some(27731), None),
     /// Allows specialization of implementations (RFC 1210).
@@ -551,6 +556,8 @@ pub fn set(&self, features: &mut Features, span: Span) {
     (active, type_privacy_lints, "1.72.0", Some(48054), None),
     /// Enables rustc to generate code that instructs libstd to NOT ignore SIGPIPE.
     (active, unix_sigpipe, "1.65.0", Some(97889), None),
+    /// Allows using the `unnecessary_visibility` lint.
+    (active, unnecessary_visibility_lint, "CURRENT_RUSTC_VERSION", None, None),
     /// Allows unsized fn parameters.
     (active, unsized_fn_params, "1.49.0", Some(48055), None),
     /// Allows unsized rvalues at arguments and parameters.
@@ -839,10 +839,12 @@ fn analysis(tcx: TyCtxt<'_>, (): ()) -> Result<()> {
                 parallel!(
                     {
                         tcx.ensure().check_private_in_public(());
+                        tcx.ensure().check_unnecessary_visibility(());
                     },
                     {
                         tcx.hir()
                             .par_for_each_module(|module| tcx.ensure().check_mod_deathness(module));
+                        tcx.ensure().check_workspace_deathness(());
                     },
                     {
                         sess.time("lint_checking", || {
@@ -4,6 +4,7 @@
 use rustc_data_structures::fx::FxHashSet;
 use rustc_data_structures::profiling::TimePassesFormat;
 use rustc_errors::{emitter::HumanReadableErrorType, registry, ColorConfig};
+use rustc_session::config::lint_config::LintConfig;
 use rustc_session::config::rustc_optgroups;
 use rustc_session::config::DebugInfo;
 use rustc_session::config::Input;
@@ -315,6 +316,25 @@ fn test_lint_cap_hash_different() {
     assert_non_crate_hash_different(&v2, &v3);
 }
 
+#[test]
+fn test_lint_config_hash_different() {
+    let mut v1 = Options::default();
+    let mut v2 = Options::default();
+    let v3 = Options::default();
+
+    let config = |level| LintConfig {
+        path: PathBuf::from("lints.toml"),
+        lints: vec![("unsafe_code".to_string(), level)],
+        modules: vec![],
+    };
+    v1.lint_config = Some(config(Level::Deny));
+    v2.lint_config = Some(config(Level::Allow));
+
+    assert_non_crate_hash_different(&v1, &v2);
+    assert_non_crate_hash_different(&v1, &v3);
+    assert_non_crate_hash_different(&v2, &v3);
+}
+
 #[test]
 fn test_search_paths_tracking_hash_different_order() {
     let mut v1 = Options::default();
@@ -702,6 +722,7 @@ macro_rules! untracked {
     untracked!(input_stats, true);
     untracked!(keep_hygiene_data, true);
     untracked!(link_native_libraries, false);
+    untracked!(lint_config, Some(PathBuf::from("lints.toml")));
     untracked!(llvm_time_trace, true);
     untracked!(ls, true);
     untracked!(macro_backtrace, true);
@@ -855,6 +876,7 @@ macro_rules! tracked {
     tracked!(verify_llvm_ir, true);
     tracked!(virtual_function_elimination, true);
     tracked!(wasi_exec_model, Some(WasiExecModel::Reactor));
+    tracked!(workspace_dead_code, Some(vec![String::from("app")]));
     // tidy-alphabetical-end
 
     macro_rules! tracked_no_crate_hash {
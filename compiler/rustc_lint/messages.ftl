@@ -320,6 +320,8 @@ lint_invalid_nan_comparisons_eq_ne = incorrect NaN comparison, NaN cannot be dir
 
 lint_invalid_nan_comparisons_lt_le_gt_ge = incorrect NaN comparison, NaN is not orderable
 
+lint_lint_config_source = `forbid` lint level was set in the lint config file `{$path}`
+
 lint_lintpass_by_hand = implementing `LintPass` by hand
     .help = try using `declare_lint_pass!` or `impl_lint_pass!` instead
 
@@ -477,6 +479,8 @@ lint_renamed_or_removed_lint = {$msg}
 
 lint_requested_level = requested on the command line with `{$level} {$lint_name}`
 
+lint_requested_level_in_config = requested in `{$path}` with `{$lint_name} = "{$level}"`
+
 lint_supertrait_as_deref_target = `{$t}` implements `Deref` with supertrait `{$target_principal}` as target
     .label = target type is set here
 
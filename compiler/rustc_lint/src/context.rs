@@ -47,6 +47,7 @@
 
 use std::cell::Cell;
 use std::iter;
+use std::path::Path;
 use std::slice;
 
 type EarlyLintPassFactory = dyn Fn() -> EarlyLintPassObject + sync::DynSend + sync::DynSync;
@@ -336,37 +337,57 @@ pub fn check_lint_name_cmdline(
         level: Level,
         registered_tools: &RegisteredTools,
     ) {
-        let (tool_name, lint_name_only) = parse_lint_and_tool_name(lint_name);
+        let (_, lint_name_only) = parse_lint_and_tool_name(lint_name);
         if lint_name_only == crate::WARNINGS.name_lower() && matches!(level, Level::ForceWarn(_)) {
             sess.emit_err(UnsupportedGroup { lint_group: crate::WARNINGS.name_lower() });
             return;
         }
+        self.check_requested_lint_name(sess, lint_name, registered_tools, || {
+            RequestedLevel::CommandLine { level, lint_name: lint_name.to_string() }
+        });
+    }
+
+    /// Checks the validity of lint names read from the `-Z lint-config` file at `path`.
+    pub fn check_lint_name_config(
+        &self,
+        sess: &Session,
+        lint_name: &str,
+        level: Level,
+        registered_tools: &RegisteredTools,
+        path: &Path,
+    ) {
+        self.check_requested_lint_name(sess, lint_name, registered_tools, || {
+            RequestedLevel::LintConfig {
+                level: level.as_str(),
+                lint_name: lint_name.to_string(),
+                path: path.display().to_string(),
+            }
+        });
+    }
+
+    fn check_requested_lint_name(
+        &self,
+        sess: &Session,
+        lint_name: &str,
+        registered_tools: &RegisteredTools,
+        requested: impl Fn() -> RequestedLevel,
+    ) {
+        let (tool_name, lint_name_only) = parse_lint_and_tool_name(lint_name);
         let lint_name = lint_name.to_string();
         match self.check_lint_name(lint_name_only, tool_name, registered_tools) {
             CheckLintNameResult::Warning(msg, _) => {
-                sess.emit_warning(CheckNameWarning {
-                    msg,
-                    sub: RequestedLevel { level, lint_name },
-                });
+                sess.emit_warning(CheckNameWarning { msg, sub: requested() });
             }
             CheckLintNameResult::NoLint(suggestion) => {
-                sess.emit_err(CheckNameUnknown {
-                    lint_name: lint_name.clone(),
-                    suggestion,
-                    sub: RequestedLevel { level, lint_name },
-                });
+                sess.emit_err(CheckNameUnknown { lint_name, suggestion, sub: requested() });
             }
             CheckLintNameResult::Tool(Err((Some(_), new_name))) => {
-                sess.emit_warning(CheckNameDeprecated {
-                    lint_name: lint_name.clone(),
-                    new_name,
-                    sub: RequestedLevel { level, lint_name },
-                });
+                sess.emit_warning(CheckNameDeprecated { lint_name, new_name, sub: requested() });
             }
             CheckLintNameResult::NoTool => {
                 sess.emit_err(CheckNameUnknownTool {
                     tool_name: tool_name.unwrap(),
-                    sub: RequestedLevel { level, lint_name },
+                    sub: requested(),
                 });
             }
             _ => {}
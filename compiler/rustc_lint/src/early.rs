@@ -89,11 +89,16 @@ fn visit_param(&mut self, param: &'a ast::Param) {
     }
 
     fn visit_item(&mut self, it: &'a ast::Item) {
+        let module = matches!(it.kind, ast::ItemKind::Mod(..))
+            .then(|| self.context.builder.push_module(it.ident.name));
         self.with_lint_attrs(it.id, &it.attrs, |cx| {
             lint_callback!(cx, check_item, it);
             ast_visit::walk_item(cx, it);
             lint_callback!(cx, check_item_post, it);
-        })
+        });
+        if let Some(push) = module {
+            self.context.builder.pop_module(push);
+        }
     }
 
     fn visit_foreign_item(&mut self, it: &'a ast::ForeignItem) {
@@ -23,6 +23,7 @@ pub enum OverruledAttributeSub {
     DefaultSource { id: String },
     NodeSource { span: Span, reason: Option<Symbol> },
     CommandLineSource,
+    LintConfigSource { path: String },
 }
 
 impl AddToDiagnostic for OverruledAttributeSub {
@@ -45,6 +46,10 @@ fn add_to_diagnostic_with<F>(self, diag: &mut Diagnostic, _: F)
             OverruledAttributeSub::CommandLineSource => {
                 diag.note(fluent::lint_command_line_source);
             }
+            OverruledAttributeSub::LintConfigSource { path } => {
+                diag.note(fluent::lint_lint_config_source);
+                diag.set_arg("path", path);
+            }
         }
     }
 }
@@ -90,10 +95,11 @@ pub struct BuiltinEllipsisInclusiveRangePatterns {
 }
 
 #[derive(Subdiagnostic)]
-#[note(lint_requested_level)]
-pub struct RequestedLevel {
-    pub level: Level,
-    pub lint_name: String,
+pub enum RequestedLevel {
+    #[note(lint_requested_level)]
+    CommandLine { level: Level, lint_name: String },
+    #[note(lint_requested_level_in_config)]
+    LintConfig { level: &'static str, lint_name: String, path: String },
 }
 
 #[derive(Diagnostic)]
@@ -12,6 +12,8 @@
 use rustc_data_structures::fx::FxHashMap;
 use rustc_errors::{DecorateLint, DiagnosticBuilder, DiagnosticMessage, MultiSpan};
 use rustc_hir as hir;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::LocalDefId;
 use rustc_hir::intravisit::{self, Visitor};
 use rustc_hir::HirId;
 use rustc_index::IndexVec;
@@ -161,6 +163,8 @@ fn shallow_lint_levels_on(tcx: TyCtxt<'_>, owner: hir::OwnerId) -> ShallowLintLe
 
     if owner == hir::CRATE_OWNER_ID {
         levels.add_command_line();
+    } else if tcx.sess.opts.lint_config.is_some() && tcx.def_kind(owner) == DefKind::Mod {
+        levels.add_lint_config_module(&module_path(tcx, owner.def_id));
     }
 
     match attrs.map.range(..) {
@@ -193,9 +197,22 @@ fn shallow_lint_levels_on(tcx: TyCtxt<'_>, owner: hir::OwnerId) -> ShallowLintLe
     specs
 }
 
+/// The names of the modules leading from the crate root to the module `def_id`.
+fn module_path(tcx: TyCtxt<'_>, def_id: LocalDefId) -> Vec<Symbol> {
+    let mut path: Vec<_> =
+        std::iter::successors(Some(def_id.to_def_id()), |&id| tcx.opt_parent(id))
+            .filter(|&id| !id.is_crate_root() && tcx.def_kind(id) == DefKind::Mod)
+            .map(|id| tcx.item_name(id))
+            .collect();
+    path.reverse();
+    path
+}
+
 pub struct TopDown {
     sets: LintLevelSets,
     cur: LintStackIndex,
+    /// The names of the modules leading from the crate root to the current node.
+    module_path: Vec<Symbol>,
 }
 
 pub trait LintLevelsProvider {
@@ -453,7 +470,11 @@ pub(crate) fn new(
     ) -> Self {
         let mut builder = LintLevelsBuilder {
             sess,
-            provider: TopDown { sets: LintLevelSets::new(), cur: COMMAND_LINE },
+            provider: TopDown {
+                sets: LintLevelSets::new(),
+                cur: COMMAND_LINE,
+                module_path: Vec::new(),
+            },
             warn_about_weird_lints,
             store,
             registered_tools,
@@ -511,6 +532,34 @@ pub(crate) fn pop(&mut self, push: BuilderPush) {
         self.provider.cur = push.prev;
         std::mem::forget(push);
     }
+
+    /// Enters the module `name`, nested in the current module, and pushes the lint levels the
+    /// `-Z lint-config` file sets for it.
+    ///
+    /// This must be called before `push` is called with the attributes of the module, so they
+    /// take precedence. Don't forget to call `pop_module`!
+    pub(crate) fn push_module(&mut self, name: Symbol) -> BuilderPush {
+        self.provider.module_path.push(name);
+        let prev = self.provider.cur;
+        self.provider.cur =
+            self.provider.sets.list.push(LintSet { specs: FxHashMap::default(), parent: prev });
+
+        let path = self.provider.module_path.clone();
+        self.add_lint_config_module(&path);
+
+        if self.provider.current_specs().is_empty() {
+            self.provider.sets.list.pop();
+            self.provider.cur = prev;
+        }
+
+        BuilderPush { prev }
+    }
+
+    /// Called after `push_module` when the module is exited.
+    pub(crate) fn pop_module(&mut self, push: BuilderPush) {
+        self.provider.module_path.pop();
+        self.pop(push);
+    }
 }
 
 #[cfg(debug_assertions)]
@@ -538,6 +587,22 @@ fn insert(&mut self, id: LintId, lvl: LevelAndSource) {
     }
 
     fn add_command_line(&mut self) {
+        if let Some(config) = &self.sess.opts.lint_config {
+            let all_levels =
+                config.lints.iter().chain(config.modules.iter().flat_map(|(_, levels)| levels));
+            for &(ref lint_name, level) in all_levels {
+                self.store.check_lint_name_config(
+                    self.sess,
+                    lint_name,
+                    level,
+                    self.registered_tools,
+                    &config.path,
+                );
+            }
+            self.add_lint_config_levels(&config.lints, None);
+            self.add_lint_config_module(&[]);
+        }
+
         for &(ref lint_name, level) in &self.sess.opts.lint_opts {
             self.store.check_lint_name_cmdline(self.sess, &lint_name, level, self.registered_tools);
             let orig_level = level;
@@ -563,6 +628,59 @@ fn add_command_line(&mut self) {
         }
     }
 
+    /// Applies the lint levels the `-Z lint-config` file sets for the module at `path`, given as
+    /// the names of the modules leading to it from the crate root.
+    fn add_lint_config_module(&mut self, path: &[Symbol]) {
+        let Some(config) = &self.sess.opts.lint_config else { return };
+        for (pattern, levels) in &config.modules {
+            if pattern.matches(path) {
+                self.add_lint_config_levels(levels, Some(Symbol::intern(pattern.as_str())));
+            }
+        }
+    }
+
+    /// Applies lint levels read from the `-Z lint-config` file, set for the whole crate or for
+    /// the modules matching the pattern `module`.
+    ///
+    /// Lint groups are applied first, so that the levels of single lints take precedence over
+    /// the levels of the groups they are in. The levels of lints set on the command line are
+    /// left alone, as the command line takes precedence over the file in every module.
+    fn add_lint_config_levels(&mut self, levels: &[(String, Level)], module: Option<Symbol>) {
+        let (groups, lints): (Vec<_>, Vec<_>) = levels
+            .iter()
+            .partition(|(lint_name, _)| self.store.is_lint_group(Symbol::intern(lint_name)));
+        for &(ref lint_name, level) in groups.into_iter().chain(lints) {
+            let Ok(ids) = self.store.find_lints(&lint_name) else {
+                // errors handled in check_lint_name_config in add_command_line
+                continue;
+            };
+            let src =
+                LintLevelSource::LintConfig { name: Symbol::intern(lint_name), level, module };
+            for id in ids {
+                // ForceWarn and Forbid cannot be overridden
+                if let (Level::ForceWarn(_) | Level::Forbid, _) =
+                    self.provider.get_lint_level(id.lint, self.sess)
+                {
+                    continue;
+                }
+                if self.is_set_on_command_line(id) {
+                    continue;
+                }
+
+                if self.check_gated_lint(id, DUMMY_SP) {
+                    self.insert(id, (level, src));
+                }
+            }
+        }
+    }
+
+    /// Whether the level of `id` is set by the `-A`, `-W`, `-D`, `-F` or `--force-warn` flags.
+    fn is_set_on_command_line(&self, id: LintId) -> bool {
+        self.sess.opts.lint_opts.iter().any(|(lint_name, _)| {
+            self.store.find_lints(lint_name).is_ok_and(|ids| ids.contains(&id))
+        })
+    }
+
     /// Attempts to insert the `id` to `level_src` map entry. If unsuccessful
     /// (e.g. if a forbid was already inserted on the same scope), then emits a
     /// diagnostic with no change to `specs`.
@@ -590,6 +708,7 @@ fn insert_spec(&mut self, id: LintId, (mut level, src): LevelAndSource) {
                     LintLevelSource::Default => false,
                     LintLevelSource::Node { name, .. } => self.store.is_lint_group(name),
                     LintLevelSource::CommandLine(symbol, _) => self.store.is_lint_group(symbol),
+                    LintLevelSource::LintConfig { name, .. } => self.store.is_lint_group(name),
                 };
                 debug!(
                     "fcw_warning={:?}, specs.get(&id) = {:?}, old_src={:?}, id_name={:?}",
@@ -606,6 +725,12 @@ fn insert_spec(&mut self, id: LintId, (mut level, src): LevelAndSource) {
                         OverruledAttributeSub::NodeSource { span, reason }
                     }
                     LintLevelSource::CommandLine(_, _) => OverruledAttributeSub::CommandLineSource,
+                    LintLevelSource::LintConfig { .. } => {
+                        let config = self.sess.opts.lint_config.as_ref().unwrap();
+                        OverruledAttributeSub::LintConfigSource {
+                            path: config.path.display().to_string(),
+                        }
+                    }
                 };
                 if !fcw_warning {
                     self.sess.emit_err(OverruledAttribute {
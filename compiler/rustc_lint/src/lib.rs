@@ -92,7 +92,9 @@
 use rustc_middle::query::Providers;
 use rustc_middle::ty::TyCtxt;
 use rustc_session::lint::builtin::{
-    BARE_TRAIT_OBJECTS, ELIDED_LIFETIMES_IN_PATHS, EXPLICIT_OUTLIVES_REQUIREMENTS,
+    AUTO_TRAITS_FROM_PRIVATE_FIELDS, BARE_TRAIT_OBJECTS, ELIDED_LIFETIMES_IN_PATHS,
+    EXHAUSTIVE_PUBLIC_ENUMS, EXHAUSTIVE_PUBLIC_STRUCTS, EXPLICIT_OUTLIVES_REQUIREMENTS,
+    EXPORTED_PRIVATE_DEPENDENCIES,
 };
 use rustc_span::symbol::Ident;
 use rustc_span::Span;
@@ -319,6 +321,15 @@ macro_rules! add_lint_group {
 
     add_lint_group!("let_underscore", LET_UNDERSCORE_DROP, LET_UNDERSCORE_LOCK);
 
+    // `exported_private_dependencies` is left out: it warns by default on stable, and allowing
+    // the unstable group must not silence it.
+    add_lint_group!(
+        "semver_hazards",
+        EXHAUSTIVE_PUBLIC_STRUCTS,
+        EXHAUSTIVE_PUBLIC_ENUMS,
+        AUTO_TRAITS_FROM_PRIVATE_FIELDS
+    );
+
     add_lint_group!(
         "rust_2018_idioms",
         BARE_TRAIT_OBJECTS,
@@ -3319,6 +3319,7 @@
         AMBIGUOUS_GLOB_REEXPORTS,
         ARITHMETIC_OVERFLOW,
         ASM_SUB_REGISTER,
+        AUTO_TRAITS_FROM_PRIVATE_FIELDS,
         BAD_ASM_STYLE,
         BARE_TRAIT_OBJECTS,
         BINDINGS_WITH_VARIANT_NAME,
@@ -3336,6 +3337,8 @@
         DEPRECATED_WHERE_CLAUSE_LOCATION,
         DUPLICATE_MACRO_ATTRIBUTES,
         ELIDED_LIFETIMES_IN_PATHS,
+        EXHAUSTIVE_PUBLIC_ENUMS,
+        EXHAUSTIVE_PUBLIC_STRUCTS,
         EXPORTED_PRIVATE_DEPENDENCIES,
         FFI_UNWIND_CALLS,
         FORBIDDEN_LINT_GROUPS,
@@ -3403,6 +3406,7 @@
         UNKNOWN_DIAGNOSTIC_ATTRIBUTES,
         UNKNOWN_LINTS,
         UNNAMEABLE_TYPES,
+        UNNECESSARY_VISIBILITY,
         UNREACHABLE_CODE,
         UNREACHABLE_PATTERNS,
         UNSAFE_OP_IN_UNSAFE_FN,
@@ -4382,6 +4386,156 @@
     @feature_gate = sym::type_privacy_lints;
 }
 
+declare_lint! {
+    /// The `exhaustive_public_structs` lint detects public structs whose fields are all public
+    /// and which are not marked `#[non_exhaustive]`.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,compile_fail
+    /// # #![feature(semver_hazard_lints)]
+    /// #![deny(exhaustive_public_structs)]
+    /// pub struct Config {
+    ///     pub verbose: bool,
+    /// }
+    /// # fn main() {}
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// Other crates can construct a struct whose fields are all public with a struct expression,
+    /// and match on it without a `..` pattern, so adding a field to it later, even a private one,
+    /// is a breaking change. Marking the struct `#[non_exhaustive]` allows adding fields, at the
+    /// cost of other crates having to construct it with the functions it provides.
+    ///
+    /// This lint is "allow" by default because many public structs, such as plain data types,
+    /// are meant to be exhaustive. It is part of the `semver_hazards` lint group.
+    pub EXHAUSTIVE_PUBLIC_STRUCTS,
+    Allow,
+    "public structs whose fields are all public and which are not `#[non_exhaustive]`",
+    @feature_gate = sym::semver_hazard_lints;
+}
+
+declare_lint! {
+    /// The `exhaustive_public_enums` lint detects public enums which are not marked
+    /// `#[non_exhaustive]`.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,compile_fail
+    /// # #![feature(semver_hazard_lints)]
+    /// #![deny(exhaustive_public_enums)]
+    /// pub enum Error {
+    ///     NotFound,
+    ///     PermissionDenied,
+    /// }
+    /// # fn main() {}
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// Other crates can match on all the variants of an enum without a wildcard arm, so adding a
+    /// variant to it later is a breaking change. Marking the enum `#[non_exhaustive]` allows
+    /// adding variants, at the cost of other crates having to handle variants they don't know
+    /// about.
+    ///
+    /// This lint is "allow" by default because many public enums, such as `Option`, are meant to
+    /// be exhaustive. It is part of the `semver_hazards` lint group.
+    pub EXHAUSTIVE_PUBLIC_ENUMS,
+    Allow,
+    "public enums which are not `#[non_exhaustive]`",
+    @feature_gate = sym::semver_hazard_lints;
+}
+
+declare_lint! {
+    /// The `auto_traits_from_private_fields` lint detects public types which don't implement
+    /// `Send` or `Sync` because of the type of one of their private fields.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,compile_fail
+    /// # #![feature(semver_hazard_lints)]
+    /// #![deny(auto_traits_from_private_fields)]
+    /// use std::rc::Rc;
+    ///
+    /// pub struct Session {
+    ///     state: Rc<String>,
+    /// }
+    /// # fn main() {}
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// Auto traits like `Send` and `Sync` are implemented for a type if the types of all its
+    /// fields implement them, private fields included. Which auto traits a public type
+    /// implements is part of its public API, but it is easy to change by accident, by changing
+    /// the type of a private field. A type which is not `Send` or `Sync` on purpose can make it
+    /// explicit with a `PhantomData` field, such as `PhantomData<*const ()>`, which this lint
+    /// doesn't report.
+    ///
+    /// This lint is "allow" by default because it is often fine for a type not to be `Send` or
+    /// `Sync`. It is part of the `semver_hazards` lint group.
+    pub AUTO_TRAITS_FROM_PRIVATE_FIELDS,
+    Allow,
+    "public types which don't implement `Send` or `Sync` because of a private field",
+    @feature_gate = sym::semver_hazard_lints;
+}
+
+declare_lint! {
+    /// The `unnecessary_visibility` lint detects `pub`, `pub(crate)` and other restricted
+    /// visibilities which are wider than what the uses of the item in the crate need.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,compile_fail
+    /// # #![feature(unnecessary_visibility_lint)]
+    /// #![deny(unnecessary_visibility)]
+    /// mod parser {
+    ///     pub(crate) fn parse(input: &str) -> u32 {
+    ///         skip_whitespace(input).len() as u32
+    ///     }
+    ///
+    ///     pub(crate) fn skip_whitespace(input: &str) -> &str {
+    ///         input.trim_start()
+    ///     }
+    /// }
+    ///
+    /// fn main() {
+    ///     parser::parse(" 42");
+    /// }
+    /// ```
+    ///
+    /// {{produces}}
+    ///
+    /// ### Explanation
+    ///
+    /// Items which are not reachable from other crates only need to be visible to the modules
+    /// which use them. Keeping their visibility as narrow as possible makes it clear which
+    /// modules depend on an item.
+    ///
+    /// The lint suggests making an item private if it is only used in its own module, and
+    /// `pub(super)` if it is only used in its own module and the parent of that module.
+    /// Items which other crates can reach, re-exported items, and items used from the output
+    /// of function-like macros are never linted.
+    ///
+    /// Like `dead_code`, this lint only sees the code of the configuration being compiled. Uses
+    /// in code removed by `#[cfg]` are not taken into account, so applying the suggestion can
+    /// break other configurations of the crate.
+    ///
+    /// This lint is "allow" by default because narrowing visibilities is a matter of style
+    /// which not every crate follows.
+    pub UNNECESSARY_VISIBILITY,
+    Allow,
+    "visibility wider than what the uses of an item in the crate need",
+    @feature_gate = sym::unnecessary_visibility_lint;
+}
+
 declare_lint! {
     /// The `unknown_diagnostic_attributes` lint detects unrecognized diagnostic attributes.
     ///
@@ -359,6 +359,7 @@ fn into_args(self) -> (DefId, SimplifiedType) {
 
     used_crate_source => { Lrc::clone(&cdata.source) }
     debugger_visualizers => { cdata.get_debugger_visualizers() }
+    workspace_uses => { cdata.get_workspace_uses() }
 
     exported_symbols => {
         let syms = cdata.exported_symbols(tcx);
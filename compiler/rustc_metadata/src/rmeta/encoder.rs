@@ -19,6 +19,7 @@
 use rustc_hir::definitions::DefPathData;
 use rustc_hir::intravisit;
 use rustc_hir::lang_items::LangItem;
+use rustc_middle::middle::dead_code::WorkspaceUses;
 use rustc_middle::middle::debugger_visualizer::DebuggerVisualizerFile;
 use rustc_middle::middle::dependency_format::Linkage;
 use rustc_middle::middle::exported_symbols::{
@@ -603,6 +604,8 @@ macro_rules! stat {
 
         let incoherent_impls = stat!("incoherent-impls", || self.encode_incoherent_impls());
 
+        let workspace_uses = stat!("workspace-uses", || self.encode_workspace_uses());
+
         _ = stat!("mir", || self.encode_mir());
 
         _ = stat!("def-ids", || self.encode_def_ids());
@@ -702,6 +705,7 @@ macro_rules! stat {
                 traits,
                 impls,
                 incoherent_impls,
+                workspace_uses,
                 exported_symbols,
                 interpret_alloc_index,
                 tables,
@@ -1832,6 +1836,14 @@ fn encode_debugger_visualizers(&mut self) -> LazyArray<DebuggerVisualizerFile> {
         )
     }
 
+    fn encode_workspace_uses(&mut self) -> Option<LazyValue<WorkspaceUses>> {
+        if self.is_proc_macro {
+            return None;
+        }
+        let uses = self.tcx.workspace_uses(LOCAL_CRATE).as_ref()?;
+        Some(self.lazy(uses))
+    }
+
     fn encode_crate_deps(&mut self) -> LazyArray<CrateDep> {
         empty_proc_macro!(self);
 
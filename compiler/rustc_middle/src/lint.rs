@@ -1,4 +1,5 @@
 use std::cmp;
+use std::path::Path;
 
 use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::sorted_map::SortedMap;
@@ -34,6 +35,10 @@ pub enum LintLevelSource {
     /// The provided `Level` is the level specified on the command line.
     /// (The actual level may be lower due to `--cap-lints`.)
     CommandLine(Symbol, Level),
+
+    /// Lint level was set by the file passed to `-Z lint-config`, for the whole crate or, if
+    /// `module` is set, for the modules matching that pattern.
+    LintConfig { name: Symbol, level: Level, module: Option<Symbol> },
 }
 
 impl LintLevelSource {
@@ -42,6 +47,7 @@ pub fn name(&self) -> Symbol {
             LintLevelSource::Default => symbol::kw::Default,
             LintLevelSource::Node { name, .. } => name,
             LintLevelSource::CommandLine(name, _) => name,
+            LintLevelSource::LintConfig { name, .. } => name,
         }
     }
 
@@ -50,6 +56,7 @@ pub fn span(&self) -> Span {
             LintLevelSource::Default => DUMMY_SP,
             LintLevelSource::Node { span, .. } => span,
             LintLevelSource::CommandLine(_, _) => DUMMY_SP,
+            LintLevelSource::LintConfig { .. } => DUMMY_SP,
         }
     }
 }
@@ -203,6 +210,7 @@ pub fn new(
 }
 
 pub fn explain_lint_level_source(
+    sess: &Session,
     lint: &'static Lint,
     level: Level,
     src: LintLevelSource,
@@ -229,6 +237,23 @@ pub fn explain_lint_level_source(
                 ));
             }
         }
+        LintLevelSource::LintConfig { name: lint_config_name, level: orig_level, module } => {
+            let path = sess.opts.lint_config.as_ref().map_or(Path::new(""), |config| &config.path);
+            let level_str = orig_level.as_str();
+            let requested = if lint_config_name.as_str() == name {
+                format!("requested in `{}` with `{name} = \"{level_str}\"`", path.display())
+            } else {
+                format!(
+                    "`{name} = \"{level_str}\"` implied by `{lint_config_name} = \"{level_str}\"` in `{}`",
+                    path.display()
+                )
+            };
+            if let Some(module) = module {
+                err.note_once(format!("{requested} for modules matching `{module}`"));
+            } else {
+                err.note_once(requested);
+            }
+        }
         LintLevelSource::Node { name: lint_attr_name, span, reason, .. } => {
             if let Some(rationale) = reason {
                 err.note(rationale.to_string());
@@ -438,7 +463,7 @@ fn struct_lint_level_impl(
 
         // Finally, run `decorate`.
         decorate(&mut err);
-        explain_lint_level_source(lint, level, src, &mut *err);
+        explain_lint_level_source(sess, lint, level, src, &mut *err);
         err.emit()
     }
     struct_lint_level_impl(sess, lint, level, src, span, msg, Box::new(decorate))
@@ -0,0 +1,16 @@
+use rustc_hir::def_id::DefId;
+
+/// The uses of items recorded in the metadata of a crate built with `-Zworkspace-dead-code`.
+///
+/// The final crates of a workspace put together the uses recorded by all the crates they depend
+/// on to find the public items of the workspace libraries which no crate uses.
+#[derive(HashStable, Clone, Debug, Default, Encodable, Decodable)]
+pub struct WorkspaceUses {
+    /// The items which are used no matter which of the public items of the crate are used, such
+    /// as the entry point, trait impls and items marked `#[allow(dead_code)]`.
+    pub roots: Vec<DefId>,
+    /// The items of this and upstream crates used by each item of this crate.
+    pub uses: Vec<(DefId, Vec<DefId>)>,
+    /// The public items of the crate which are reported if no crate uses them.
+    pub exported: Vec<DefId>,
+}
@@ -12,6 +12,7 @@
 use crate::lint::LintExpectation;
 use crate::metadata::ModChild;
 use crate::middle::codegen_fn_attrs::CodegenFnAttrs;
+use crate::middle::dead_code::WorkspaceUses;
 use crate::middle::debugger_visualizer::DebuggerVisualizerFile;
 use crate::middle::exported_symbols::{ExportedSymbol, SymbolExportInfo};
 use crate::middle::lib_features::LibFeatures;
@@ -954,6 +955,20 @@
         desc { |tcx| "checking deathness of variables in {}", describe_as_module(key, tcx) }
     }
 
+    /// The uses of items recorded in the metadata of a crate built with `-Zworkspace-dead-code`,
+    /// or `None` if it was built without it.
+    query workspace_uses(_: CrateNum) -> &'tcx Option<WorkspaceUses> {
+        arena_cache
+        desc { "finding the uses of items for workspace dead code check" }
+        separate_provide_extern
+    }
+
+    /// Reports the public items of the workspace libraries which no crate uses, if this is one
+    /// of the final crates listed in `-Zworkspace-dead-code`.
+    query check_workspace_deathness(_: ()) -> () {
+        desc { "checking deathness of public items in the workspace" }
+    }
+
     query check_mod_impl_wf(key: LocalDefId) -> () {
         desc { |tcx| "checking that impls are well-formed in {}", describe_as_module(key, tcx) }
     }
@@ -1125,6 +1140,10 @@
         eval_always
         desc { "checking for private elements in public interfaces" }
     }
+    query check_unnecessary_visibility(_: ()) -> () {
+        eval_always
+        desc { "checking for visibilities wider than their uses need" }
+    }
 
     query reachable_set(_: ()) -> &'tcx LocalDefIdSet {
         arena_cache
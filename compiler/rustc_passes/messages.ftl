@@ -222,6 +222,9 @@ passes_doc_masked_only_extern_crate =
     .not_an_extern_crate_label = not an `extern crate` item
     .note = read <https://doc.rust-lang.org/unstable-book/language-features/doc-masked.html> for more information
 
+passes_doc_since_takes_string =
+    `#[doc(since = "...")]` takes a version string
+
 passes_doc_test_literal = `#![doc(test(...)]` does not take a literal
 
 passes_doc_test_takes_list =
@@ -744,6 +747,10 @@ passes_unused_duplicate =
 passes_unused_empty_lints_note =
     attribute `{$name}` with an empty list has no effect
 
+passes_unused_in_workspace =
+    {$descr} `{$path}` is never used by any crate in the workspace
+    .note = `{$krate}` was built with `-Zworkspace-dead-code`, and neither it nor any crate depending on it uses this {$descr}
+
 passes_unused_multiple =
     multiple `{$name}` attributes
     .suggestion = remove this attribute
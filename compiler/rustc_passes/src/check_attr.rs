@@ -1014,6 +1014,21 @@ fn check_doc_cfg_hide(&self, meta: &NestedMetaItem, hir_id: HirId) -> bool {
         }
     }
 
+    /// Checks that `#[doc(since = "...")]` is given a string value. Returns `true` if valid.
+    fn check_doc_since(&self, meta: &NestedMetaItem, hir_id: HirId) -> bool {
+        if meta.value_str().is_some() {
+            true
+        } else {
+            self.tcx.emit_spanned_lint(
+                INVALID_DOC_ATTRIBUTES,
+                hir_id,
+                meta.span(),
+                errors::DocSinceTakesString,
+            );
+            false
+        }
+    }
+
     /// Runs various checks on `#[doc]` attributes. Returns `true` if valid.
     ///
     /// `specified_inline` should be initialized to `None` and kept for the scope
@@ -1074,6 +1089,13 @@ fn check_doc_attrs(
                             is_valid = false;
                         }
 
+                        sym::since
+                            if !self.check_attr_not_crate_level(meta, hir_id, "since")
+                                || !self.check_doc_since(meta, hir_id) =>
+                        {
+                            is_valid = false;
+                        }
+
                         sym::inline | sym::no_inline
                             if !self.check_doc_inline(
                                 attr,
@@ -1118,6 +1140,7 @@ fn check_doc_attrs(
                         | sym::notable_trait
                         | sym::passes
                         | sym::plugins
+                        | sym::since
                         | sym::fake_variadic => {}
 
                         sym::test => {
@@ -4,16 +4,19 @@
 
 use hir::def_id::{LocalDefIdMap, LocalDefIdSet};
 use itertools::Itertools;
+use rustc_data_structures::fx::{FxIndexMap, FxIndexSet};
 use rustc_errors::MultiSpan;
 use rustc_hir as hir;
 use rustc_hir::def::{CtorOf, DefKind, Res};
-use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::def_id::{DefId, DefIdMap, DefIdSet, LocalDefId, LOCAL_CRATE};
 use rustc_hir::intravisit::{self, Visitor};
-use rustc_hir::{Node, PatKind, TyKind};
+use rustc_hir::{Node, PatKind, TyKind, CRATE_HIR_ID};
 use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
+use rustc_middle::middle::dead_code::WorkspaceUses;
 use rustc_middle::middle::privacy::Level;
-use rustc_middle::query::Providers;
+use rustc_middle::query::{LocalCrate, Providers};
 use rustc_middle::ty::{self, TyCtxt};
+use rustc_session::config::CrateType;
 use rustc_session::lint;
 use rustc_span::symbol::{sym, Symbol};
 use rustc_target::abi::FieldIdx;
@@ -21,7 +24,7 @@
 
 use crate::errors::{
     ChangeFieldsToBeOfUnitType, IgnoredDerivedImpls, MultipleDeadCodes, ParentInfo,
-    UselessAssignment,
+    UnusedInWorkspace, UselessAssignment,
 };
 
 // Any local node that may call something in its body block should be
@@ -57,6 +60,10 @@ struct MarkSymbolVisitor<'tcx> {
     // and the span of their respective impl (i.e., part of the derive
     // macro)
     ignored_derived_traits: LocalDefIdMap<Vec<(DefId, DefId)>>,
+    // the items used by each item, recorded for `-Zworkspace-dead-code`
+    // (see `workspace_uses`), and the item being visited
+    uses: Option<FxIndexMap<LocalDefId, FxIndexSet<DefId>>>,
+    current_owner: Option<LocalDefId>,
 }
 
 impl<'tcx> MarkSymbolVisitor<'tcx> {
@@ -70,6 +77,7 @@ fn typeck_results(&self) -> &'tcx ty::TypeckResults<'tcx> {
     }
 
     fn check_def_id(&mut self, def_id: DefId) {
+        self.record_use(def_id);
         if let Some(def_id) = def_id.as_local() {
             if should_explore(self.tcx, def_id) || self.struct_constructors.contains_key(&def_id) {
                 self.worklist.push(def_id);
@@ -85,6 +93,25 @@ fn insert_def_id(&mut self, def_id: DefId) {
         }
     }
 
+    fn record_use(&mut self, def_id: DefId) {
+        let Some(uses) = &mut self.uses else { return };
+        let Some(owner) = self.current_owner else { return };
+        let def_id = match self.tcx.def_kind(def_id) {
+            DefKind::Ctor(CtorOf::Struct, _) => self.tcx.parent(def_id),
+            // variants are recorded as uses of their enum by `handle_res`
+            DefKind::Ctor(CtorOf::Variant, _)
+            | DefKind::Variant
+            | DefKind::Field
+            | DefKind::TyParam
+            | DefKind::ConstParam
+            | DefKind::LifetimeParam => return,
+            _ => def_id,
+        };
+        if def_id != owner.to_def_id() {
+            uses.entry(owner).or_default().insert(def_id);
+        }
+    }
+
     fn handle_res(&mut self, res: Res) {
         match res {
             Res::Def(DefKind::Const | DefKind::AssocConst | DefKind::TyAlias, def_id) => {
@@ -287,6 +314,10 @@ fn mark_live_symbols(&mut self) {
 
             if let Some(node) = self.tcx.hir().find_by_def_id(id) {
                 self.live_symbols.insert(id);
+                if self.uses.is_some() {
+                    self.current_owner =
+                        Some(self.tcx.hir().local_def_id_to_hir_id(id).owner.def_id);
+                }
                 self.visit_node(node);
             }
         }
@@ -635,32 +666,39 @@ fn check_foreign_item(tcx: TyCtxt<'_>, worklist: &mut Vec<LocalDefId>, id: hir::
     }
 }
 
-fn create_and_seed_worklist(tcx: TyCtxt<'_>) -> (Vec<LocalDefId>, LocalDefIdMap<LocalDefId>) {
-    let effective_visibilities = &tcx.effective_visibilities(());
+fn public_items(tcx: TyCtxt<'_>) -> impl Iterator<Item = LocalDefId> + '_ {
+    tcx.effective_visibilities(()).iter().filter_map(|(&id, effective_vis)| {
+        effective_vis.is_public_at_level(Level::Reachable).then_some(id)
+    })
+}
+
+/// Seeds the worklist with the items which are live whether they are public or not.
+fn seed_worklist(tcx: TyCtxt<'_>, worklist: &mut Vec<LocalDefId>) -> LocalDefIdMap<LocalDefId> {
     // see `MarkSymbolVisitor::struct_constructors`
     let mut struct_constructors = Default::default();
-    let mut worklist = effective_visibilities
-        .iter()
-        .filter_map(|(&id, effective_vis)| {
-            effective_vis.is_public_at_level(Level::Reachable).then_some(id)
-        })
-        // Seed entry point
-        .chain(tcx.entry_fn(()).and_then(|(def_id, _)| def_id.as_local()))
-        .collect::<Vec<_>>();
+
+    // Seed entry point
+    worklist.extend(tcx.entry_fn(()).and_then(|(def_id, _)| def_id.as_local()));
 
     let crate_items = tcx.hir_crate_items(());
     for id in crate_items.items() {
-        check_item(tcx, &mut worklist, &mut struct_constructors, id);
+        check_item(tcx, worklist, &mut struct_constructors, id);
     }
 
     for id in crate_items.trait_items() {
-        check_trait_item(tcx, &mut worklist, id);
+        check_trait_item(tcx, worklist, id);
     }
 
     for id in crate_items.foreign_items() {
-        check_foreign_item(tcx, &mut worklist, id);
+        check_foreign_item(tcx, worklist, id);
     }
 
+    struct_constructors
+}
+
+fn create_and_seed_worklist(tcx: TyCtxt<'_>) -> (Vec<LocalDefId>, LocalDefIdMap<LocalDefId>) {
+    let mut worklist = public_items(tcx).collect::<Vec<_>>();
+    let struct_constructors = seed_worklist(tcx, &mut worklist);
     (worklist, struct_constructors)
 }
 
@@ -680,11 +718,153 @@ fn live_symbols_and_ignored_derived_traits(
         ignore_variant_stack: vec![],
         struct_constructors,
         ignored_derived_traits: Default::default(),
+        uses: None,
+        current_owner: None,
     };
     symbol_visitor.mark_live_symbols();
     (symbol_visitor.live_symbols, symbol_visitor.ignored_derived_traits)
 }
 
+/// Whether the local crate is one of the final crates `-Zworkspace-dead-code` lists.
+///
+/// The library and the binaries of a Cargo package share a crate name, so only crates no other
+/// crate can depend on, such as executables, are final.
+fn is_final_crate(tcx: TyCtxt<'_>, final_crates: &[String]) -> bool {
+    let crate_name = tcx.crate_name(LOCAL_CRATE);
+    final_crates.iter().any(|name| *name == crate_name.as_str())
+        && tcx.sess.crate_types().iter().all(|crate_type| {
+            matches!(crate_type, CrateType::Executable | CrateType::Staticlib | CrateType::Cdylib)
+        })
+}
+
+/// Whether `def_id` is a public item which `-Zworkspace-dead-code` reports if no crate uses it.
+fn is_reported_in_workspace(tcx: TyCtxt<'_>, def_id: LocalDefId) -> bool {
+    let reported = match tcx.def_kind(def_id) {
+        DefKind::Fn
+        | DefKind::Const
+        | DefKind::Static(_)
+        | DefKind::Struct
+        | DefKind::Enum
+        | DefKind::Union
+        | DefKind::TyAlias
+        | DefKind::Trait => true,
+        // Trait items are used through the trait, and trait impl items are always live.
+        DefKind::AssocFn | DefKind::AssocConst => {
+            matches!(tcx.def_kind(tcx.local_parent(def_id)), DefKind::Impl { of_trait: false })
+        }
+        _ => false,
+    };
+    reported
+        && tcx.opt_item_name(def_id.to_def_id()).is_some_and(|name| !name.as_str().starts_with('_'))
+        && !has_allow_dead_code_or_lang_attr(tcx, def_id)
+}
+
+/// Records which items each item of the crate uses, so that the final crates of the workspace
+/// can find the public items no crate uses.
+///
+/// Unlike `live_symbols_and_ignored_derived_traits`, which considers all public items live, the
+/// public items of a library are only live if some crate of the workspace uses them. The public
+/// items of a final crate are still all live, as other crates could use them.
+fn workspace_uses(tcx: TyCtxt<'_>, _: LocalCrate) -> Option<WorkspaceUses> {
+    let final_crates = tcx.sess.opts.unstable_opts.workspace_dead_code.as_deref()?;
+    let is_final = is_final_crate(tcx, final_crates);
+
+    let mut roots = Vec::new();
+    let struct_constructors = seed_worklist(tcx, &mut roots);
+    if is_final {
+        roots.extend(public_items(tcx));
+    }
+
+    // Public items are still explored, as downstream crates may use them.
+    let worklist = public_items(tcx).chain(roots.iter().copied()).collect();
+    let mut symbol_visitor = MarkSymbolVisitor {
+        worklist,
+        tcx,
+        maybe_typeck_results: None,
+        live_symbols: Default::default(),
+        repr_has_repr_c: false,
+        repr_has_repr_simd: false,
+        in_pat: false,
+        ignore_variant_stack: vec![],
+        struct_constructors,
+        ignored_derived_traits: Default::default(),
+        uses: Some(Default::default()),
+        current_owner: None,
+    };
+    symbol_visitor.mark_live_symbols();
+
+    let mut exported = Vec::new();
+    if !is_final {
+        let effective_visibilities = tcx.effective_visibilities(());
+        exported.extend(effective_visibilities.iter().map(|(&id, _)| id).filter(|&id| {
+            effective_visibilities.is_exported(id) && is_reported_in_workspace(tcx, id)
+        }));
+        exported.sort_by_key(|id| id.local_def_index);
+    }
+
+    let owner = |id: LocalDefId| tcx.hir().local_def_id_to_hir_id(id).owner.to_def_id();
+    Some(WorkspaceUses {
+        roots: roots.into_iter().map(owner).collect(),
+        uses: symbol_visitor
+            .uses
+            .unwrap()
+            .into_iter()
+            .map(|(id, used)| (id.to_def_id(), used.into_iter().collect()))
+            .collect(),
+        exported: exported.into_iter().map(LocalDefId::to_def_id).collect(),
+    })
+}
+
+/// Reports the public items of the libraries built with `-Zworkspace-dead-code` which no crate
+/// uses, when this is one of the final crates it lists.
+fn check_workspace_deathness(tcx: TyCtxt<'_>, (): ()) {
+    let Some(final_crates) = tcx.sess.opts.unstable_opts.workspace_dead_code.as_deref() else {
+        return;
+    };
+    if !is_final_crate(tcx, final_crates) {
+        return;
+    }
+
+    let crates: Vec<_> = std::iter::once(LOCAL_CRATE)
+        .chain(tcx.crates(()).iter().copied())
+        .filter_map(|cnum| Some((cnum, tcx.workspace_uses(cnum).as_ref()?)))
+        .collect();
+
+    let mut uses = DefIdMap::default();
+    let mut worklist = Vec::new();
+    for (_, crate_uses) in &crates {
+        worklist.extend(crate_uses.roots.iter().copied());
+        uses.extend(crate_uses.uses.iter().map(|(id, used)| (*id, used)));
+    }
+    let mut live = DefIdSet::default();
+    while let Some(id) = worklist.pop() {
+        if live.insert(id)
+            && let Some(used) = uses.get(&id)
+        {
+            worklist.extend(used.iter().copied());
+        }
+    }
+
+    for (cnum, crate_uses) in crates {
+        for &def_id in &crate_uses.exported {
+            if live.contains(&def_id) {
+                continue;
+            }
+            let span = tcx.def_ident_span(def_id).unwrap_or_else(|| tcx.def_span(def_id));
+            tcx.emit_spanned_lint(
+                lint::builtin::DEAD_CODE,
+                CRATE_HIR_ID,
+                span,
+                UnusedInWorkspace {
+                    descr: tcx.def_descr(def_id),
+                    path: tcx.def_path_str(def_id),
+                    krate: tcx.crate_name(cnum),
+                },
+            );
+        }
+    }
+}
+
 struct DeadVariant {
     def_id: LocalDefId,
     name: Symbol,
@@ -979,6 +1159,11 @@ fn check_mod_deathness(tcx: TyCtxt<'_>, module: LocalDefId) {
 }
 
 pub(crate) fn provide(providers: &mut Providers) {
-    *providers =
-        Providers { live_symbols_and_ignored_derived_traits, check_mod_deathness, ..*providers };
+    *providers = Providers {
+        live_symbols_and_ignored_derived_traits,
+        check_mod_deathness,
+        workspace_uses,
+        check_workspace_deathness,
+        ..*providers
+    };
 }
@@ -312,6 +312,10 @@ pub struct DocTestUnknown {
 #[diag(passes_doc_cfg_hide_takes_list)]
 pub struct DocCfgHideTakesList;
 
+#[derive(LintDiagnostic)]
+#[diag(passes_doc_since_takes_string)]
+pub struct DocSinceTakesString;
+
 #[derive(LintDiagnostic)]
 #[diag(passes_doc_test_unknown_any)]
 pub struct DocTestUnknownAny {
@@ -1582,6 +1586,15 @@ pub struct ChangeFieldsToBeOfUnitType {
     pub spans: Vec<Span>,
 }
 
+#[derive(LintDiagnostic)]
+#[diag(passes_unused_in_workspace)]
+#[note]
+pub struct UnusedInWorkspace<'tcx> {
+    pub descr: &'tcx str,
+    pub path: String,
+    pub krate: Symbol,
+}
+
 #[derive(Diagnostic)]
 #[diag(passes_proc_macro_bad_sig)]
 pub(crate) struct ProcMacroBadSig {
@@ -1,3 +1,14 @@
+privacy_auto_trait_from_private_field_lint =
+    {$kind} `{$descr}` is not `{$trait_name}` because of its private field `{$field_name}`
+    .note = which auto traits a public type implements is part of its public API
+    .help = if `{$descr}` is not meant to be `{$trait_name}`, make it explicit with a `PhantomData` field, such as `PhantomData<*const ()>`
+
+privacy_exhaustive_public_enum_lint = enum `{$descr}` is exhaustive, so adding a variant to it is a breaking change
+    .help = mark it `#[non_exhaustive]` if variants may be added to it later
+
+privacy_exhaustive_public_struct_lint = struct `{$descr}` is exhaustive, so adding a field to it is a breaking change
+    .help = mark it `#[non_exhaustive]` if fields may be added to it later
+
 privacy_field_is_private = field `{$field_name}` of {$variant_descr} `{$def_path_str}` is private
 privacy_field_is_private_is_update_syntax_label = field `{$field_name}` is private
 privacy_field_is_private_label = private field
@@ -28,3 +39,12 @@ privacy_unnameable_types_lint = {$kind} `{$descr}` is reachable but cannot be na
 
 privacy_unnamed_item_is_private = {$kind} is private
     .label = private {$kind}
+
+privacy_unnecessary_visibility_lint = {$kind} `{$descr}` is `{$vis}` but is not used outside of {$narrowed ->
+        [private] its module
+        *[super] its parent module
+    }
+    .suggestion = {$narrowed ->
+        [private] remove the visibility
+        *[super] restrict the visibility to the parent module
+    }
@@ -110,6 +110,43 @@ pub struct UnnameableTypesLint<'a> {
     pub reexported_vis: &'a str,
 }
 
+#[derive(LintDiagnostic)]
+#[diag(privacy_unnecessary_visibility_lint)]
+pub struct UnnecessaryVisibilityLint<'a> {
+    pub kind: &'a str,
+    pub descr: DiagnosticArgFromDisplay<'a>,
+    pub vis: &'a str,
+    pub narrowed: &'static str,
+    #[suggestion(code = "{replacement}", applicability = "machine-applicable")]
+    pub suggestion: Span,
+    pub replacement: &'static str,
+}
+
+#[derive(LintDiagnostic)]
+#[diag(privacy_exhaustive_public_struct_lint)]
+#[help]
+pub struct ExhaustivePublicStructLint<'a> {
+    pub descr: DiagnosticArgFromDisplay<'a>,
+}
+
+#[derive(LintDiagnostic)]
+#[diag(privacy_exhaustive_public_enum_lint)]
+#[help]
+pub struct ExhaustivePublicEnumLint<'a> {
+    pub descr: DiagnosticArgFromDisplay<'a>,
+}
+
+#[derive(LintDiagnostic)]
+#[diag(privacy_auto_trait_from_private_field_lint)]
+#[note]
+#[help]
+pub struct AutoTraitFromPrivateFieldLint<'a> {
+    pub kind: &'a str,
+    pub descr: DiagnosticArgFromDisplay<'a>,
+    pub trait_name: Symbol,
+    pub field_name: Symbol,
+}
+
 // Used for `private_interfaces` and `private_bounds` lints.
 // They will replace private-in-public errors and compatibility lints in future.
 // See https://rust-lang.github.io/rfcs/2145-type-privacy.html for more details.
@@ -12,17 +12,18 @@
 
 mod errors;
 
-use rustc_ast::MacroDef;
+use rustc_ast::{Attribute, MacroDef};
 use rustc_attr as attr;
-use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::intern::Interned;
 use rustc_errors::{DiagnosticMessage, SubdiagnosticMessage};
 use rustc_fluent_macro::fluent_messages;
 use rustc_hir as hir;
-use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def::{CtorOf, DefKind, Res};
 use rustc_hir::def_id::{DefId, LocalDefId, CRATE_DEF_ID};
 use rustc_hir::intravisit::{self, Visitor};
 use rustc_hir::{AssocItemKind, ForeignItemKind, HirIdSet, ItemId, Node, PatKind};
+use rustc_infer::infer::TyCtxtInferExt;
 use rustc_middle::bug;
 use rustc_middle::hir::nested_filter;
 use rustc_middle::middle::privacy::{EffectiveVisibilities, EffectiveVisibility, Level};
@@ -30,20 +31,26 @@
 use rustc_middle::span_bug;
 use rustc_middle::ty::GenericArgs;
 use rustc_middle::ty::{self, Const, GenericParamDefKind};
-use rustc_middle::ty::{TraitRef, Ty, TyCtxt, TypeSuperVisitable, TypeVisitable, TypeVisitor};
+use rustc_middle::ty::{
+    TraitRef, Ty, TyCtxt, TypeSuperVisitable, TypeVisitable, TypeVisitableExt, TypeVisitor,
+};
+use rustc_session::config::CrateType;
 use rustc_session::lint;
-use rustc_span::hygiene::Transparency;
+use rustc_span::hygiene::{ExpnKind, MacroKind, Transparency};
 use rustc_span::symbol::{kw, sym, Ident};
 use rustc_span::Span;
+use rustc_trait_selection::infer::InferCtxtExt;
 
 use std::marker::PhantomData;
 use std::ops::ControlFlow;
 use std::{fmt, mem};
 
 use errors::{
+    AutoTraitFromPrivateFieldLint, ExhaustivePublicEnumLint, ExhaustivePublicStructLint,
     FieldIsPrivate, FieldIsPrivateLabel, FromPrivateDependencyInPublicInterface, InPublicInterface,
     InPublicInterfaceTraits, ItemIsPrivate, PrivateInPublicLint, PrivateInterfacesOrBoundsLint,
     ReportEffectiveVisibility, UnnameableTypesLint, UnnamedItemIsPrivate,
+    UnnecessaryVisibilityLint,
 };
 
 fluent_messages! { "../messages.ftl" }
@@ -311,6 +318,25 @@ fn min(vis1: ty::Visibility, vis2: ty::Visibility, tcx: TyCtxt<'_>) -> ty::Visib
     if vis1.is_at_least(vis2, tcx) { vis2 } else { vis1 }
 }
 
+/// Returns the narrowest visibility which is at least as wide as both `vis1` and `vis2`.
+fn max(vis1: ty::Visibility, vis2: ty::Visibility, tcx: TyCtxt<'_>) -> ty::Visibility {
+    if vis1.is_at_least(vis2, tcx) {
+        return vis1;
+    }
+    if vis2.is_at_least(vis1, tcx) {
+        return vis2;
+    }
+    // Neither visibility is public, so both are restricted to modules of the local crate.
+    let (ty::Visibility::Restricted(mut module), ty::Visibility::Restricted(other)) = (vis1, vis2)
+    else {
+        bug!("public visibility is at least as wide as any other");
+    };
+    while !tcx.is_descendant_of(other.to_def_id(), module.to_def_id()) {
+        module = tcx.parent_module_from_def_id(module);
+    }
+    ty::Visibility::Restricted(module)
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 /// Visitor used to determine impl visibility and reachability.
 ////////////////////////////////////////////////////////////////////////////////
@@ -1959,6 +1985,103 @@ fn check_unnameable(&self, def_id: LocalDefId, effective_vis: Option<EffectiveVi
         }
     }
 
+    /// Checks an exported struct, union or enum for the patterns which make changes to it
+    /// breaking, reported by the lints of the `semver_hazards` group.
+    fn check_semver_hazards(&self, def_id: LocalDefId) {
+        if !self.effective_visibilities.is_exported(def_id) {
+            return;
+        }
+
+        let tcx = self.tcx;
+        let adt = tcx.adt_def(def_id);
+        let hir_id = tcx.hir().local_def_id_to_hir_id(def_id);
+        let span = tcx.def_span(def_id);
+        let descr = LazyDefPathStr { def_id: def_id.to_def_id(), tcx };
+
+        if adt.is_enum() {
+            if !adt.variants().is_empty() && !adt.is_variant_list_non_exhaustive() {
+                tcx.emit_spanned_lint(
+                    lint::builtin::EXHAUSTIVE_PUBLIC_ENUMS,
+                    hir_id,
+                    span,
+                    ExhaustivePublicEnumLint { descr: (&descr).into() },
+                );
+            }
+            return;
+        }
+
+        let variant = adt.non_enum_variant();
+        let private_fields: Vec<_> =
+            variant.fields.iter().filter(|field| !field.vis.is_public()).collect();
+        if adt.is_struct() && private_fields.is_empty() && !variant.is_field_list_non_exhaustive() {
+            tcx.emit_spanned_lint(
+                lint::builtin::EXHAUSTIVE_PUBLIC_STRUCTS,
+                hir_id,
+                span,
+                ExhaustivePublicStructLint { descr: (&descr).into() },
+            );
+        }
+
+        let param_env = tcx.param_env(def_id);
+        let infcx = tcx.infer_ctxt().build();
+        for trait_name in [sym::Send, sym::Sync] {
+            let Some(trait_def_id) = tcx.get_diagnostic_item(trait_name) else {
+                continue;
+            };
+
+            // An explicit impl, positive or negative, decides whether the type implements the
+            // trait on purpose.
+            let has_explicit_impl =
+                tcx.all_local_trait_impls(()).get(&trait_def_id).is_some_and(|impls| {
+                    impls.iter().any(|&impl_def_id| {
+                        let self_ty = tcx.type_of(impl_def_id).instantiate_identity();
+                        self_ty.ty_adt_def().is_some_and(|self_adt| self_adt.did() == adt.did())
+                    })
+                });
+            if has_explicit_impl {
+                continue;
+            }
+
+            let mut field_missing_trait = None;
+            for &field in &private_fields {
+                let field_ty = tcx.type_of(field.did).instantiate_identity();
+                let Ok(field_ty) = tcx.try_normalize_erasing_regions(param_env, field_ty) else {
+                    continue;
+                };
+                // Whether fields of generic types implement the trait is up to the user.
+                if field_ty.has_non_region_param()
+                    || infcx
+                        .type_implements_trait(trait_def_id, [field_ty], param_env)
+                        .must_apply_modulo_regions()
+                {
+                    continue;
+                }
+                // A `PhantomData` field makes it explicit that the type doesn't implement the
+                // trait.
+                if field_ty.is_phantom_data() {
+                    field_missing_trait = None;
+                    break;
+                }
+                field_missing_trait.get_or_insert(field);
+            }
+
+            if let Some(field) = field_missing_trait {
+                let field_def_id = field.did.expect_local();
+                tcx.emit_spanned_lint(
+                    lint::builtin::AUTO_TRAITS_FROM_PRIVATE_FIELDS,
+                    tcx.hir().local_def_id_to_hir_id(field_def_id),
+                    tcx.def_span(field_def_id),
+                    AutoTraitFromPrivateFieldLint {
+                        kind: tcx.def_descr(def_id.to_def_id()),
+                        descr: (&descr).into(),
+                        trait_name,
+                        field_name: field.name,
+                    },
+                );
+            }
+        }
+    }
+
     fn check_assoc_item(
         &self,
         def_id: LocalDefId,
@@ -2052,6 +2175,8 @@ pub fn check_item(&mut self, id: ItemId) {
                             self.check(field.def_id, item_visibility, effective_vis).ty();
                         }
                     }
+
+                    self.check_semver_hazards(item.owner_id.def_id);
                 }
             }
             // Subitems of foreign modules have their own publicity.
@@ -2097,6 +2222,8 @@ pub fn check_item(&mut self, id: ItemId) {
                         )
                         .ty();
                     }
+
+                    self.check_semver_hazards(item.owner_id.def_id);
                 }
             }
             // An inherent impl is public when its type is public
@@ -2168,11 +2295,304 @@ pub fn check_item(&mut self, id: ItemId) {
     }
 }
 
+///////////////////////////////////////////////////////////////////////////////
+/// RequiredVisibilities collects the narrowest visibility each local item needs
+/// for all of its uses, for the `unnecessary_visibility` lint.
+/// UsesVisitor records the uses of items by paths and type-dependent resolutions,
+/// InterfaceUsesVisitor records the uses of items in the interfaces of other items.
+///////////////////////////////////////////////////////////////////////////////
+
+#[derive(Default)]
+struct RequiredVisibilities {
+    required: FxHashMap<LocalDefId, ty::Visibility>,
+    /// Items used from the output of function-like macros. Paths in macro output may be
+    /// resolved at the definition site of the macro, so these items are never linted.
+    pinned: FxHashSet<LocalDefId>,
+}
+
+impl RequiredVisibilities {
+    /// Returns the local item whose visibility a use of `def_id` depends on.
+    fn used_item(tcx: TyCtxt<'_>, def_id: DefId) -> Option<LocalDefId> {
+        let def_id = def_id.as_local()?;
+        // Constructors and variants are visible wherever their type is.
+        Some(match tcx.def_kind(def_id) {
+            DefKind::Ctor(CtorOf::Struct, _) | DefKind::Variant => tcx.local_parent(def_id),
+            DefKind::Ctor(CtorOf::Variant, _) => tcx.local_parent(tcx.local_parent(def_id)),
+            _ => def_id,
+        })
+    }
+
+    fn require(&mut self, tcx: TyCtxt<'_>, def_id: DefId, vis: ty::Visibility) {
+        if let Some(def_id) = Self::used_item(tcx, def_id) {
+            self.required
+                .entry(def_id)
+                .and_modify(|required_vis| *required_vis = max(*required_vis, vis, tcx))
+                .or_insert(vis);
+        }
+    }
+
+    fn pin(&mut self, tcx: TyCtxt<'_>, def_id: DefId) {
+        if let Some(def_id) = Self::used_item(tcx, def_id) {
+            self.pinned.insert(def_id);
+        }
+    }
+
+    fn interface<'a, 'tcx>(
+        &'a mut self,
+        tcx: TyCtxt<'tcx>,
+        def_id: LocalDefId,
+        required_visibility: ty::Visibility,
+    ) -> InterfaceUsesVisitor<'a, 'tcx> {
+        InterfaceUsesVisitor {
+            tcx,
+            item_def_id: def_id,
+            required_visibility,
+            impl_header_items: Default::default(),
+            required: self,
+        }
+    }
+
+    /// Records the visibilities needed by the interfaces of all local items, following the
+    /// rules of `PrivateItemsInPublicInterfacesChecker`.
+    fn require_interfaces(&mut self, tcx: TyCtxt<'_>) {
+        for def_id in tcx.hir_crate_items(()).definitions() {
+            let def_kind = tcx.def_kind(def_id);
+            match def_kind {
+                DefKind::Const | DefKind::Static(_) | DefKind::Fn | DefKind::TyAlias => {
+                    self.interface(tcx, def_id, tcx.local_visibility(def_id))
+                        .generics()
+                        .predicates()
+                        .ty();
+                }
+                DefKind::OpaqueTy => {
+                    self.interface(tcx, def_id, tcx.local_visibility(def_id)).generics().bounds();
+                }
+                DefKind::Trait
+                | DefKind::TraitAlias
+                | DefKind::Struct
+                | DefKind::Union
+                | DefKind::Enum => {
+                    self.interface(tcx, def_id, tcx.local_visibility(def_id))
+                        .generics()
+                        .predicates();
+                }
+                DefKind::Field => {
+                    let parent = tcx.local_parent(def_id);
+                    // Fields of variants have the visibility of their enum.
+                    let vis = if let DefKind::Variant = tcx.def_kind(parent) {
+                        tcx.local_visibility(tcx.local_parent(parent))
+                    } else {
+                        min(tcx.local_visibility(def_id), tcx.local_visibility(parent), tcx)
+                    };
+                    self.interface(tcx, def_id, vis).ty();
+                }
+                DefKind::Impl { of_trait: false } => {
+                    let impl_vis =
+                        ty::Visibility::of_impl::<false>(def_id, tcx, &Default::default());
+                    self.interface(tcx, def_id, impl_vis).in_impl(def_id).generics().predicates();
+                }
+                DefKind::AssocConst | DefKind::AssocFn | DefKind::AssocTy => {
+                    let parent = tcx.local_parent(def_id);
+                    let vis = match tcx.def_kind(parent) {
+                        DefKind::Trait => tcx.local_visibility(parent),
+                        DefKind::Impl { of_trait } => {
+                            let impl_vis =
+                                ty::Visibility::of_impl::<false>(parent, tcx, &Default::default());
+                            if of_trait {
+                                impl_vis
+                            } else {
+                                min(tcx.local_visibility(def_id), impl_vis, tcx)
+                            }
+                        }
+                        _ => continue,
+                    };
+                    let mut interface = self.interface(tcx, def_id, vis);
+                    if let DefKind::Impl { .. } = tcx.def_kind(parent) {
+                        interface.in_impl(parent);
+                    }
+                    interface.generics().predicates();
+                    if def_kind != DefKind::AssocTy || tcx.defaultness(def_id).has_value() {
+                        interface.ty();
+                    }
+                    if def_kind == DefKind::AssocTy && tcx.def_kind(parent) == DefKind::Trait {
+                        interface.bounds();
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+struct UsesVisitor<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    maybe_typeck_results: Option<&'tcx ty::TypeckResults<'tcx>>,
+    /// Visibility of the `use` item being visited. Imported items must be at least as visible
+    /// as the import itself, so that re-exports stay valid.
+    use_visibility: Option<ty::Visibility>,
+    required: &'a mut RequiredVisibilities,
+}
+
+impl UsesVisitor<'_, '_> {
+    fn record(&mut self, def_id: DefId, vis: ty::Visibility, span: Span) {
+        if span
+            .macro_backtrace()
+            .any(|expn_data| matches!(expn_data.kind, ExpnKind::Macro(MacroKind::Bang, _)))
+        {
+            self.required.pin(self.tcx, def_id);
+        } else {
+            self.required.require(self.tcx, def_id, vis);
+        }
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for UsesVisitor<'_, 'tcx> {
+    type NestedFilter = nested_filter::OnlyBodies;
+
+    fn nested_visit_map(&mut self) -> Self::Map {
+        self.tcx.hir()
+    }
+
+    fn visit_nested_body(&mut self, body: hir::BodyId) {
+        let old_maybe_typeck_results =
+            self.maybe_typeck_results.replace(self.tcx.typeck_body(body));
+        let body = self.tcx.hir().body(body);
+        self.visit_body(body);
+        self.maybe_typeck_results = old_maybe_typeck_results;
+    }
+
+    fn visit_item(&mut self, item: &'tcx hir::Item<'tcx>) {
+        if let hir::ItemKind::Use(..) = item.kind {
+            self.use_visibility = Some(self.tcx.local_visibility(item.owner_id.def_id));
+            intravisit::walk_item(self, item);
+            self.use_visibility = None;
+        } else {
+            intravisit::walk_item(self, item);
+        }
+    }
+
+    fn visit_path(&mut self, path: &hir::Path<'tcx>, hir_id: hir::HirId) {
+        let module_vis = ty::Visibility::Restricted(self.tcx.parent_module(hir_id));
+        // Every module along the path has to be visible from the module of the path.
+        for segment in path.segments {
+            if let Res::Def(_, def_id) = segment.res {
+                self.record(def_id, module_vis, segment.ident.span);
+            }
+        }
+        if let Res::Def(_, def_id) = path.res {
+            self.record(def_id, self.use_visibility.unwrap_or(module_vis), path.span);
+        }
+        intravisit::walk_path(self, path);
+    }
+
+    fn visit_qpath(&mut self, qpath: &'tcx hir::QPath<'tcx>, id: hir::HirId, _span: Span) {
+        if let hir::QPath::TypeRelative(_, segment) = qpath
+            && let Some(typeck_results) = self.maybe_typeck_results
+            && let Some((_, def_id)) = typeck_results.type_dependent_def(id)
+        {
+            let module_vis = ty::Visibility::Restricted(self.tcx.parent_module(id));
+            self.record(def_id, module_vis, segment.ident.span);
+        }
+        intravisit::walk_qpath(self, qpath, id);
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        if let hir::ExprKind::MethodCall(segment, ..) = expr.kind
+            && let Some(typeck_results) = self.maybe_typeck_results
+            && let Some(def_id) = typeck_results.type_dependent_def_id(expr.hir_id)
+        {
+            let module_vis = ty::Visibility::Restricted(self.tcx.parent_module(expr.hir_id));
+            self.record(def_id, module_vis, segment.ident.span);
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+struct InterfaceUsesVisitor<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    item_def_id: LocalDefId,
+    /// Items in the interface must be at least this visible.
+    required_visibility: ty::Visibility,
+    /// Items in the header of the impl the interface belongs to. The visibility of an impl is
+    /// the narrowest visibility of those items, so they always satisfy `required_visibility`.
+    impl_header_items: FxHashSet<DefId>,
+    required: &'a mut RequiredVisibilities,
+}
+
+impl<'tcx> InterfaceUsesVisitor<'_, 'tcx> {
+    fn in_impl(&mut self, impl_def_id: LocalDefId) -> &mut Self {
+        let self_ty = self.tcx.type_of(impl_def_id).instantiate_identity();
+        let trait_ref =
+            self.tcx.impl_trait_ref(impl_def_id).map(|trait_ref| trait_ref.instantiate_identity());
+        self.impl_header_items.extend(trait_ref.map(|trait_ref| trait_ref.def_id));
+        let args = trait_ref.into_iter().flat_map(|trait_ref| trait_ref.args.iter());
+        for arg in args.chain([self_ty.into()]).flat_map(|arg| arg.walk()) {
+            if let Some(ty) = arg.as_type()
+                && let ty::Adt(adt, _) = ty.kind()
+            {
+                self.impl_header_items.insert(adt.did());
+            }
+        }
+        self
+    }
+
+    fn generics(&mut self) -> &mut Self {
+        for param in &self.tcx.generics_of(self.item_def_id).params {
+            match param.kind {
+                GenericParamDefKind::Lifetime => {}
+                GenericParamDefKind::Type { has_default, .. } => {
+                    if has_default {
+                        self.visit(self.tcx.type_of(param.def_id).instantiate_identity());
+                    }
+                }
+                GenericParamDefKind::Const { .. } => {
+                    self.visit(self.tcx.type_of(param.def_id).instantiate_identity());
+                }
+            }
+        }
+        self
+    }
+
+    fn predicates(&mut self) -> &mut Self {
+        self.visit_predicates(self.tcx.explicit_predicates_of(self.item_def_id));
+        self
+    }
+
+    fn bounds(&mut self) -> &mut Self {
+        self.visit_clauses(self.tcx.explicit_item_bounds(self.item_def_id).skip_binder());
+        self
+    }
+
+    fn ty(&mut self) -> &mut Self {
+        self.visit(self.tcx.type_of(self.item_def_id).instantiate_identity());
+        self
+    }
+}
+
+impl<'tcx> DefIdVisitor<'tcx> for InterfaceUsesVisitor<'_, 'tcx> {
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.tcx
+    }
+    fn visit_def_id(
+        &mut self,
+        def_id: DefId,
+        _kind: &str,
+        _descr: &dyn fmt::Display,
+    ) -> ControlFlow<Self::BreakTy> {
+        // Function types mention the function itself.
+        if def_id != self.item_def_id.to_def_id() && !self.impl_header_items.contains(&def_id) {
+            self.required.require(self.tcx, def_id, self.required_visibility);
+        }
+        ControlFlow::Continue(())
+    }
+}
+
 pub fn provide(providers: &mut Providers) {
     *providers = Providers {
         visibility,
         effective_visibilities,
         check_private_in_public,
+        check_unnecessary_visibility,
         check_mod_privacy,
         ..*providers
     };
@@ -2320,3 +2740,140 @@ fn check_private_in_public(tcx: TyCtxt<'_>, (): ()) {
         checker.check_item(id);
     }
 }
+
+/// Whether some `#[warn]`, `#[deny]`, `#[forbid]` or `#[expect]` attribute in the crate names the
+/// `unnecessary_visibility` lint.
+fn has_unnecessary_visibility_attr(tcx: TyCtxt<'_>) -> bool {
+    let name = lint::builtin::UNNECESSARY_VISIBILITY.name_lower();
+    let names_lint = |attr: &Attribute| {
+        [sym::warn, sym::deny, sym::forbid, sym::expect]
+            .into_iter()
+            .any(|level| attr.has_name(level))
+            && attr.meta_item_list().is_some_and(|list| {
+                list.iter().any(|nested| nested.ident().is_some_and(|ident| ident.as_str() == name))
+            })
+    };
+    tcx.hir_crate_items(())
+        .owners()
+        .any(|owner| tcx.hir_attrs(owner).map.values().any(|attrs| attrs.iter().any(names_lint)))
+}
+
+fn check_unnecessary_visibility(tcx: TyCtxt<'_>, (): ()) {
+    // The lint is allowed by default, don't look for uses unless something enables it.
+    if tcx.lint_level_at_node(lint::builtin::UNNECESSARY_VISIBILITY, hir::CRATE_HIR_ID).0
+        == lint::Level::Allow
+        && !has_unnecessary_visibility_attr(tcx)
+    {
+        return;
+    }
+
+    // Nothing in an executable can be used by other crates, whatever its effective visibility.
+    let is_executable = tcx.sess.crate_types().iter().all(|&ty| ty == CrateType::Executable);
+    let effective_visibilities = tcx.effective_visibilities(());
+
+    let candidates: Vec<_> = tcx
+        .hir_crate_items(())
+        .definitions()
+        .filter_map(|def_id| {
+            let vis_span = match tcx.def_kind(def_id) {
+                DefKind::Mod
+                | DefKind::Struct
+                | DefKind::Union
+                | DefKind::Enum
+                | DefKind::Trait
+                | DefKind::TraitAlias
+                | DefKind::TyAlias
+                | DefKind::Fn
+                | DefKind::Const
+                | DefKind::Static(_) => match tcx.hir().get_by_def_id(def_id) {
+                    Node::Item(item) => item.vis_span,
+                    Node::ForeignItem(foreign_item) => foreign_item.vis_span,
+                    _ => return None,
+                },
+                // Items of trait impls have the visibility of their trait.
+                DefKind::AssocConst | DefKind::AssocFn | DefKind::AssocTy
+                    if matches!(
+                        tcx.def_kind(tcx.local_parent(def_id)),
+                        DefKind::Impl { of_trait: false }
+                    ) =>
+                {
+                    tcx.hir().expect_impl_item(def_id).vis_span
+                }
+                _ => return None,
+            };
+            let hir_id = tcx.hir().local_def_id_to_hir_id(def_id);
+            let skip = vis_span.is_empty()
+                || vis_span.from_expansion()
+                || (!is_executable && effective_visibilities.is_reachable(def_id))
+                || tcx.has_attr(def_id, sym::no_mangle)
+                || tcx.has_attr(def_id, sym::export_name)
+                || tcx.lint_level_at_node(lint::builtin::UNNECESSARY_VISIBILITY, hir_id).0
+                    == lint::Level::Allow;
+            (!skip).then_some((def_id, hir_id, vis_span))
+        })
+        .collect();
+    if candidates.is_empty() {
+        return;
+    }
+
+    let mut required = RequiredVisibilities::default();
+    let mut visitor = UsesVisitor {
+        tcx,
+        maybe_typeck_results: None,
+        use_visibility: None,
+        required: &mut required,
+    };
+    tcx.hir().visit_all_item_likes_in_crate(&mut visitor);
+    required.require_interfaces(tcx);
+
+    for (def_id, hir_id, vis_span) in candidates {
+        if required.pinned.contains(&def_id) {
+            continue;
+        }
+
+        let vis = tcx.local_visibility(def_id);
+        let module = tcx.parent_module_from_def_id(def_id);
+        let private_vis = ty::Visibility::Restricted(module);
+        let required_vis = match required.required.get(&def_id) {
+            Some(&required_vis) => max(required_vis, private_vis, tcx),
+            None => private_vis,
+        };
+        if required_vis.is_at_least(vis, tcx) {
+            continue;
+        }
+
+        // Only suggest visibilities which don't need a path. Narrowing `pub` to `pub(crate)`
+        // is left to the `unreachable_pub` lint.
+        let (narrowed, suggestion, replacement) = if required_vis == private_vis {
+            // Remove the whitespace after the visibility along with it.
+            let span = tcx
+                .sess
+                .source_map()
+                .span_extend_while(vis_span, char::is_whitespace)
+                .unwrap_or(vis_span);
+            ("private", span, "")
+        } else if let ty::Visibility::Restricted(required_module) = required_vis
+            && !module.is_top_level_module()
+            && required_module == tcx.parent_module_from_def_id(module)
+            && !required_module.is_top_level_module()
+        {
+            ("super", vis_span, "pub(super)")
+        } else {
+            continue;
+        };
+
+        tcx.emit_spanned_lint(
+            lint::builtin::UNNECESSARY_VISIBILITY,
+            hir_id,
+            tcx.def_span(def_id),
+            UnnecessaryVisibilityLint {
+                kind: tcx.def_descr(def_id.to_def_id()),
+                descr: (&LazyDefPathStr { def_id: def_id.to_def_id(), tcx }).into(),
+                vis: &vis_to_string(def_id, vis, tcx),
+                narrowed,
+                suggestion,
+                replacement,
+            },
+        );
+    }
+}
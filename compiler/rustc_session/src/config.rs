@@ -8,6 +8,7 @@
 use crate::utils::{CanonicalizedPath, NativeLib, NativeLibKind};
 use crate::{lint, HashStableContext};
 use crate::{EarlyErrorHandler, Session};
+use lint_config::LintConfig;
 
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::stable_hasher::{StableOrd, ToStableHashKey};
@@ -39,6 +40,7 @@
 use std::str::{self, FromStr};
 use std::sync::LazyLock;
 
+pub mod lint_config;
 pub mod sigpipe;
 
 /// The different settings that the `-C strip` flag can have.
@@ -1017,6 +1019,7 @@ fn default() -> Options {
             debuginfo: DebugInfo::None,
             lint_opts: Vec::new(),
             lint_cap: None,
+            lint_config: None,
             describe_lints: false,
             output_types: OutputTypes(BTreeMap::new()),
             search_paths: vec![],
@@ -2584,6 +2587,8 @@ pub fn build_session_options(
 
     let mut unstable_opts = UnstableOptions::build(handler, matches);
     let (lint_opts, describe_lints, lint_cap) = get_cmd_lint_options(handler, matches);
+    let lint_config =
+        unstable_opts.lint_config.as_deref().map(|path| LintConfig::load(handler, path));
 
     check_error_format_stability(handler, &unstable_opts, error_format, json_rendered);
 
@@ -2828,6 +2833,7 @@ pub fn build_session_options(
         debuginfo,
         lint_opts,
         lint_cap,
+        lint_config,
         describe_lints,
         output_types,
         search_paths,
@@ -3110,6 +3116,7 @@ pub fn needs_analysis(&self) -> bool {
 /// we have an opt-in scheme here, so one is hopefully forced to think about
 /// how the hash should be calculated when adding a new command-line argument.
 pub(crate) mod dep_tracking {
+    use super::lint_config::LintConfig;
     use super::{
         BranchProtection, CFGuard, CFProtection, CrateType, DebugInfo, ErrorOutputType,
         InstrumentCoverage, InstrumentXRay, LdImpl, LinkerPluginLto, LocationDetail, LtoCli,
@@ -3178,6 +3185,7 @@ fn hash(
         String,
         PathBuf,
         lint::Level,
+        LintConfig,
         WasiExecModel,
         u32,
         RelocModel,
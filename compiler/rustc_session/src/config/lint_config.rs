@@ -0,0 +1,146 @@
+//! Lint levels read from the file passed to `-Z lint-config`.
+
+use crate::EarlyErrorHandler;
+use crate::lint::Level;
+use rustc_span::Symbol;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Lint levels for the whole crate and for the modules matching path patterns, read from the
+/// TOML file passed to `-Z lint-config`:
+///
+/// ```toml
+/// [lints]
+/// unsafe_code = "deny"
+///
+/// [modules."ffi::*"]
+/// unsafe_code = "allow"
+/// ```
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct LintConfig {
+    /// The file the levels were read from.
+    pub path: PathBuf,
+    /// The levels set for the whole crate.
+    pub lints: Vec<(String, Level)>,
+    /// The levels set for the modules matching each pattern, least specific pattern first.
+    pub modules: Vec<(ModulePattern, Vec<(String, Level)>)>,
+}
+
+impl LintConfig {
+    /// Reads the lint config file at `path`, exiting with an error if it can't be read or is
+    /// malformed.
+    pub fn load(handler: &EarlyErrorHandler, path: &Path) -> LintConfig {
+        fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|contents| LintConfig::parse(path, &contents))
+            .unwrap_or_else(|e| {
+                handler.early_error(format!(
+                    "failed to load lint config file `{}`: {e}",
+                    path.display()
+                ))
+            })
+    }
+
+    fn parse(path: &Path, contents: &str) -> Result<LintConfig, String> {
+        let table: toml::value::Table = toml::from_str(contents).map_err(|e| e.to_string())?;
+        let mut config =
+            LintConfig { path: path.to_owned(), lints: Vec::new(), modules: Vec::new() };
+        for (key, value) in table {
+            match &*key {
+                "lints" => config.lints = parse_levels("lints", value)?,
+                "modules" => {
+                    let toml::Value::Table(modules) = value else {
+                        return Err("`modules` must be a table".to_string());
+                    };
+                    for (pattern, levels) in modules {
+                        let levels = parse_levels(&format!("modules.\"{pattern}\""), levels)?;
+                        config.modules.push((ModulePattern::parse(&pattern)?, levels));
+                    }
+                }
+                _ => return Err(format!("unknown key `{key}`, expected `lints` or `modules`")),
+            }
+        }
+        config.modules.sort_by_key(|(pattern, _)| pattern.specificity());
+        Ok(config)
+    }
+}
+
+fn parse_levels(table_name: &str, value: toml::Value) -> Result<Vec<(String, Level)>, String> {
+    let toml::Value::Table(table) = value else {
+        return Err(format!("`{table_name}` must be a table of lint levels"));
+    };
+    table
+        .into_iter()
+        .map(|(lint, level)| match level.as_str().and_then(Level::from_str) {
+            Some(level) => Ok((lint.replace('-', "_"), level)),
+            None => Err(format!(
+                "the level of `{lint}` in `{table_name}` must be one of \
+                 \"allow\", \"warn\", \"deny\" or \"forbid\""
+            )),
+        })
+        .collect()
+}
+
+/// A pattern matching module paths, written relative to the crate root like `ffi::sys`.
+///
+/// A `*` segment matches any number of segments, including none, so `ffi::*` matches `ffi` and
+/// all the modules nested in it, and `*::tests` matches every module named `tests`.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ModulePattern {
+    pattern: String,
+    segments: Vec<String>,
+}
+
+impl ModulePattern {
+    fn parse(pattern: &str) -> Result<ModulePattern, String> {
+        let path = pattern.strip_prefix("crate::").unwrap_or(pattern);
+        let segments: Vec<_> = if path == "crate" {
+            Vec::new()
+        } else {
+            path.split("::").map(|segment| segment.strip_prefix("r#").unwrap_or(segment)).collect()
+        };
+        if let Some(segment) =
+            segments.iter().find(|segment| **segment != "*" && !is_ident(segment))
+        {
+            return Err(format!(
+                "invalid module pattern `{pattern}`: `{segment}` is neither a module name nor `*`"
+            ));
+        }
+        Ok(ModulePattern {
+            pattern: pattern.to_owned(),
+            segments: segments.into_iter().map(str::to_owned).collect(),
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Whether the pattern matches the module at `path`, given as the names of the modules
+    /// leading to it from the crate root.
+    pub fn matches(&self, path: &[Symbol]) -> bool {
+        fn matches(segments: &[String], path: &[Symbol]) -> bool {
+            match segments.split_first() {
+                None => path.is_empty(),
+                Some((first, rest)) if first == "*" => {
+                    (0..=path.len()).any(|skip| matches(rest, &path[skip..]))
+                }
+                Some((first, rest)) => path
+                    .split_first()
+                    .is_some_and(|(name, path)| name.as_str() == first && matches(rest, path)),
+            }
+        }
+        matches(&self.segments, path)
+    }
+
+    /// The number of segments which aren't `*`.
+    fn specificity(&self) -> usize {
+        self.segments.iter().filter(|segment| *segment != "*").count()
+    }
+}
+
+fn is_ident(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars.next().is_some_and(|c| c == '_' || c.is_alphabetic())
+        && chars.all(|c| c == '_' || c.is_alphanumeric())
+}
@@ -1,3 +1,4 @@
+use crate::config::lint_config::LintConfig;
 use crate::config::*;
 
 use crate::search_paths::SearchPath;
@@ -141,6 +142,8 @@ pub struct Options {
         debuginfo: DebugInfo [TRACKED],
         lint_opts: Vec<(String, lint::Level)> [TRACKED_NO_CRATE_HASH],
         lint_cap: Option<lint::Level> [TRACKED_NO_CRATE_HASH],
+        /// Lint levels read from the file passed to `-Z lint-config`.
+        lint_config: Option<LintConfig> [TRACKED_NO_CRATE_HASH],
         describe_lints: bool [UNTRACKED],
         output_types: OutputTypes [TRACKED],
         search_paths: Vec<SearchPath> [UNTRACKED],
@@ -1573,6 +1576,9 @@ pub(crate) fn parse_dump_solver_proof_tree(
         "link native libraries in the linker invocation (default: yes)"),
     link_only: bool = (false, parse_bool, [TRACKED],
         "link the `.rlink` file generated by `-Z no-link` (default: no)"),
+    // The levels read from the file are tracked in `Options::lint_config`.
+    lint_config: Option<PathBuf> = (None, parse_opt_pathbuf, [UNTRACKED],
+        "read lint levels for the crate and its modules from a TOML file"),
     llvm_plugins: Vec<String> = (Vec::new(), parse_list, [TRACKED],
         "a list LLVM plugins to enable (space separated)"),
     llvm_time_trace: bool = (false, parse_bool, [UNTRACKED],
@@ -1876,6 +1882,9 @@ pub(crate) fn parse_dump_solver_proof_tree(
         Requires `-Clto[=[fat,yes]]`"),
     wasi_exec_model: Option<WasiExecModel> = (None, parse_wasi_exec_model, [TRACKED],
         "whether to build a wasi command or reactor"),
+    workspace_dead_code: Option<Vec<String>> = (None, parse_opt_comma_list, [TRACKED],
+        "record the uses of items in the crate metadata, and in the listed final crates, report \
+        the public items of the libraries built with this flag which no crate uses"),
     write_long_types_to_disk: bool = (true, parse_bool, [UNTRACKED],
         "whether long type names should be written to files instead of being printed in errors"),
     // tidy-alphabetical-end
@@ -1472,6 +1472,11 @@ pub fn build_session(
 
     let mut parse_sess = ParseSess::with_span_handler(span_diagnostic, source_map);
     parse_sess.assume_incomplete_release = sopts.unstable_opts.assume_incomplete_release;
+    // The levels read from `-Z lint-config` depend on the file, so it is listed in the dep-info.
+    if let Some(lint_config) = &sopts.lint_config {
+        let path = lint_config.path.to_string_lossy();
+        parse_sess.file_depinfo.get_mut().insert(Symbol::intern(&path));
+    }
 
     let host_triple = config::host_triple();
     let target_triple = sopts.target_triple.triple();
@@ -638,6 +638,7 @@
         doc_masked,
         doc_notable_trait,
         doc_primitive,
+        doc_since,
         doc_spotlight,
         doctest,
         document_private_items,
@@ -1356,6 +1357,7 @@
         self_in_typedefs,
         self_struct_ctor,
         semitransparent,
+        semver_hazard_lints,
         shadow_call_stack,
         shl,
         shl_assign,
@@ -1593,6 +1595,7 @@
         unix_sigpipe,
         unlikely,
         unmarked_api,
+        unnecessary_visibility_lint,
         unpin,
         unreachable,
         unreachable_2015,
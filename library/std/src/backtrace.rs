@@ -89,10 +89,12 @@
 // a backtrace or actually symbolizing it.
 
 use crate::backtrace_rs::{self, BytesOrWideString};
+use crate::borrow::Cow;
 use crate::cell::UnsafeCell;
 use crate::env;
 use crate::ffi::c_void;
 use crate::fmt;
+use crate::path::{Path, PathBuf};
 use crate::sync::atomic::{AtomicUsize, Ordering::Relaxed};
 use crate::sync::Once;
 use crate::sys_common::backtrace::{lock, output_filename};
@@ -161,7 +163,12 @@ enum RawFrame {
     Fake,
 }
 
-struct BacktraceSymbol {
+/// A symbol which a frame of a backtrace resolved to.
+///
+/// Filenames, line numbers and columns are only available if the program was
+/// compiled with debug information.
+#[unstable(feature = "backtrace_frames", issue = "79676")]
+pub struct BacktraceSymbol {
     name: Option<Vec<u8>>,
     filename: Option<BytesOrWide>,
     lineno: Option<u32>,
@@ -173,6 +180,16 @@ enum BytesOrWide {
     Wide(Vec<u16>),
 }
 
+/// The name of a symbol in a backtrace.
+///
+/// Displaying the name demangles it. The alternate form, `{:#}`, leaves out
+/// the hash at the end of Rust symbol names.
+#[unstable(feature = "backtrace_frames", issue = "79676")]
+#[derive(Clone, Copy)]
+pub struct BacktraceSymbolName<'a> {
+    bytes: &'a [u8],
+}
+
 #[stable(feature = "backtrace", since = "1.65.0")]
 impl fmt::Debug for Backtrace {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -209,6 +226,7 @@ fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+#[unstable(feature = "backtrace_frames", issue = "79676")]
 impl fmt::Debug for BacktraceSymbol {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         // FIXME: improve formatting: https://github.com/rust-lang/rust/issues/65280
@@ -249,6 +267,20 @@ fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+#[unstable(feature = "backtrace_frames", issue = "79676")]
+impl fmt::Debug for BacktraceSymbolName<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&backtrace_rs::SymbolName::new(self.bytes), fmt)
+    }
+}
+
+#[unstable(feature = "backtrace_frames", issue = "79676")]
+impl fmt::Display for BacktraceSymbolName<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&backtrace_rs::SymbolName::new(self.bytes), fmt)
+    }
+}
+
 impl Backtrace {
     /// Returns whether backtrace captures are enabled through environment
     /// variables.
@@ -380,6 +412,132 @@ pub fn frames(&'a self) -> &'a [BacktraceFrame] {
     }
 }
 
+impl BacktraceFrame {
+    /// Returns the instruction pointer of this frame.
+    ///
+    /// For all frames but the innermost one, this is usually the address of
+    /// the instruction after the call the frame was executing, though not all
+    /// platforms report it the same way.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn ip(&self) -> *mut c_void {
+        self.frame.ip()
+    }
+
+    /// Returns the base address of the executable or shared library this
+    /// frame's code belongs to, if the platform reports it.
+    ///
+    /// Subtracting it from [`ip`](BacktraceFrame::ip) gives an address which
+    /// doesn't depend on where the module was loaded, so it can be resolved
+    /// later against the debug information of the module.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn module_base_address(&self) -> Option<*mut c_void> {
+        self.frame.module_base_address()
+    }
+
+    /// Returns the symbols this frame resolved to.
+    ///
+    /// A frame resolves to several symbols when functions were inlined into
+    /// each other. The innermost inlined function comes first and the function
+    /// the frame belongs to comes last. The slice is empty if the frame
+    /// couldn't be resolved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(backtrace_frames)]
+    /// use std::backtrace::Backtrace;
+    ///
+    /// let backtrace = Backtrace::force_capture();
+    /// for frame in backtrace.frames() {
+    ///     for symbol in frame.symbols() {
+    ///         match symbol.name() {
+    ///             Some(name) => print!("{name:#}"),
+    ///             None => print!("<unknown>"),
+    ///         }
+    ///         if let (Some(filename), Some(line)) = (symbol.filename(), symbol.line()) {
+    ///             print!(" at {}:{line}", filename.display());
+    ///         }
+    ///         println!();
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn symbols(&self) -> &[BacktraceSymbol] {
+        &self.symbols
+    }
+}
+
+impl BacktraceSymbol {
+    /// Returns the name of this symbol, if known.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn name(&self) -> Option<BacktraceSymbolName<'_>> {
+        self.name.as_deref().map(|bytes| BacktraceSymbolName { bytes })
+    }
+
+    /// Returns the path of the file which contains the code of this symbol, if
+    /// known.
+    ///
+    /// Filenames which can't be represented as a path on this platform are
+    /// converted lossily.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn filename(&self) -> Option<Cow<'_, Path>> {
+        self.filename.as_ref().map(|filename| match filename {
+            #[cfg(unix)]
+            BytesOrWide::Bytes(bytes) => {
+                use crate::os::unix::prelude::*;
+                Cow::Borrowed(Path::new(crate::ffi::OsStr::from_bytes(bytes)))
+            }
+            #[cfg(not(unix))]
+            BytesOrWide::Bytes(bytes) => match String::from_utf8_lossy(bytes) {
+                Cow::Borrowed(filename) => Cow::Borrowed(Path::new(filename)),
+                Cow::Owned(filename) => Cow::Owned(PathBuf::from(filename)),
+            },
+            #[cfg(windows)]
+            BytesOrWide::Wide(wide) => {
+                use crate::os::windows::prelude::*;
+                Cow::Owned(PathBuf::from(crate::ffi::OsString::from_wide(wide)))
+            }
+            #[cfg(not(windows))]
+            BytesOrWide::Wide(wide) => Cow::Owned(PathBuf::from(String::from_utf16_lossy(wide))),
+        })
+    }
+
+    /// Returns the line number of the code of this symbol, if known.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn line(&self) -> Option<u32> {
+        self.lineno
+    }
+
+    /// Returns the column of the code of this symbol, if known.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn column(&self) -> Option<u32> {
+        self.colno
+    }
+}
+
+impl<'a> BacktraceSymbolName<'a> {
+    /// Returns the raw bytes of the name, which are usually mangled.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns the raw name if it is valid UTF-8.
+    #[must_use]
+    #[unstable(feature = "backtrace_frames", issue = "79676")]
+    pub fn as_str(&self) -> Option<&'a str> {
+        crate::str::from_utf8(self.bytes).ok()
+    }
+}
+
 #[stable(feature = "backtrace", since = "1.65.0")]
 impl fmt::Display for Backtrace {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -501,4 +659,12 @@ fn ip(&self) -> *mut c_void {
             RawFrame::Fake => crate::ptr::invalid_mut(1),
         }
     }
+
+    fn module_base_address(&self) -> Option<*mut c_void> {
+        match self {
+            RawFrame::Actual(frame) => frame.module_base_address(),
+            #[cfg(test)]
+            RawFrame::Fake => None,
+        }
+    }
 }
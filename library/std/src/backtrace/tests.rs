@@ -93,3 +93,32 @@ fn test_frames() {
 
     assert!(iter.all(|(f, e)| format!("{f:#?}") == *e));
 }
+
+#[test]
+fn test_frame_accessors() {
+    let frames = generate_fake_frames();
+
+    assert_eq!(frames[1].symbols()[0].filename(), None);
+
+    let symbols = frames[2].symbols();
+    assert_eq!(symbols.len(), 2);
+
+    let symbol = &symbols[0];
+    assert_eq!(symbol.name().unwrap().as_str(), Some("std::rt::lang_start_internal"));
+    assert_eq!(symbol.filename().as_deref(), Some(Path::new("rust/rt.rs")));
+    assert_eq!(symbol.line(), Some(300));
+    assert_eq!(symbol.column(), Some(5));
+    assert_eq!(symbols[1].column(), None);
+
+    assert_eq!(frames[2].ip(), crate::ptr::invalid_mut(1));
+    assert_eq!(frames[2].module_base_address(), None);
+}
+
+#[test]
+fn test_symbol_name_demangling() {
+    let name = BacktraceSymbolName { bytes: b"_ZN3std2rt10lang_start17h0123456789abcdefE" };
+
+    assert_eq!(name.as_bytes(), b"_ZN3std2rt10lang_start17h0123456789abcdefE");
+    assert_eq!(name.to_string(), "std::rt::lang_start::h0123456789abcdef");
+    assert_eq!(format!("{name:#}"), "std::rt::lang_start");
+}
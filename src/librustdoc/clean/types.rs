@@ -596,6 +596,12 @@ pub(crate) fn const_stable_since(&self, tcx: TyCtxt<'_>) -> Option<Symbol> {
         }
     }
 
+    /// Returns the version this item was added in. This comes from its `#[stable]` attribute
+    /// if it has one, and from `#[doc(since = "...")]` otherwise.
+    pub(crate) fn added_since(&self, tcx: TyCtxt<'_>) -> Option<Symbol> {
+        self.stable_since(tcx).or_else(|| self.attrs.doc_since())
+    }
+
     pub(crate) fn is_non_exhaustive(&self) -> bool {
         self.attrs.other_attrs.iter().any(|a| a.has_name(sym::non_exhaustive))
     }
@@ -1137,6 +1143,14 @@ fn is_doc_hidden(&self) -> bool {
         self.has_doc_flag(sym::hidden)
     }
 
+    /// Returns the version given with `#[doc(since = "...")]`, if any.
+    pub(crate) fn doc_since(&self) -> Option<Symbol> {
+        self.other_attrs
+            .lists(sym::doc)
+            .find(|a| a.has_name(sym::since))
+            .and_then(|a| a.value_str())
+    }
+
     pub(crate) fn from_ast(attrs: &[ast::Attribute]) -> Attributes {
         Attributes::from_ast_iter(attrs.iter().map(|attr| (attr, None)), false)
     }
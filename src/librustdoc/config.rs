@@ -129,6 +129,8 @@ pub(crate) struct Options {
     /// The path to a rustc-like binary to build tests with. If not set, we
     /// default to loading from `$sysroot/bin/rustc`.
     pub(crate) test_builder: Option<PathBuf>,
+    /// How doctests are built and run: natively, with an interpreter, or with a sanitizer.
+    pub(crate) doctest_run_mode: DoctestRunMode,
 
     // Options that affect the documentation process
     /// Whether to run the `calculate-doc-coverage` pass, which counts the number of public items
@@ -195,6 +197,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             .field("crate_version", &self.crate_version)
             .field("runtool", &self.runtool)
             .field("runtool_args", &self.runtool_args)
+            .field("doctest_run_mode", &self.doctest_run_mode)
             .field("enable-per-target-ignores", &self.enable_per_target_ignores)
             .field("run_check", &self.run_check)
             .field("no_run", &self.no_run)
@@ -263,18 +266,57 @@ pub(crate) struct RenderOptions {
     pub(crate) generate_redirect_map: bool,
     /// Show the memory layout of types in the docs.
     pub(crate) show_type_layout: bool,
+    /// List, on each type and trait page, the documented items that use it in their signatures.
+    pub(crate) show_used_by: bool,
     /// Note: this field is duplicated in `Options` because it's useful to have
     /// it in both places.
     pub(crate) unstable_features: rustc_feature::UnstableFeatures,
     pub(crate) emit: Vec<EmitType>,
     /// If `true`, HTML source pages will generate links for items to their definition.
     pub(crate) generate_link_to_definition: bool,
+    /// If `true`, generate a page listing the items added or deprecated in each version.
+    pub(crate) generate_api_history: bool,
     /// Set of function-call locations to include as examples
     pub(crate) call_locations: AllCallLocations,
     /// If `true`, Context::init will not emit shared files.
     pub(crate) no_emit_shared: bool,
 }
 
+/// The sanitizers which can be passed to `--doctest-sanitizer`, which are the ones `-Zsanitizer`
+/// accepts. Whether a sanitizer is supported on the target is left to rustc to check. The memory
+/// and thread sanitizers also need a standard library built with the same sanitizer, which this
+/// doesn't check either.
+const DOCTEST_SANITIZERS: &[&str] = &[
+    "address",
+    "cfi",
+    "hwaddress",
+    "kcfi",
+    "leak",
+    "memory",
+    "memtag",
+    "safestack",
+    "shadow-call-stack",
+    "thread",
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum DoctestRunMode {
+    /// Doctests are compiled into native executables, which are run directly or with the
+    /// `--runtool`.
+    Native,
+    /// Doctests are built and run by an interpreter such as Miri. The interpreter is invoked
+    /// like rustc to build each doctest, then with the path of the built doctest to run it.
+    Interpreter { path: PathBuf, args: Vec<String> },
+    /// Doctests are compiled with `-Zsanitizer` and run natively.
+    Sanitizer(String),
+}
+
+impl DoctestRunMode {
+    pub(crate) fn is_native(&self) -> bool {
+        matches!(self, DoctestRunMode::Native)
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub(crate) enum ModuleSorting {
     DeclarationOrder,
@@ -678,14 +720,57 @@ fn println_condition(condition: Condition) {
         let extern_strs = matches.opt_strs("extern");
         let runtool = matches.opt_str("runtool");
         let runtool_args = matches.opt_strs("runtool-arg");
+        let doctest_interpreter = matches.opt_str("doctest-interpreter");
+        let doctest_interpreter_args = matches.opt_strs("doctest-interpreter-arg");
+        let doctest_sanitizer = matches.opt_str("doctest-sanitizer");
+        let doctest_run_mode = match (doctest_interpreter, doctest_sanitizer) {
+            (None, _) if !doctest_interpreter_args.is_empty() => {
+                diag.struct_err("`--doctest-interpreter-arg` requires `--doctest-interpreter`")
+                    .emit();
+                return Err(1);
+            }
+            (Some(_), Some(_)) => {
+                diag.struct_err(
+                    "cannot use both `--doctest-interpreter` and `--doctest-sanitizer` at once",
+                )
+                .emit();
+                return Err(1);
+            }
+            (Some(_), None) if test_builder.is_some() || runtool.is_some() => {
+                diag.struct_err(
+                    "`--doctest-interpreter` cannot be used with `--test-builder` or `--runtool`",
+                )
+                .emit();
+                return Err(1);
+            }
+            (Some(path), None) => DoctestRunMode::Interpreter {
+                path: PathBuf::from(path),
+                args: doctest_interpreter_args,
+            },
+            (None, Some(sanitizer)) => {
+                if !DOCTEST_SANITIZERS.contains(&sanitizer.as_str()) {
+                    diag.struct_err(format!("unknown sanitizer `{sanitizer}`"))
+                        .note(format!(
+                            "supported sanitizers are: {}",
+                            DOCTEST_SANITIZERS.join(", ")
+                        ))
+                        .emit();
+                    return Err(1);
+                }
+                DoctestRunMode::Sanitizer(sanitizer)
+            }
+            (None, None) => DoctestRunMode::Native,
+        };
         let enable_per_target_ignores = matches.opt_present("enable-per-target-ignores");
         let document_private = matches.opt_present("document-private-items");
         let document_hidden = matches.opt_present("document-hidden-items");
         let run_check = matches.opt_present("check");
         let generate_redirect_map = matches.opt_present("generate-redirect-map");
         let show_type_layout = matches.opt_present("show-type-layout");
+        let show_used_by = matches.opt_present("show-used-by");
         let nocapture = matches.opt_present("nocapture");
         let generate_link_to_definition = matches.opt_present("generate-link-to-definition");
+        let generate_api_history = matches.opt_present("generate-api-history");
         let extern_html_root_takes_precedence =
             matches.opt_present("extern-html-root-takes-precedence");
 
@@ -697,6 +782,14 @@ fn println_condition(condition: Condition) {
             return Err(1);
         }
 
+        if generate_api_history && (show_coverage || output_format != OutputFormat::Html) {
+            diag.struct_err(
+                "--generate-api-history option can only be used with HTML output format",
+            )
+            .emit();
+            return Err(1);
+        }
+
         let scrape_examples_options = ScrapeExamplesOptions::new(matches, &diag)?;
         let with_examples = matches.opt_strs("with-examples");
         let call_locations = crate::scrape_examples::load_call_locations(with_examples, &diag)?;
@@ -735,6 +828,7 @@ fn println_condition(condition: Condition) {
             runtool_args,
             enable_per_target_ignores,
             test_builder,
+            doctest_run_mode,
             run_check,
             no_run,
             nocapture,
@@ -766,9 +860,11 @@ fn println_condition(condition: Condition) {
             document_hidden,
             generate_redirect_map,
             show_type_layout,
+            show_used_by,
             unstable_features,
             emit,
             generate_link_to_definition,
+            generate_api_history,
             call_locations,
             no_emit_shared: false,
         };
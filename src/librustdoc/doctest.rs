@@ -30,7 +30,7 @@
 use std::sync::{Arc, Mutex};
 
 use crate::clean::{types::AttributesExt, Attributes};
-use crate::config::Options as RustdocOptions;
+use crate::config::{DoctestRunMode, Options as RustdocOptions};
 use crate::html::markdown::{self, ErrorCodes, Ignore, LangString};
 use crate::lint::init_lints;
 use crate::passes::span_of_attrs;
@@ -263,8 +263,16 @@ enum TestFailure {
     ///
     /// This typically means an assertion in the test failed or another form of panic occurred.
     ExecutionFailure(process::Output),
+    /// The test is marked `should_panic`, but the interpreter or sanitizer it was run with
+    /// reported an error instead of a panic.
+    UnexpectedRunError(process::Output),
     /// The test is marked `should_panic` but the test binary executed successfully.
     UnexpectedRunPass,
+    /// The test is followed by an `output` block, but the test binary printed something else.
+    UnexpectedOutput { expected: String, actual: String },
+    /// The test is followed by an `output` block, but it's marked `no_run` or `compile_fail`, so
+    /// its output can't be checked.
+    UncheckableOutput,
 }
 
 enum DirState {
@@ -320,6 +328,10 @@ fn run_test(
     test_id: &str,
     report_unused_externs: impl Fn(UnusedExterns),
 ) -> Result<(), TestFailure> {
+    if lang_string.expected_output.is_some() && (lang_string.no_run || lang_string.compile_fail) {
+        return Err(TestFailure::UncheckableOutput);
+    }
+
     let (test, line_offset, supports_color) =
         make_test(test, Some(crate_name), lang_string.test_harness, opts, edition, Some(test_id));
 
@@ -327,10 +339,13 @@ fn run_test(
     let rust_out = add_exe_suffix("rust_out".to_owned(), &target);
     let output_file = outdir.path().join(rust_out);
 
-    let rustc_binary = rustdoc_options
-        .test_builder
-        .as_deref()
-        .unwrap_or_else(|| rustc_interface::util::rustc_path().expect("found rustc"));
+    let rustc_binary = match rustdoc_options.doctest_run_mode {
+        DoctestRunMode::Interpreter { ref path, .. } => path.as_path(),
+        _ => rustdoc_options
+            .test_builder
+            .as_deref()
+            .unwrap_or_else(|| rustc_interface::util::rustc_path().expect("found rustc")),
+    };
     let mut compiler = Command::new(&rustc_binary);
     compiler.arg("--crate-type").arg("bin");
     for cfg in &rustdoc_options.cfgs {
@@ -371,6 +386,9 @@ fn run_test(
     for unstable_option_str in &rustdoc_options.unstable_opts_strs {
         compiler.arg("-Z").arg(&unstable_option_str);
     }
+    if let DoctestRunMode::Sanitizer(ref sanitizer) = rustdoc_options.doctest_run_mode {
+        compiler.arg("-Z").arg(format!("sanitizer={sanitizer}"));
+    }
     if no_run && !lang_string.compile_fail && rustdoc_options.persist_doctests.is_none() {
         compiler.arg("--emit=metadata");
     }
@@ -469,7 +487,11 @@ fn drop(&mut self) {
     // Run the code!
     let mut cmd;
 
-    if let Some(tool) = runtool {
+    if let DoctestRunMode::Interpreter { ref path, ref args } = rustdoc_options.doctest_run_mode {
+        cmd = Command::new(path);
+        cmd.args(args);
+        cmd.arg(output_file);
+    } else if let Some(tool) = runtool {
         cmd = Command::new(tool);
         cmd.args(runtool_args);
         cmd.arg(output_file);
@@ -480,7 +502,15 @@ fn drop(&mut self) {
         cmd.current_dir(run_directory);
     }
 
-    let result = if rustdoc_options.nocapture {
+    let result = if rustdoc_options.nocapture && lang_string.expected_output.is_some() {
+        // The output still needs to be captured to be compared with the expected one, so it
+        // is printed once the test is done.
+        cmd.stderr(Stdio::inherit());
+        cmd.output().map(|out| {
+            print!("{}", String::from_utf8_lossy(&out.stdout));
+            out
+        })
+    } else if rustdoc_options.nocapture {
         cmd.status().map(|status| process::Output {
             status,
             stdout: Vec::new(),
@@ -494,15 +524,102 @@ fn drop(&mut self) {
         Ok(out) => {
             if lang_string.should_panic && out.status.success() {
                 return Err(TestFailure::UnexpectedRunPass);
+            } else if lang_string.should_panic
+                && !rustdoc_options.doctest_run_mode.is_native()
+                && out.status.code() != Some(101)
+            {
+                // Interpreters and sanitizers report errors with their own exit codes, which
+                // should not be mistaken for the panic the test expects (exit code 101).
+                return Err(TestFailure::UnexpectedRunError(out));
             } else if !lang_string.should_panic && !out.status.success() {
                 return Err(TestFailure::ExecutionFailure(out));
             }
+
+            if let Some(expected) = lang_string.expected_output {
+                let actual = String::from_utf8_lossy(&out.stdout);
+                if normalize_output(&expected) != normalize_output(&actual) {
+                    return Err(TestFailure::UnexpectedOutput { expected, actual: actual.into() });
+                }
+            }
         }
     }
 
     Ok(())
 }
 
+/// Normalizes the output of a doctest before comparing it with the expected output, so that
+/// line endings and trailing whitespace don't matter.
+fn normalize_output(output: &str) -> String {
+    let mut normalized = output.lines().map(str::trim_end).collect::<Vec<_>>().join("\n");
+    normalized.truncate(normalized.trim_end().len());
+    normalized
+}
+
+/// Renders a line-based diff between the expected and the actual output of a doctest.
+fn output_diff(expected: &str, actual: &str) -> String {
+    let expected = normalize_output(expected);
+    let actual = normalize_output(actual);
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+
+    // `lcs[i][j]` is the length of the longest common subsequence of `expected[i..]` and
+    // `actual[j..]`.
+    let mut lcs = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            diff.push_str(&format!(" {}\n", expected[i]));
+            i += 1;
+            j += 1;
+        } else if j < actual.len() && (i == expected.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            diff.push_str(&format!("+{}\n", actual[j]));
+            j += 1;
+        } else {
+            diff.push_str(&format!("-{}\n", expected[i]));
+            i += 1;
+        }
+    }
+    diff
+}
+
+/// Prints the captured stdout and stderr of a failed doctest.
+fn print_test_output(out: &process::Output) {
+    // FIXME(#12309): An unfortunate side-effect of capturing the test
+    // executable's output is that the relative ordering between the test's
+    // stdout and stderr is lost. However, this is better than the
+    // alternative: if the test executable inherited the parent's I/O
+    // handles the output wouldn't be captured at all, even on success.
+    //
+    // The ordering could be preserved if the test process' stderr was
+    // redirected to stdout, but that functionality does not exist in the
+    // standard library, so it may not be portable enough.
+    let stdout = str::from_utf8(&out.stdout).unwrap_or_default();
+    let stderr = str::from_utf8(&out.stderr).unwrap_or_default();
+
+    if !stdout.is_empty() || !stderr.is_empty() {
+        eprintln!();
+
+        if !stdout.is_empty() {
+            eprintln!("stdout:\n{stdout}");
+        }
+
+        if !stderr.is_empty() {
+            eprintln!("stderr:\n{stderr}");
+        }
+    }
+}
+
 /// Transforms a test into code that can be compiled into a Rust binary, and returns the number of
 /// lines before the test code begins as well as if the output stream supports colors or not.
 pub(crate) fn make_test(
@@ -1112,6 +1229,19 @@ fn add_test(&mut self, test: String, config: LangString, line: usize) {
                         TestFailure::UnexpectedRunPass => {
                             eprint!("Test executable succeeded, but it's marked `should_panic`.");
                         }
+                        TestFailure::UnexpectedOutput { expected, actual } => {
+                            eprintln!(
+                                "Test executable's output differs from the expected one \
+                                 (-expected +actual):\n"
+                            );
+                            eprint!("{}", output_diff(&expected, &actual).trim_end());
+                        }
+                        TestFailure::UncheckableOutput => {
+                            eprint!(
+                                "Test is followed by an `output` block, but it's marked \
+                                 `no_run` or `compile_fail`, so its output can't be checked."
+                            );
+                        }
                         TestFailure::MissingErrorCodes(codes) => {
                             eprint!("Some expected error codes were not found: {:?}", codes);
                         }
@@ -1123,30 +1253,15 @@ fn add_test(&mut self, test: String, config: LangString, line: usize) {
                         }
                         TestFailure::ExecutionFailure(out) => {
                             eprintln!("Test executable failed ({reason}).", reason = out.status);
-
-                            // FIXME(#12309): An unfortunate side-effect of capturing the test
-                            // executable's output is that the relative ordering between the test's
-                            // stdout and stderr is lost. However, this is better than the
-                            // alternative: if the test executable inherited the parent's I/O
-                            // handles the output wouldn't be captured at all, even on success.
-                            //
-                            // The ordering could be preserved if the test process' stderr was
-                            // redirected to stdout, but that functionality does not exist in the
-                            // standard library, so it may not be portable enough.
-                            let stdout = str::from_utf8(&out.stdout).unwrap_or_default();
-                            let stderr = str::from_utf8(&out.stderr).unwrap_or_default();
-
-                            if !stdout.is_empty() || !stderr.is_empty() {
-                                eprintln!();
-
-                                if !stdout.is_empty() {
-                                    eprintln!("stdout:\n{stdout}");
-                                }
-
-                                if !stderr.is_empty() {
-                                    eprintln!("stderr:\n{stderr}");
-                                }
-                            }
+                            print_test_output(&out);
+                        }
+                        TestFailure::UnexpectedRunError(out) => {
+                            eprintln!(
+                                "Test executable failed ({reason}) without panicking, but it's \
+                                 marked `should_panic`.",
+                                reason = out.status,
+                            );
+                            print_test_output(&out);
                         }
                     }
 
@@ -1,4 +1,4 @@
-use super::{make_test, GlobalTestOptions};
+use super::{make_test, normalize_output, output_diff, GlobalTestOptions};
 use rustc_span::edition::DEFAULT_EDITION;
 
 #[test]
@@ -302,3 +302,16 @@ fn main() { #[allow(non_snake_case)] fn _doctest_main__some_unique_name() {
         make_test(input, None, false, &opts, DEFAULT_EDITION, Some("_some_unique_name"));
     assert_eq!((output, len), (expected, 2));
 }
+
+#[test]
+fn output_diff_marks_changed_lines() {
+    let expected = "| a | b |\n| 1 | 2 |\n| 3 | 4 |\n";
+    let actual = "| a | b |\r\n| 1 | 3 |\r\n| 3 | 4 |   \r\n";
+    assert_eq!(output_diff(expected, actual), " | a | b |\n+| 1 | 3 |\n-| 1 | 2 |\n | 3 | 4 |\n");
+}
+
+#[test]
+fn normalize_output_ignores_trailing_whitespace() {
+    assert_eq!(normalize_output("a  \r\nb\n\n"), normalize_output("a\nb"));
+    assert_ne!(normalize_output("a\n\nb"), normalize_output("a\nb"));
+}
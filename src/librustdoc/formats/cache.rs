@@ -69,6 +69,13 @@ pub(crate) struct Cache {
     /// of trait ids to the list of known implementors of the trait
     pub(crate) implementors: FxHashMap<DefId, Vec<Impl>>,
 
+    /// Maps a type or trait ID to the documented items which mention it in their
+    /// signatures: functions and methods taking or returning it, and trait impls
+    /// for it when it comes from another crate. This is only filled when the
+    /// `--show-used-by` flag is passed, and is used to print the "Used by" section
+    /// of the type's page, and the `used-by` javascript files for other crates.
+    pub(crate) used_by: DefIdMap<FxIndexSet<UsedBy>>,
+
     /// Cache of where external crate documentation can be found.
     pub(crate) extern_locations: FxHashMap<CrateNum, ExternalLocation>,
 
@@ -136,6 +143,8 @@ struct CacheBuilder<'a, 'tcx> {
     cache: &'a mut Cache,
     /// This field is used to prevent duplicated impl blocks.
     impl_ids: DefIdMap<DefIdSet>,
+    /// Whether the `used_by` map of the cache should be filled.
+    collect_used_by: bool,
     tcx: TyCtxt<'tcx>,
 }
 
@@ -180,8 +189,12 @@ pub(crate) fn populate(cx: &mut DocContext<'_>, mut krate: clean::Crate) -> clea
         }
 
         let (krate, mut impl_ids) = {
-            let mut cache_builder =
-                CacheBuilder { tcx, cache: &mut cx.cache, impl_ids: Default::default() };
+            let mut cache_builder = CacheBuilder {
+                tcx,
+                cache: &mut cx.cache,
+                impl_ids: Default::default(),
+                collect_used_by: cx.render_options.show_used_by,
+            };
             krate = cache_builder.fold_crate(krate);
             (krate, cache_builder.impl_ids)
         };
@@ -254,6 +267,11 @@ fn fold_item(&mut self, item: clean::Item) -> Option<clean::Item> {
                 .push(Impl { impl_item: item.clone() });
         }
 
+        // Collect the items mentioned in the signature of this item.
+        if self.collect_used_by && !self.cache.stripped_mod && !item.is_stripped() {
+            self.record_used_by(&item);
+        }
+
         // Index this method for searching later on.
         if let Some(s) = item.name.or_else(|| {
             if item.is_stripped() {
@@ -538,6 +556,179 @@ fn fold_item(&mut self, item: clean::Item) -> Option<clean::Item> {
     }
 }
 
+impl<'a, 'tcx> CacheBuilder<'a, 'tcx> {
+    fn record_used_by(&mut self, item: &clean::Item) {
+        let mut dids = FxIndexSet::default();
+        let used_by = match *item.kind {
+            clean::FunctionItem(ref f) | clean::ForeignFunctionItem(ref f) => {
+                collect_function_def_ids(f, &mut dids);
+                UsedBy { page: item.item_id.expect_def_id(), assoc: None, trait_impl: false }
+            }
+            clean::TyMethodItem(ref f) | clean::MethodItem(ref f, _) => {
+                // Methods of trait impls are documented on the trait itself.
+                let page = match self.cache.parent_stack.last() {
+                    Some(ParentStackItem::Type(item_id)) => item_id.as_def_id(),
+                    Some(ParentStackItem::Impl { for_, trait_: None, .. }) => {
+                        for_.def_id(self.cache)
+                    }
+                    _ => None,
+                };
+                let (Some(page), Some(name)) = (page, item.name) else { return };
+                collect_function_def_ids(f, &mut dids);
+                UsedBy { page, assoc: Some((item.type_(), name)), trait_impl: false }
+            }
+            clean::ImplItem(ref i) => {
+                let Some(trait_) = &i.trait_ else { return };
+                if !item.item_id.is_local() || i.kind.is_blanket() || i.kind.is_auto() {
+                    return;
+                }
+                // Impls for local types are already listed on the type's page.
+                let Some(did) = i.for_.without_borrowed_ref().def_id(self.cache) else { return };
+                if did.is_local() {
+                    return;
+                }
+                dids.insert(did);
+                UsedBy { page: trait_.def_id(), assoc: None, trait_impl: true }
+            }
+            _ => return,
+        };
+        for did in dids {
+            if did != used_by.page {
+                self.cache.used_by.entry(did).or_default().insert(used_by);
+            }
+        }
+    }
+}
+
+/// An item mentioning another item in its signature, see [`Cache::used_by`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct UsedBy {
+    /// The item whose page documents the signature.
+    pub(crate) page: DefId,
+    /// The associated item of `page` with the signature, if any.
+    pub(crate) assoc: Option<(ItemType, Symbol)>,
+    /// Whether the signature is the one of an impl of the `page` trait.
+    pub(crate) trait_impl: bool,
+}
+
+fn collect_function_def_ids(f: &clean::Function, dids: &mut FxIndexSet<DefId>) {
+    collect_generics_def_ids(&f.generics, dids);
+    collect_decl_def_ids(&f.decl, dids);
+}
+
+fn collect_decl_def_ids(decl: &clean::FnDecl, dids: &mut FxIndexSet<DefId>) {
+    for arg in &decl.inputs.values {
+        collect_type_def_ids(&arg.type_, dids);
+    }
+    collect_type_def_ids(&decl.output, dids);
+}
+
+fn collect_generics_def_ids(generics: &clean::Generics, dids: &mut FxIndexSet<DefId>) {
+    for param in &generics.params {
+        match param.kind {
+            clean::GenericParamDefKind::Type { ref bounds, ref default, .. } => {
+                collect_bounds_def_ids(bounds, dids);
+                if let Some(default) = default {
+                    collect_type_def_ids(default, dids);
+                }
+            }
+            clean::GenericParamDefKind::Const { ref ty, .. } => collect_type_def_ids(ty, dids),
+            clean::GenericParamDefKind::Lifetime { .. } => {}
+        }
+    }
+    for predicate in &generics.where_predicates {
+        match predicate {
+            clean::WherePredicate::BoundPredicate { ty, bounds, .. } => {
+                collect_type_def_ids(ty, dids);
+                collect_bounds_def_ids(bounds, dids);
+            }
+            clean::WherePredicate::EqPredicate { lhs, rhs, .. } => {
+                collect_type_def_ids(lhs, dids);
+                if let Some(rhs) = rhs.ty() {
+                    collect_type_def_ids(rhs, dids);
+                }
+            }
+            clean::WherePredicate::RegionPredicate { .. } => {}
+        }
+    }
+}
+
+fn collect_bounds_def_ids(bounds: &[clean::GenericBound], dids: &mut FxIndexSet<DefId>) {
+    for bound in bounds {
+        if let clean::GenericBound::TraitBound(poly_trait, _) = bound {
+            collect_path_def_ids(&poly_trait.trait_, dids);
+        }
+    }
+}
+
+fn collect_path_def_ids(path: &clean::Path, dids: &mut FxIndexSet<DefId>) {
+    if let Some(did) = path.res.opt_def_id() {
+        dids.insert(did);
+    }
+    for segment in &path.segments {
+        match segment.args {
+            clean::GenericArgs::AngleBracketed { ref args, ref bindings } => {
+                for arg in args.iter() {
+                    if let clean::GenericArg::Type(ty) = arg {
+                        collect_type_def_ids(ty, dids);
+                    }
+                }
+                for binding in bindings {
+                    match binding.kind {
+                        clean::TypeBindingKind::Equality { ref term } => {
+                            if let Some(ty) = term.ty() {
+                                collect_type_def_ids(ty, dids);
+                            }
+                        }
+                        clean::TypeBindingKind::Constraint { ref bounds } => {
+                            collect_bounds_def_ids(bounds, dids);
+                        }
+                    }
+                }
+            }
+            clean::GenericArgs::Parenthesized { ref inputs, ref output } => {
+                for input in inputs.iter() {
+                    collect_type_def_ids(input, dids);
+                }
+                if let Some(output) = output {
+                    collect_type_def_ids(output, dids);
+                }
+            }
+        }
+    }
+}
+
+/// Collects the IDs of all the types and traits named in `ty`. Primitive types are
+/// left out since they are used pretty much everywhere.
+fn collect_type_def_ids(ty: &clean::Type, dids: &mut FxIndexSet<DefId>) {
+    match ty {
+        clean::Type::Path { path } => collect_path_def_ids(path, dids),
+        clean::Type::DynTrait(bounds, _) => {
+            for bound in bounds {
+                collect_path_def_ids(&bound.trait_, dids);
+            }
+        }
+        clean::Type::ImplTrait(bounds) => collect_bounds_def_ids(bounds, dids),
+        clean::Type::BareFunction(f) => collect_decl_def_ids(&f.decl, dids),
+        clean::Type::Tuple(tys) => {
+            for ty in tys {
+                collect_type_def_ids(ty, dids);
+            }
+        }
+        clean::Type::Slice(ty)
+        | clean::Type::Array(ty, _)
+        | clean::Type::RawPointer(_, ty)
+        | clean::Type::BorrowedRef { type_: ty, .. } => collect_type_def_ids(ty, dids),
+        clean::Type::QPath(qpath) => {
+            collect_type_def_ids(&qpath.self_type, dids);
+            if let Some(trait_) = &qpath.trait_ {
+                collect_path_def_ids(trait_, dids);
+            }
+        }
+        clean::Type::Generic(_) | clean::Type::Primitive(_) | clean::Type::Infer => {}
+    }
+}
+
 pub(crate) struct OrphanImplItem {
     pub(crate) parent: DefId,
     pub(crate) item: clean::Item,
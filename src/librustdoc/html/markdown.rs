@@ -718,7 +718,15 @@ pub(crate) fn find_testable_code<T: doctest::Tester>(
     let mut prev_offset = 0;
     let mut nb_lines = 0;
     let mut register_header = None;
+    // A doctest is only added once we know whether the next code block holds its expected output.
+    let mut pending_test: Option<(String, LangString, usize)> = None;
     while let Some((event, offset)) = parser.next() {
+        // An `output` block only belongs to the doctest it directly follows.
+        if !matches!(event, Event::Start(Tag::CodeBlock(_)))
+            && let Some((text, config, line)) = pending_test.take()
+        {
+            tests.add_test(text, config, line);
+        }
         match event {
             Event::Start(Tag::CodeBlock(kind)) => {
                 let block_info = match kind {
@@ -736,6 +744,18 @@ pub(crate) fn find_testable_code<T: doctest::Tester>(
                     }
                     CodeBlockKind::Indented => Default::default(),
                 };
+                if block_info.output && let Some((text, mut config, line)) = pending_test.take() {
+                    let mut expected_output = String::new();
+                    while let Some((Event::Text(s), _)) = parser.next() {
+                        expected_output.push_str(&s);
+                    }
+                    config.expected_output = Some(expected_output);
+                    tests.add_test(text, config, line);
+                    continue;
+                }
+                if let Some((text, config, line)) = pending_test.take() {
+                    tests.add_test(text, config, line);
+                }
                 if !block_info.rust {
                     continue;
                 }
@@ -759,7 +779,7 @@ pub(crate) fn find_testable_code<T: doctest::Tester>(
                     nb_lines -= 1;
                 }
                 let line = tests.get_line() + nb_lines + 1;
-                tests.add_test(text, block_info, line);
+                pending_test = Some((text, block_info, line));
                 prev_offset = offset.start;
             }
             Event::Start(Tag::Heading(level, _, _)) => {
@@ -773,6 +793,9 @@ pub(crate) fn find_testable_code<T: doctest::Tester>(
             _ => {}
         }
     }
+    if let Some((text, config, line)) = pending_test {
+        tests.add_test(text, config, line);
+    }
 }
 
 pub(crate) struct ExtraInfo<'tcx> {
@@ -810,6 +833,10 @@ pub(crate) struct LangString {
     pub(crate) compile_fail: bool,
     pub(crate) error_codes: Vec<String>,
     pub(crate) edition: Option<Edition>,
+    /// Whether this block holds the expected output of the doctest preceding it.
+    pub(crate) output: bool,
+    /// The expected output of this doctest, if it is followed by an `output` block.
+    pub(crate) expected_output: Option<String>,
 }
 
 #[derive(Eq, PartialEq, Clone, Debug)]
@@ -831,6 +858,8 @@ fn default() -> Self {
             compile_fail: false,
             error_codes: Vec::new(),
             edition: None,
+            output: false,
+            expected_output: None,
         }
     }
 }
@@ -919,6 +948,10 @@ fn parse(
                 x if x.starts_with("edition") => {
                     data.edition = x[7..].parse::<Edition>().ok();
                 }
+                "output" => {
+                    data.output = true;
+                    seen_other_tags = true;
+                }
                 x if allow_error_code_check && x.starts_with('E') && x.len() == 5 => {
                     if x[1..].parse::<u32>().is_ok() {
                         data.error_codes.push(x.to_owned());
@@ -1553,6 +1586,8 @@ fn init_id_map() -> FxHashMap<Cow<'static, str>, usize> {
     map.insert("blanket-implementations-list".into(), 1);
     map.insert("deref-methods".into(), 1);
     map.insert("layout".into(), 1);
+    map.insert("used-by".into(), 1);
+    map.insert("used-by-list".into(), 1);
     map
 }
 
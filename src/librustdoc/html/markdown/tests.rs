@@ -117,6 +117,13 @@ fn t(lg: LangString) {
         edition: Some(Edition::Edition2018),
         ..Default::default()
     });
+    t(LangString { original: "output".into(), output: true, rust: false, ..Default::default() });
+    t(LangString {
+        original: "text,output".into(),
+        output: true,
+        rust: false,
+        ..Default::default()
+    });
 }
 
 #[test]
@@ -308,6 +315,35 @@ fn add_test(&mut self, _test: String, _config: LangString, line: usize) {
     t("\n ```rust\n```", &[2]);
     t("```rust\n```\n```rust\n```", &[1, 3]);
     t("```rust\n```\n ```rust\n```", &[1, 3]);
+    t("```rust\n```\n```text,output\n```\n```rust\n```", &[1, 5]);
+}
+
+#[test]
+fn test_find_testable_code_expected_output() {
+    fn t(input: &str, expect: &[Option<&str>]) {
+        struct Outputs(Vec<Option<String>>);
+        impl crate::doctest::Tester for Outputs {
+            fn add_test(&mut self, _test: String, config: LangString, _line: usize) {
+                self.0.push(config.expected_output);
+            }
+        }
+        let mut outputs = Outputs(Vec::new());
+        find_testable_code(input, &mut outputs, ErrorCodes::No, false, None);
+        assert_eq!(outputs.0.iter().map(Option::as_deref).collect::<Vec<_>>(), expect);
+    }
+
+    t("```rust\n```", &[None]);
+    t("```rust\n```\n```text,output\nhello\n```", &[Some("hello\n")]);
+    t("```rust\n```\n```output\nhello\n```", &[Some("hello\n")]);
+    // The output block must directly follow the doctest it belongs to.
+    t("```rust\n```\n```text\n```\n```text,output\nhello\n```", &[None]);
+    t("```text,output\nhello\n```\n```rust\n```", &[None]);
+    t("```rust\n```\n\nIt prints:\n\n```text,output\nhello\n```", &[None]);
+    t("```rust\n```\n# Output\n```text,output\nhello\n```", &[None]);
+    t(
+        "```rust\n```\n```text,output\na\n```\n```rust\n```\n```text,output\nb\n```",
+        &[Some("a\n"), Some("b\n")],
+    );
 }
 
 #[test]
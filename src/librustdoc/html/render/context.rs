@@ -20,7 +20,7 @@
     collect_spans_and_sources, scrape_examples_help,
     sidebar::print_sidebar,
     sidebar::{sidebar_module_like, Sidebar},
-    AllTypes, LinkFromSrc, StylePath,
+    AllTypes, ApiHistory, LinkFromSrc, StylePath,
 };
 use crate::clean::{self, types::ExternalLocation, ExternalCrate};
 use crate::config::{ModuleSorting, RenderOptions};
@@ -94,6 +94,8 @@ pub(crate) struct SharedContext<'tcx> {
     pub(crate) local_sources: FxHashMap<PathBuf, String>,
     /// Show the memory layout of types in the docs.
     pub(super) show_type_layout: bool,
+    /// List the items using a type or trait on its page.
+    pub(super) show_used_by: bool,
     /// The base-URL of the issue tracker for when an item has been tagged with
     /// an issue number.
     pub(super) issue_tracker_base_url: Option<String>,
@@ -123,6 +125,10 @@ pub(crate) struct SharedContext<'tcx> {
     /// to `Some(...)`, it'll store redirections and then generate a JSON file at the top level of
     /// the crate.
     redirections: Option<RefCell<FxHashMap<String, String>>>,
+    /// `None` by default, depends on the `generate-api-history` option flag. If this field is set
+    /// to `Some(...)`, it'll collect the versions in which items were added or deprecated and
+    /// then generate the `history.html` and `history.json` files in the crate folder.
+    api_history: Option<RefCell<ApiHistory>>,
 
     /// Correspondence map used to link types used in the source code pages to allow to click on
     /// links to jump to the type's definition.
@@ -460,7 +466,9 @@ fn init(
             static_root_path,
             generate_redirect_map,
             show_type_layout,
+            show_used_by,
             generate_link_to_definition,
+            generate_api_history,
             call_locations,
             no_emit_shared,
             ..
@@ -542,7 +550,9 @@ fn init(
             all: RefCell::new(AllTypes::new()),
             errors: receiver,
             redirections: if generate_redirect_map { Some(Default::default()) } else { None },
+            api_history: if generate_api_history { Some(Default::default()) } else { None },
             show_type_layout,
+            show_used_by,
             span_correspondence_map: matches,
             cache,
             call_locations,
@@ -746,6 +756,30 @@ fn after_krate(&mut self) -> Result<(), Error> {
             shared.fs.write(scrape_examples_help_file, v)?;
         }
 
+        if let Some(ref api_history) = shared.api_history && !api_history.borrow().is_empty() {
+            let api_history = api_history.borrow();
+            let crate_dst = self.dst.join(crate_name.as_str());
+            shared.ensure_dir(&crate_dst)?;
+
+            page.title = "API history";
+            page.description = "Items added or deprecated in each version of this crate";
+            page.root_path = "../";
+            let sidebar = format!(
+                "<h2 class=\"location\"><a href=\"index.html\">Crate {}</a></h2>\
+                 <div class=\"sidebar-elems\"></div>",
+                Escape(crate_name.as_str()),
+            );
+            let v = layout::render(
+                &shared.layout,
+                &page,
+                sidebar,
+                |buf: &mut Buffer| api_history.print(buf),
+                &shared.style_files,
+            );
+            shared.fs.write(crate_dst.join("history.html"), v)?;
+            shared.fs.write(crate_dst.join("history.json"), api_history.to_json())?;
+        }
+
         if let Some(ref redirections) = shared.redirections && !redirections.borrow().is_empty() {
             let redirect_map_path =
                 self.dst.join(crate_name.as_str()).join("redirect-map.json");
@@ -851,6 +885,28 @@ fn item(&mut self, item: clean::Item) -> Result<(), Error> {
 
             if !self.render_redirect_pages {
                 self.shared.all.borrow_mut().append(full_path(self, &item), &item_type);
+                if let Some(ref api_history) = self.shared.api_history {
+                    let inherent_impls = item
+                        .item_id
+                        .as_def_id()
+                        .and_then(|did| self.cache().impls.get(&did))
+                        .into_iter()
+                        .flatten()
+                        .filter(|i| i.inner_impl().trait_.is_none());
+                    let assoc_items = match *item.kind {
+                        clean::TraitItem(ref t) => &t.items[..],
+                        _ => &[][..],
+                    }
+                    .iter()
+                    .chain(inherent_impls.flat_map(|i| i.inner_impl().items.iter()));
+                    api_history.borrow_mut().append_with_assoc_items(
+                        self.tcx(),
+                        full_path(self, &item),
+                        &item_type,
+                        &item,
+                        assoc_items,
+                    );
+                }
             }
             // If the item is a macro, redirect from the old macro URL (with !)
             // to the new one (without).
@@ -33,6 +33,7 @@
 mod sidebar;
 mod span_map;
 mod type_layout;
+mod used_by;
 mod write_shared;
 
 pub(crate) use self::context::*;
@@ -266,11 +267,7 @@ fn new() -> AllTypes {
     }
 
     fn append(&mut self, item_name: String, item_type: &ItemType) {
-        let mut url: Vec<_> = item_name.split("::").skip(1).collect();
-        if let Some(name) = url.pop() {
-            let new_url = format!("{}/{}.{}.html", url.join("/"), item_type, name);
-            url.push(name);
-            let name = url.join("::");
+        if let Some((new_url, name)) = item_entry_parts(&item_name, item_type) {
             match *item_type {
                 ItemType::Struct => self.structs.insert(ItemEntry::new(new_url, name)),
                 ItemType::Enum => self.enums.insert(ItemEntry::new(new_url, name)),
@@ -382,6 +379,147 @@ fn print_entries(f: &mut Buffer, e: &FxHashSet<ItemEntry>, kind: ItemSection) {
     }
 }
 
+/// Splits the full path of an item (starting with the crate name) into the URL of its page,
+/// relative to the crate root, and its path inside the crate.
+fn item_entry_parts(item_name: &str, item_type: &ItemType) -> Option<(String, String)> {
+    let mut url: Vec<_> = item_name.split("::").skip(1).collect();
+    let name = url.pop()?;
+    let new_url = format!("{}/{}.{}.html", url.join("/"), item_type, name);
+    url.push(name);
+    Some((new_url, url.join("::")))
+}
+
+/// The kind of change an item went through in a given version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ApiChange {
+    Added,
+    ConstStabilized,
+    Deprecated,
+}
+
+impl ApiChange {
+    fn title(self) -> &'static str {
+        match self {
+            ApiChange::Added => "Added",
+            ApiChange::ConstStabilized => "Stabilized in const contexts",
+            ApiChange::Deprecated => "Deprecated",
+        }
+    }
+}
+
+/// Aggregates the `since` versions of the `#[stable]`, `#[rustc_const_stable]`, `#[deprecated]`
+/// and `#[doc(since)]` attributes of all items, to generate the "API history" page.
+#[derive(Debug, Default)]
+struct ApiHistory {
+    versions: FxHashMap<Symbol, FxHashSet<(ApiChange, ItemEntry)>>,
+}
+
+impl ApiHistory {
+    fn append(&mut self, tcx: TyCtxt<'_>, url: &str, name: &str, item: &clean::Item) {
+        let mut record = |version: Option<Symbol>, change| {
+            if let Some(version) = version.filter(|v| !v.is_empty()) {
+                self.versions
+                    .entry(version)
+                    .or_default()
+                    .insert((change, ItemEntry::new(url.to_owned(), name.to_owned())));
+            }
+        };
+        record(item.added_since(tcx), ApiChange::Added);
+        record(item.const_stable_since(tcx), ApiChange::ConstStabilized);
+        // Deprecations planned for a future rustc version haven't happened yet.
+        let deprecated_since = item
+            .deprecation(tcx)
+            .filter(|depr| stability::deprecation_in_effect(depr))
+            .and_then(|depr| depr.since);
+        record(deprecated_since, ApiChange::Deprecated);
+    }
+
+    /// Records `item`, which has its own page, as well as its associated items.
+    fn append_with_assoc_items<'a>(
+        &mut self,
+        tcx: TyCtxt<'_>,
+        item_name: String,
+        item_type: &ItemType,
+        item: &clean::Item,
+        assoc_items: impl Iterator<Item = &'a clean::Item>,
+    ) {
+        let Some((url, name)) = item_entry_parts(&item_name, item_type) else { return };
+        self.append(tcx, &url, &name, item);
+        for assoc_item in assoc_items {
+            let Some(assoc_name) = assoc_item.name else { continue };
+            let assoc_url = format!("{url}#{}.{assoc_name}", assoc_item.type_());
+            self.append(tcx, &assoc_url, &format!("{name}::{assoc_name}"), assoc_item);
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.versions.is_empty()
+    }
+
+    /// Returns the recorded versions, most recent first, with their sorted changes.
+    fn sorted(&self) -> Vec<(Symbol, Vec<&(ApiChange, ItemEntry)>)> {
+        fn version_key(version: Symbol) -> Vec<Result<u64, String>> {
+            version.as_str().split('.').map(|c| c.parse().map_err(|_| c.to_owned())).collect()
+        }
+
+        let mut versions: Vec<_> = self
+            .versions
+            .iter()
+            .map(|(version, changes)| {
+                let mut changes: Vec<_> = changes.iter().collect();
+                changes.sort();
+                (*version, changes)
+            })
+            .collect();
+        versions.sort_by_cached_key(|(version, _)| std::cmp::Reverse(version_key(*version)));
+        versions
+    }
+
+    fn print(&self, f: &mut Buffer) {
+        f.write_str("<h1>API history</h1>");
+        for (version, changes) in self.sorted() {
+            write!(
+                f,
+                "<h2 id=\"version-{version}\">{version}</h2>",
+                version = Escape(version.as_str()),
+            );
+            let mut changes = changes.into_iter().peekable();
+            while let Some(&&(change, _)) = changes.peek() {
+                write!(f, "<h3>{}</h3><ul class=\"all-items\">", change.title());
+                while let Some((_, entry)) = changes.next_if(|(c, _)| *c == change) {
+                    write!(f, "<li>{}</li>", entry.print());
+                }
+                f.write_str("</ul>");
+            }
+        }
+    }
+
+    fn to_json(&self) -> String {
+        #[derive(Serialize)]
+        struct JsonEntry<'a> {
+            version: &'a str,
+            change: ApiChange,
+            name: &'a str,
+            url: &'a str,
+        }
+
+        let sorted = self.sorted();
+        let entries: Vec<_> = sorted
+            .iter()
+            .flat_map(|(version, changes)| {
+                changes.iter().map(move |(change, entry)| JsonEntry {
+                    version: version.as_str(),
+                    change: *change,
+                    name: &entry.name,
+                    url: &entry.url,
+                })
+            })
+            .collect();
+        serde_json::to_string(&entries).unwrap()
+    }
+}
+
 fn scrape_examples_help(shared: &SharedContext<'_>) -> String {
     let mut content = SCRAPE_EXAMPLES_HELP_MD.to_owned();
     content.push_str(&format!(
@@ -15,6 +15,7 @@
 use std::rc::Rc;
 
 use super::type_layout::document_type_layout;
+use super::used_by::document_used_by;
 use super::{
     collect_paths_for_type, document, ensure_trailing_slash, get_filtered_impls_for_reference,
     item_ty_to_section, notable_traits_button, notable_traits_json, render_all_impls,
@@ -289,6 +290,8 @@ pub(super) fn print_item(
         }
     }
 
+    write!(buf, "{}", document_used_by(cx, item));
+
     // Render notable-traits.js used for all methods in this module.
     if !cx.types_with_notable_traits.is_empty() {
         write!(
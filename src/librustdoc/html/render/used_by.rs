@@ -0,0 +1,111 @@
+use std::fmt;
+use std::iter::{once, repeat};
+
+use rustc_data_structures::captures::Captures;
+use rustc_span::Symbol;
+
+use crate::clean;
+use crate::formats::cache::UsedBy;
+use crate::formats::item_type::ItemType;
+use crate::html::escape::Escape;
+use crate::html::format::{display_fn, href, join_with_double_colon};
+use crate::html::render::Context;
+use crate::html::url_parts_builder::UrlPartsBuilder;
+
+/// Returns the text and the URL of the link to the item described by `used_by`. The URL is
+/// relative to the current page of `cx`.
+pub(super) fn used_by_link(cx: &Context<'_>, used_by: &UsedBy) -> Option<(String, String)> {
+    let (mut url, _, fqp) = href(used_by.page, cx).ok()?;
+    let mut text = join_with_double_colon(&fqp);
+    if used_by.trait_impl {
+        text.insert_str(0, "impl ");
+        url.push_str("#implementors");
+    } else if let Some((item_type, name)) = used_by.assoc {
+        text.push_str("::");
+        text.push_str(name.as_str());
+        url.push_str(&format!("#{item_type}.{name}"));
+    }
+    Some((text, url))
+}
+
+/// Returns the key under which the users of the item with the given type and path are stored in
+/// the `used-by` javascript file of its crate.
+pub(super) fn used_by_key(item_type: ItemType, fqp: &[Symbol]) -> String {
+    let (name, module) = fqp.split_last().expect("empty path");
+    let mut key: UrlPartsBuilder = module.iter().copied().collect();
+    key.push_fmt(format_args!("{item_type}.{name}"));
+    key.finish()
+}
+
+/// Renders the "Used by" section of a type or trait page, listing the documented items which
+/// mention it in their signatures. The section is left out if no item of the current crate uses
+/// the type or trait.
+///
+/// The items from the crates depending on it are added by the `used-by` javascript file of the
+/// crate defining the type or trait, which creates the section if needed. This file is generated
+/// in the same way as the implementors ones (see `item_trait` and `write_shared`), except that
+/// there is one per crate, so it always exists.
+pub(super) fn document_used_by<'a, 'cx: 'a>(
+    cx: &'a Context<'cx>,
+    it: &'a clean::Item,
+) -> impl fmt::Display + 'a + Captures<'cx> {
+    display_fn(move |f| {
+        if !cx.shared.show_used_by {
+            return Ok(());
+        }
+        if !matches!(
+            *it.kind,
+            clean::StructItem(..)
+                | clean::EnumItem(..)
+                | clean::UnionItem(..)
+                | clean::TraitItem(..)
+                | clean::TraitAliasItem(..)
+                | clean::TypedefItem(..)
+                | clean::ForeignTypeItem
+        ) {
+            return Ok(());
+        }
+        let Some(did) = it.item_id.as_def_id() else { return Ok(()) };
+        let cache = cx.cache();
+
+        let mut links = cache
+            .used_by
+            .get(&did)
+            .into_iter()
+            .flatten()
+            .filter_map(|used_by| used_by_link(cx, used_by))
+            .collect::<Vec<_>>();
+        links.sort();
+        links.dedup();
+
+        if !links.is_empty() {
+            write!(
+                f,
+                "<h2 id=\"used-by\" class=\"small-section-header\">\
+                    Used by<a href=\"#used-by\" class=\"anchor\">§</a>\
+                 </h2>\
+                 <ul id=\"used-by-list\">"
+            )?;
+            for (text, url) in &links {
+                write!(f, "<li><a href=\"{url}\"><code>{}</code></a></li>", Escape(text))?;
+            }
+            f.write_str("</ul>")?;
+        }
+
+        let fqp: Vec<Symbol> = cache
+            .exact_paths
+            .get(&did)
+            .or_else(|| cache.external_paths.get(&did).map(|s| &s.0))
+            .cloned()
+            .unwrap_or_else(|| cx.current.iter().copied().chain(it.name).collect());
+        let mut js_src_path: UrlPartsBuilder =
+            repeat("..").take(cx.current.len()).chain(once("used-by")).collect();
+        js_src_path.push_fmt(format_args!("{}.js", fqp[0]));
+        write!(
+            f,
+            "<script src=\"{}\" data-used-by-item=\"{}\" async></script>",
+            js_src_path.finish(),
+            Escape(&used_by_key(it.type_(), &fqp)),
+        )
+    })
+}
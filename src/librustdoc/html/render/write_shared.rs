@@ -1,4 +1,5 @@
 use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::fs::{self, File};
 use std::io::prelude::*;
 use std::io::{self, BufReader};
@@ -8,9 +9,11 @@
 use itertools::Itertools;
 use rustc_data_structures::flock;
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_span::Symbol;
 use serde::ser::SerializeSeq;
 use serde::{Serialize, Serializer};
 
+use super::used_by::{used_by_key, used_by_link};
 use super::{collect_paths_for_type, ensure_trailing_slash, Context};
 use crate::clean::Crate;
 use crate::config::{EmitType, RenderOptions};
@@ -466,5 +469,79 @@ fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         v.push_str("})()");
         cx.shared.fs.write(mydst, v)?;
     }
+
+    // Update the lists of the items using types and traits from other crates, which are built
+    // in the same way as the implementors ones, except that there is one file per crate defining
+    // the types and traits.
+    if cx.shared.show_used_by {
+        let dst = cx.dst.join("used-by");
+        cx.shared.ensure_dir(&dst)?;
+
+        // The file of the current crate is always written, so that its pages never load a
+        // missing file.
+        let mut used_by_crate: FxHashMap<Symbol, BTreeMap<String, Vec<(String, String)>>> =
+            FxHashMap::default();
+        used_by_crate.entry(krate.name(cx.tcx())).or_default();
+        for (&did, users) in &cache.used_by {
+            // The items of the current crate are directly listed in the page.
+            if did.is_local() {
+                continue;
+            }
+            let Some(&(_, remote_item_type)) =
+                cache.paths.get(&did).or_else(|| cache.external_paths.get(&did))
+            else {
+                continue;
+            };
+            let Some(remote_path) = cache
+                .exact_paths
+                .get(&did)
+                .or_else(|| cache.external_paths.get(&did).map(|s| &s.0))
+            else {
+                continue;
+            };
+
+            let mut links =
+                users.iter().filter_map(|used_by| used_by_link(cx, used_by)).collect::<Vec<_>>();
+            if links.is_empty() {
+                continue;
+            }
+            links.sort();
+            links.dedup();
+
+            used_by_crate
+                .entry(remote_path[0])
+                .or_default()
+                .insert(used_by_key(remote_item_type, remote_path), links);
+        }
+
+        for (remote_crate, items) in used_by_crate {
+            let used_by = format!(
+                r#""{}":{}"#,
+                krate.name(cx.tcx()),
+                serde_json::to_string(&items).expect("failed serde conversion"),
+            );
+
+            let mydst = dst.join(format!("{remote_crate}.js"));
+            let (mut all_used_by, _) =
+                try_err!(collect(&mydst, krate.name(cx.tcx()).as_str()), &mydst);
+            all_used_by.push(used_by);
+            // Sort the lists by crate so the file will be generated identically even with
+            // rustdoc running in parallel.
+            all_used_by.sort();
+
+            let mut v = String::from("(function() {var usedBy = {\n");
+            v.push_str(&all_used_by.join(",\n"));
+            v.push_str("\n};");
+            v.push_str(
+                "if (window.register_used_by) {\
+                     window.register_used_by(usedBy);\
+                 } else {\
+                     window.pending_used_by = usedBy;\
+                 }",
+            );
+            v.push_str("})()");
+            cx.shared.fs.write(mydst, v)?;
+        }
+    }
     Ok(())
 }
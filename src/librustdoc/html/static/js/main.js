@@ -596,6 +596,61 @@ function preLoadCss(cssUrl) {
         window.register_implementors(window.pending_implementors);
     }
 
+    window.register_used_by = usedBy => {
+        const script = document.querySelector("script[data-used-by-item]");
+        if (!script) {
+            return;
+        }
+        const key = script.getAttribute("data-used-by-item");
+        const TEXT_IDX = 0;
+        const HREF_IDX = 1;
+
+        let list = document.getElementById("used-by-list");
+        for (const lib of Object.getOwnPropertyNames(usedBy)) {
+            // The items of the current crate are already in the HTML.
+            if (lib === window.currentCrate ||
+                !Object.prototype.hasOwnProperty.call(usedBy[lib], key)) {
+                continue;
+            }
+            if (!list) {
+                // No item of the current crate uses this one, so the section isn't in the HTML.
+                const anchor = document.createElement("a");
+                anchor.href = "#used-by";
+                anchor.className = "anchor";
+                anchor.textContent = "§";
+
+                const header = document.createElement("h2");
+                header.id = "used-by";
+                header.className = "small-section-header";
+                header.appendChild(document.createTextNode("Used by"));
+                header.appendChild(anchor);
+
+                list = document.createElement("ul");
+                list.id = "used-by-list";
+
+                const mainContent = document.getElementById("main-content");
+                mainContent.appendChild(header);
+                mainContent.appendChild(list);
+            }
+            for (const item of usedBy[lib][key]) {
+                const code = document.createElement("code");
+                code.textContent = item[TEXT_IDX];
+
+                const link = document.createElement("a");
+                const href = item[HREF_IDX];
+                link.href = /^(?:[a-z+]+:)?\/\//.test(href) ? href : window.rootPath + href;
+                link.appendChild(code);
+
+                const li = document.createElement("li");
+                li.appendChild(link);
+                list.appendChild(li);
+            }
+        }
+    };
+    if (window.pending_used_by) {
+        window.register_used_by(window.pending_used_by);
+    }
+
     function addSidebarCrates() {
         if (!window.ALL_CRATES) {
             return;
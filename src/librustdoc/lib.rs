@@ -552,6 +552,30 @@ fn opts() -> Vec<RustcOptGroup> {
         unstable("test-builder", |o| {
             o.optopt("", "test-builder", "The rustc-like binary to use as the test builder", "PATH")
         }),
+        unstable("doctest-interpreter", |o| {
+            o.optopt(
+                "",
+                "doctest-interpreter",
+                "The interpreter (like Miri) to build and run doctests with",
+                "PATH",
+            )
+        }),
+        unstable("doctest-interpreter-arg", |o| {
+            o.optmulti(
+                "",
+                "doctest-interpreter-arg",
+                "One (of possibly many) arguments to pass to the doctest interpreter",
+                "ARG",
+            )
+        }),
+        unstable("doctest-sanitizer", |o| {
+            o.optopt(
+                "",
+                "doctest-sanitizer",
+                "Build doctests with the given sanitizer enabled",
+                "address|leak|memory|thread|...",
+            )
+        }),
         unstable("check", |o| o.optflagmulti("", "check", "Run rustdoc checks")),
         unstable("generate-redirect-map", |o| {
             o.optflagmulti(
@@ -574,6 +598,13 @@ fn opts() -> Vec<RustcOptGroup> {
         unstable("show-type-layout", |o| {
             o.optflagmulti("", "show-type-layout", "Include the memory layout of types in the docs")
         }),
+        unstable("show-used-by", |o| {
+            o.optflagmulti(
+                "",
+                "show-used-by",
+                "List the items using a type or trait in their signatures on its page",
+            )
+        }),
         unstable("nocapture", |o| {
             o.optflag("", "nocapture", "Don't capture stdout and stderr of tests")
         }),
@@ -584,6 +615,13 @@ fn opts() -> Vec<RustcOptGroup> {
                 "Make the identifiers in the HTML source code pages navigable",
             )
         }),
+        unstable("generate-api-history", |o| {
+            o.optflag(
+                "",
+                "generate-api-history",
+                "Generate a page listing the items added or deprecated in each version",
+            )
+        }),
         unstable("scrape-examples-output-path", |o| {
             o.optopt(
                 "",
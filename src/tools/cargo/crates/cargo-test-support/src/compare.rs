@@ -328,6 +328,8 @@ fn add_regex_redactions(subs: &mut snapbox::Redactions) {
     ("[BLOCKING]", "    Blocking"),
     ("[GENERATED]", "   Generated"),
     ("[OPENING]", "     Opening"),
+    ("[VENDORING]", "   Vendoring"),
+    ("[STUBBING]", "    Stubbing"),
 ];
 
 /// Normalizes the output so that it can be compared against the expected value.
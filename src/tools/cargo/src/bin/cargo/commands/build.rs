@@ -37,6 +37,8 @@ pub fn cli() -> Command {
         .arg_artifact_dir()
         .arg_build_plan()
         .arg_unit_graph()
+        .arg_sbom()
+        .arg_explain_rebuilds()
         .arg_timings()
         .arg_manifest_path()
         .arg_lockfile_path()
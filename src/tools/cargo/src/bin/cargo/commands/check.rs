@@ -34,6 +34,7 @@ pub fn cli() -> Command {
         .arg_target_triple("Check for the target triple")
         .arg_target_dir()
         .arg_unit_graph()
+        .arg_explain_rebuilds()
         .arg_timings()
         .arg_manifest_path()
         .arg_lockfile_path()
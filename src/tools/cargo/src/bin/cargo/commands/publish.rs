@@ -18,7 +18,11 @@ pub fn cli() -> Command {
             "Allow dirty working directories to be packaged",
         ))
         .arg_silent_suggestion()
-        .arg_package("Package to publish")
+        .arg_package_spec_no_all(
+            "Package(s) to publish",
+            "Publish all packages in the workspace (unstable)",
+            "Don't publish specified packages (unstable)",
+        )
         .arg_features()
         .arg_parallel()
         .arg_target_triple("Build for the target triple")
@@ -31,6 +35,22 @@ pub fn cli() -> Command {
 }
 
 pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+    if args.flag("workspace") {
+        gctx.cli_unstable().fail_if_stable_opt_custom_z(
+            "--workspace",
+            10948,
+            "package-workspace",
+            gctx.cli_unstable().package_workspace,
+        )?;
+    }
+    if !args._values_of("exclude").is_empty() {
+        gctx.cli_unstable().fail_if_stable_opt_custom_z(
+            "--exclude",
+            10948,
+            "package-workspace",
+            gctx.cli_unstable().package_workspace,
+        )?;
+    }
     let reg_or_index = args.registry_or_index(gctx)?;
     let ws = args.workspace(gctx)?;
     if ws.root_maybe().is_embedded() {
@@ -1,6 +1,7 @@
 use crate::command_prelude::*;
 use cargo::core::compiler::future_incompat::{OnDiskReports, REPORT_PREAMBLE};
 use cargo::drop_println;
+use cargo::ops;
 
 pub fn cli() -> Command {
     subcommand("report")
@@ -23,11 +24,27 @@ pub fn cli() -> Command {
                 )
                 .arg_package("Package to display a report for"),
         )
+        .subcommand(
+            subcommand("outdated")
+                .about("Reports dependencies that have newer versions available (unstable)")
+                .arg(
+                    opt("format", "Output format")
+                        .value_parser(ops::OutdatedFormat::POSSIBLE_VALUES)
+                        .default_value("human"),
+                )
+                .arg_package_spec_no_all(
+                    "Package to report dependencies of",
+                    "Report dependencies of all packages in the workspace",
+                    "Exclude specific workspace members",
+                )
+                .arg_manifest_path(),
+        )
 }
 
 pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
     match args.subcommand() {
         Some(("future-incompatibilities", args)) => report_future_incompatibilities(gctx, args),
+        Some(("outdated", args)) => report_outdated(gctx, args),
         Some((cmd, _)) => {
             unreachable!("unexpected command {}", cmd)
         }
@@ -49,3 +66,20 @@ fn report_future_incompatibilities(gctx: &GlobalContext, args: &ArgMatches) -> C
     drop(gctx.shell().print_ansi_stdout(report.as_bytes()));
     Ok(())
 }
+
+fn report_outdated(gctx: &GlobalContext, args: &ArgMatches) -> CliResult {
+    gctx.cli_unstable().fail_if_stable_command(
+        gctx,
+        "report outdated",
+        4309,
+        "unstable-options",
+        gctx.cli_unstable().unstable_options,
+    )?;
+    let ws = args.workspace(gctx)?;
+    let opts = ops::OutdatedOptions {
+        to_report: args.packages_from_flags()?,
+        format: args.get_one::<String>("format").unwrap().parse()?,
+    };
+    ops::report_outdated(&ws, &opts)?;
+    Ok(())
+}
@@ -32,6 +32,11 @@ pub fn cli() -> Command {
             "versioned-dirs",
             "Always include version in subdir name",
         ))
+        .arg(multi_opt(
+            "platform",
+            "TRIPLE",
+            "Only vendor the full sources of crates used on the given platform",
+        ))
         .arg(unsupported("no-merge-sources"))
         .arg(unsupported("relative-path"))
         .arg(unsupported("only-git-deps"))
@@ -75,6 +80,11 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
             no_delete: args.flag("no-delete"),
             destination: &path,
             versioned_dirs: args.flag("versioned-dirs"),
+            platforms: args
+                .get_many::<String>("platform")
+                .unwrap_or_default()
+                .cloned()
+                .collect(),
             extra: args
                 .get_many::<PathBuf>("tomls")
                 .unwrap_or_default()
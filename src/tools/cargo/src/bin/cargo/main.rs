@@ -28,7 +28,9 @@ fn main() {
         }
     };
 
-    let result = if let Some(lock_addr) = cargo::ops::fix_get_proxy_lock_addr() {
+    let result = if let Some(policy) = cargo::core::compiler::sandbox_get_policy() {
+        cargo::core::compiler::sandbox_exec(&policy).map_err(|e| CliError::from(e))
+    } else if let Some(lock_addr) = cargo::ops::fix_get_proxy_lock_addr() {
         cargo::ops::fix_exec_rustc(&gctx, &lock_addr).map_err(|e| CliError::from(e))
     } else {
         let _token = cargo::util::job::setup();
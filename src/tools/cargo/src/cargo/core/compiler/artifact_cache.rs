@@ -0,0 +1,274 @@
+//! A local cache of compiled units, shared across workspaces.
+//!
+//! When `-Zartifact-cache` is enabled, Cargo stores the outputs of the units
+//! it builds for packages coming from registries and git repositories in a
+//! directory keyed by the hash of the unit's [`Fingerprint`]. The next time
+//! the same unit has to be built, in another workspace or after a
+//! `cargo clean`, the outputs are copied back from the cache instead of
+//! running `rustc` or the build script again.
+//!
+//! The fingerprint covers everything Cargo tracks to decide whether a unit is
+//! up-to-date: the `rustc` version, the profile, the features, the flags, the
+//! path of the sources and, transitively, the fingerprints of all the
+//! dependencies. Local packages are never cached, as their sources can be
+//! edited in place and their fingerprints depend on the mtime of these files,
+//! rather than on their contents. For the same reason, units which depend on a
+//! local package, directly or not, such as a registry package depending on a
+//! `[patch]`ed crate, aren't cached either.
+//!
+//! The cache is located at `$CARGO_HOME/artifact-cache` by default, and can
+//! be moved with the `build.artifact-cache-dir` config. Its layout is:
+//!
+//! ```text
+//! <cache dir>/
+//!   <package name>-<fingerprint hash>/
+//!     files/  # The outputs of `rustc`, or the `output`, `stderr` and
+//!             # `root-output` files of a build script run.
+//!     index   # The names of the files stored in `files/`, one per line.
+//!     out/    # The `OUT_DIR` of a build script run.
+//! ```
+//!
+//! Entries are written to a temporary directory first, which is then renamed
+//! into place, so that concurrent builds never see a partially written entry.
+//!
+//! Entries are never removed, so the cache grows without bound until it is
+//! deleted by hand.
+//!
+//! [`Fingerprint`]: super::fingerprint::Fingerprint
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use cargo_util::paths;
+
+use super::build_runner::BuildRunner;
+use super::fingerprint::Fingerprint;
+use super::job_queue::Work;
+use super::{BuildOutput, CompileMode, Unit};
+use crate::util::errors::CargoResult;
+use crate::util::interning::InternedString;
+use crate::util::GlobalContext;
+
+/// The directory of the artifact cache, enabled with `-Zartifact-cache`.
+pub struct ArtifactCache {
+    root: PathBuf,
+}
+
+impl ArtifactCache {
+    /// Returns the artifact cache configured for this build, or `None` if it
+    /// isn't enabled.
+    pub fn new(gctx: &GlobalContext) -> CargoResult<Option<ArtifactCache>> {
+        if !gctx.cli_unstable().artifact_cache {
+            return Ok(None);
+        }
+        let root = match &gctx.build_config()?.artifact_cache_dir {
+            Some(dir) => dir.resolve_path(gctx),
+            None => gctx.home().as_path_unlocked().join("artifact-cache"),
+        };
+        Ok(Some(ArtifactCache { root }))
+    }
+
+    /// Returns the entry of `unit` in the cache, or `None` if its outputs
+    /// can't be cached.
+    ///
+    /// This must be called after the fingerprint of `unit` is calculated.
+    pub fn entry(&self, build_runner: &BuildRunner<'_, '_>, unit: &Unit) -> Option<CacheEntry> {
+        if depends_on_local(build_runner, unit, &mut HashSet::new()) {
+            return None;
+        }
+        match unit.mode {
+            CompileMode::Build
+            | CompileMode::Check { test: false }
+            | CompileMode::RunCustomBuild => {}
+            _ => return None,
+        }
+        Some(CacheEntry {
+            root: self.root.clone(),
+            name: unit.pkg.name(),
+            fingerprint: Arc::clone(build_runner.fingerprints.get(unit)?),
+        })
+    }
+}
+
+/// Returns whether `unit` is local, or depends on a local unit, directly or
+/// not.
+fn depends_on_local(
+    build_runner: &BuildRunner<'_, '_>,
+    unit: &Unit,
+    visited: &mut HashSet<Unit>,
+) -> bool {
+    if unit.is_local() {
+        return true;
+    }
+    if !visited.insert(unit.clone()) {
+        return false;
+    }
+    build_runner
+        .unit_deps(unit)
+        .iter()
+        .any(|dep| depends_on_local(build_runner, &dep.unit, visited))
+}
+
+/// The location of the outputs of a unit in the [`ArtifactCache`].
+///
+/// The key of the entry is only computed when the unit is built, as the
+/// fingerprint of a unit changes once the build scripts it depends on have
+/// run and declared what they depend on.
+pub struct CacheEntry {
+    root: PathBuf,
+    name: InternedString,
+    fingerprint: Arc<Fingerprint>,
+}
+
+impl CacheEntry {
+    fn dir(&self) -> PathBuf {
+        self.root.join(format!(
+            "{}-{:016x}",
+            self.name,
+            self.fingerprint.hash_u64()
+        ))
+    }
+
+    /// Copies the files of this entry to `files`, and the contents of its
+    /// `OUT_DIR` to `out_dir`.
+    ///
+    /// Returns `false` if the cache has no entry for the unit, or if any of
+    /// the files stored in the entry can't be restored, in which case the
+    /// unit has to be built again.
+    pub fn restore(&self, files: &[PathBuf], out_dir: Option<&Path>) -> bool {
+        let dir = self.dir();
+        let Ok(index) = paths::read(&dir.join("index")) else {
+            return false;
+        };
+        let stored: HashSet<&str> = index.lines().collect();
+        let cached_files = dir.join("files");
+        for file in files {
+            let name = file.file_name().unwrap();
+            if !stored.contains(&*name.to_string_lossy()) {
+                continue;
+            }
+            if paths::copy(cached_files.join(name), file).is_err() {
+                return false;
+            }
+        }
+        match out_dir {
+            Some(out_dir) => copy_dir(&dir.join("out"), out_dir).is_ok(),
+            None => true,
+        }
+    }
+
+    /// Stores `files`, and the contents of `out_dir`, as the entry of the
+    /// unit. Files which don't exist, such as optional debuginfo, are
+    /// skipped.
+    pub fn store(&self, files: &[PathBuf], out_dir: Option<&Path>) -> CargoResult<()> {
+        let dir = self.dir();
+        if dir.exists() {
+            return Ok(());
+        }
+        let tmp_dir = self.root.join(format!(
+            ".tmp-{}-{}",
+            dir.file_name().unwrap().to_str().unwrap(),
+            process::id()
+        ));
+        let result = write_entry(&tmp_dir, files, out_dir).and_then(|()| {
+            // Another build may have stored the same unit in the meantime, in
+            // which case its entry is kept.
+            if fs::rename(&tmp_dir, &dir).is_err() && !dir.exists() {
+                anyhow::bail!("failed to move `{}` into place", dir.display());
+            }
+            Ok(())
+        });
+        if tmp_dir.exists() {
+            paths::remove_dir_all(&tmp_dir)?;
+        }
+        result.with_context(|| format!("failed to store `{}` in the artifact cache", dir.display()))
+    }
+}
+
+/// Writes `files`, and the contents of `out_dir`, to the entry directory `dir`.
+fn write_entry(dir: &Path, files: &[PathBuf], out_dir: Option<&Path>) -> CargoResult<()> {
+    let cached_files = dir.join("files");
+    paths::create_dir_all(&cached_files)?;
+    let mut index = String::new();
+    for file in files {
+        if file.is_file() {
+            let name = file.file_name().unwrap();
+            paths::copy(file, cached_files.join(name))?;
+            index.push_str(&name.to_string_lossy());
+            index.push('\n');
+        }
+    }
+    if let Some(out_dir) = out_dir {
+        copy_dir(out_dir, &dir.join("out"))?;
+    }
+    paths::write(&dir.join("index"), index)
+}
+
+/// Returns whether the run of a build script which printed `output` can be
+/// cached.
+///
+/// Build scripts can depend on the environment and on files outside their
+/// package, which aren't part of the fingerprint calculated before they run.
+/// Only the runs which don't declare such dependencies are stored. The runs
+/// which declare no dependencies at all aren't stored either, as the build
+/// script may still probe the environment, for example to find a system
+/// library.
+pub fn is_build_output_cacheable(output: &BuildOutput) -> bool {
+    !output.rerun_if_changed.is_empty()
+        && output.rerun_if_env_changed.is_empty()
+        && output
+            .rerun_if_changed
+            .iter()
+            .all(|path| path.is_relative())
+}
+
+/// Wraps the `rustc` work of `unit` so that its outputs are restored from the
+/// artifact cache when possible, and stored in it after a successful build
+/// otherwise.
+pub fn wrap_rustc(
+    build_runner: &BuildRunner<'_, '_>,
+    unit: &Unit,
+    rustc: Work,
+) -> CargoResult<Work> {
+    let Some(entry) = build_runner
+        .artifact_cache
+        .as_ref()
+        .and_then(|cache| cache.entry(build_runner, unit))
+    else {
+        return Ok(rustc);
+    };
+    let outputs: Vec<PathBuf> = build_runner
+        .outputs(unit)?
+        .iter()
+        .map(|output| output.path.clone())
+        .collect();
+    Ok(Work::new(move |state| {
+        if entry.restore(&outputs, None) {
+            return Ok(());
+        }
+        rustc.call(state)?;
+        if let Err(e) = entry.store(&outputs, None) {
+            state.warning(format!("{e:?}"))?;
+        }
+        Ok(())
+    }))
+}
+
+/// Recursively copies the contents of the `src` directory into `dst`.
+fn copy_dir(src: &Path, dst: &Path) -> CargoResult<()> {
+    paths::create_dir_all(dst)?;
+    for entry in walkdir::WalkDir::new(src).min_depth(1) {
+        let entry = entry?;
+        let target = dst.join(entry.path().strip_prefix(src).unwrap());
+        if entry.file_type().is_dir() {
+            paths::create_dir_all(&target)?;
+        } else {
+            paths::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
@@ -31,6 +31,10 @@ pub struct BuildConfig {
     pub build_plan: bool,
     /// Output the unit graph to stdout instead of actually compiling.
     pub unit_graph: bool,
+    /// Write a CycloneDX SBOM next to each artifact of the root units.
+    pub sbom: bool,
+    /// Explain why each unit that is not fresh gets rebuilt.
+    pub explain_rebuilds: bool,
     /// An optional override of the rustc process for primary units
     pub primary_unit_rustc: Option<ProcessBuilder>,
     /// A thread used by `cargo fix` to receive messages on a socket regarding
@@ -112,6 +116,8 @@ pub fn new(
             force_rebuild: false,
             build_plan: false,
             unit_graph: false,
+            sbom: false,
+            explain_rebuilds: false,
             primary_unit_rustc: None,
             rustfix_diagnostic_server: Rc::new(RefCell::new(None)),
             export_dir: None,
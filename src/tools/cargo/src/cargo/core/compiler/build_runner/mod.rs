@@ -14,6 +14,7 @@
 use itertools::Itertools;
 use jobserver::Client;
 
+use super::artifact_cache::ArtifactCache;
 use super::build_plan::BuildPlan;
 use super::custom_build::{self, BuildDeps, BuildScriptOutputs, BuildScripts};
 use super::fingerprint::Fingerprint;
@@ -21,6 +22,7 @@
 use super::layout::Layout;
 use super::lto::Lto;
 use super::unit_graph::UnitDep;
+use super::{sandbox, sbom};
 use super::{
     BuildContext, Compilation, CompileKind, CompileMode, Executor, FileFlavor, RustDocFingerprint,
 };
@@ -85,6 +87,10 @@ pub struct BuildRunner<'a, 'gctx> {
     /// because the target has a type error. This is in an Arc<Mutex<..>>
     /// because it is continuously updated as the job progresses.
     pub failed_scrape_units: Arc<Mutex<HashSet<Metadata>>>,
+
+    /// The cache of compiled units shared across workspaces, if
+    /// `-Zartifact-cache` is enabled.
+    pub artifact_cache: Option<ArtifactCache>,
 }
 
 impl<'a, 'gctx> BuildRunner<'a, 'gctx> {
@@ -123,6 +129,7 @@ pub fn new(bcx: &'a BuildContext<'a, 'gctx>) -> CargoResult<Self> {
             lto: HashMap::new(),
             metadata_for_doc_units: HashMap::new(),
             failed_scrape_units: Arc::new(Mutex::new(HashSet::new())),
+            artifact_cache: ArtifactCache::new(bcx.gctx)?,
         })
     }
 
@@ -147,6 +154,9 @@ pub fn compile(mut self, exec: &Arc<dyn Executor>) -> CargoResult<Compilation<'g
         self.lto = super::lto::generate(self.bcx)?;
         self.prepare_units()?;
         self.prepare()?;
+        if sandbox::is_enabled(self.bcx.gctx)? {
+            sandbox::warn_about_rustc_wrapper(self.bcx)?;
+        }
         custom_build::build_map(&mut self)?;
         self.check_collisions()?;
         self.compute_metadata_for_doc_units();
@@ -184,6 +194,15 @@ pub fn compile(mut self, exec: &Arc<dyn Executor>) -> CargoResult<Compilation<'g
         if build_plan {
             plan.set_inputs(self.build_plan_inputs()?);
             plan.output_plan(self.bcx.gctx);
+        } else if self.bcx.build_config.sbom {
+            for unit in &self.bcx.roots {
+                if matches!(
+                    unit.mode,
+                    CompileMode::Build | CompileMode::Test | CompileMode::Bench
+                ) {
+                    sbom::output_sbom(&self, unit)?;
+                }
+            }
         }
 
         // Add `OUT_DIR` to env vars if unit has a build script.
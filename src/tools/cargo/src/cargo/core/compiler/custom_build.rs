@@ -31,7 +31,7 @@
 //! [`CompileMode::RunCustomBuild`]: super::CompileMode
 //! [instructions]: https://doc.rust-lang.org/cargo/reference/build-scripts.html#outputs-of-the-build-script
 
-use super::{fingerprint, BuildRunner, Job, Unit, Work};
+use super::{artifact_cache, fingerprint, sandbox, BuildRunner, Job, Unit, Work};
 use crate::core::compiler::artifact;
 use crate::core::compiler::build_runner::Metadata;
 use crate::core::compiler::fingerprint::DirtyReason;
@@ -355,6 +355,19 @@ fn build_work(build_runner: &mut BuildRunner<'_, '_>, unit: &Unit) -> CargoResul
     cmd.env("CARGO_ENCODED_RUSTFLAGS", unit.rustflags.join("\x1f"));
     cmd.env_remove("RUSTFLAGS");
 
+    // Run the build script under `-Zbuild-sandbox`, with its own temporary
+    // directory since it may not write anywhere else.
+    let sandbox_note = if sandbox::is_enabled(bcx.gctx)? {
+        let tmp_dir = script_run_dir.join("tmp");
+        paths::create_dir_all(&tmp_dir)?;
+        cmd.env("TMPDIR", &tmp_dir);
+        let policy = sandbox::build_script_policy(build_runner, unit, &script_out_dir, &tmp_dir)?;
+        cmd = sandbox::wrap(bcx.gctx, cmd, &policy)?;
+        Some(sandbox::failure_note(&unit.pkg))
+    } else {
+        None
+    };
+
     if build_runner.bcx.ws.gctx().extra_verbose() {
         cmd.display_env_vars();
     }
@@ -411,6 +424,14 @@ fn build_work(build_runner: &mut BuildRunner<'_, '_>, unit: &Unit) -> CargoResul
     let targets_fresh = targets.clone();
     let msrv_fresh = msrv.clone();
 
+    // The fingerprint of the build script run has already been calculated
+    // along with the ones of the units depending on it, which are prepared
+    // first.
+    let cache_entry = build_runner
+        .artifact_cache
+        .as_ref()
+        .and_then(|cache| cache.entry(build_runner, unit));
+
     let env_profile_name = unit.profile.name.to_uppercase();
     let built_with_debuginfo = build_runner
         .bcx
@@ -469,6 +490,41 @@ fn build_work(build_runner: &mut BuildRunner<'_, '_>, unit: &Unit) -> CargoResul
             return Ok(());
         }
 
+        let run_files = [
+            output_file.clone(),
+            err_file.clone(),
+            root_output_file.clone(),
+        ];
+        if let Some(entry) = &cache_entry {
+            if entry.restore(&run_files, Some(&script_out_dir)) {
+                // The restored outputs refer to the `OUT_DIR` of the build which
+                // stored them, in the same way as when the target directory is
+                // moved.
+                let prev_script_out_dir =
+                    paths::bytes2path(&paths::read_bytes(&root_output_file)?)?;
+                paths::write(&root_output_file, paths::path2bytes(&script_out_dir)?)?;
+                let parsed_output = BuildOutput::parse_file(
+                    &output_file,
+                    library_name,
+                    &pkg_descr,
+                    &prev_script_out_dir,
+                    &script_out_dir,
+                    nightly_features_allowed,
+                    &targets,
+                    &msrv,
+                )?;
+
+                if json_messages {
+                    emit_build_output(state, &parsed_output, script_out_dir.as_path(), id)?;
+                }
+                build_script_outputs
+                    .lock()
+                    .unwrap()
+                    .insert(id, metadata_hash, parsed_output);
+                return Ok(());
+            }
+        }
+
         // And now finally, run the build command itself!
         state.running(&cmd);
         let timestamp = paths::set_invocation_time(&script_run_dir)?;
@@ -518,6 +574,11 @@ fn build_work(build_runner: &mut BuildRunner<'_, '_>, unit: &Unit) -> CargoResul
                     }
                 }
 
+                if let Some(note) = &sandbox_note {
+                    build_error_context.push('\n');
+                    build_error_context.push_str(note);
+                }
+
                 build_error_context
             });
 
@@ -557,6 +618,14 @@ fn build_work(build_runner: &mut BuildRunner<'_, '_>, unit: &Unit) -> CargoResul
             &msrv,
         )?;
 
+        if let Some(entry) = &cache_entry {
+            if artifact_cache::is_build_output_cacheable(&parsed_output) {
+                if let Err(e) = entry.store(&run_files, Some(&script_out_dir)) {
+                    state.warning(format!("{e:?}"))?;
+                }
+            }
+        }
+
         if json_messages {
             emit_build_output(state, &parsed_output, script_out_dir.as_path(), id)?;
         }
@@ -1,6 +1,8 @@
 use std::fmt;
 use std::fmt::Debug;
 
+use serde_json::json;
+
 use super::*;
 use crate::core::Shell;
 
@@ -147,80 +149,90 @@ fn after(old_time: FileTime, new_time: FileTime, what: &'static str) -> After {
     }
 
     pub fn present_to(&self, s: &mut Shell, unit: &Unit, root: &Path) -> CargoResult<()> {
+        s.dirty_because(unit, self.describe(root))?;
+        self.note_to(s)
+    }
+
+    /// Explains why `unit` is rebuilt, for `--explain-rebuilds`.
+    ///
+    /// Unlike [`DirtyReason::present_to`], this names the target of the unit,
+    /// as it is shown for every unit rebuilt and not only the first one of
+    /// each package.
+    pub fn explain_to(&self, s: &mut Shell, unit: &Unit, root: &Path) -> CargoResult<()> {
+        let mut target = if unit.mode.is_run_custom_build() {
+            "build script run".to_string()
+        } else {
+            unit.target.description_named()
+        };
+        if unit.mode.is_rustc_test() && !(unit.target.is_test() || unit.target.is_bench()) {
+            target.push_str(" test");
+        }
+        s.status(
+            "Dirty",
+            format_args!("{} ({target}): {}", unit.pkg, self.describe(root)),
+        )?;
+        self.note_to(s)
+    }
+
+    fn note_to(&self, s: &mut Shell) -> CargoResult<()> {
+        if let DirtyReason::LocalLengthsChanged = self {
+            s.note(
+                "this could happen because of added/removed `cargo::rerun-if` instructions in the build script",
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Describes this reason, with the paths relative to `root`.
+    pub fn describe(&self, root: &Path) -> String {
         match self {
-            DirtyReason::RustcChanged => s.dirty_because(unit, "the toolchain changed"),
-            DirtyReason::FeaturesChanged { .. } => {
-                s.dirty_because(unit, "the list of features changed")
-            }
+            DirtyReason::RustcChanged => "the toolchain changed".to_string(),
+            DirtyReason::FeaturesChanged { .. } => "the list of features changed".to_string(),
             DirtyReason::DeclaredFeaturesChanged { .. } => {
-                s.dirty_because(unit, "the list of declared features changed")
+                "the list of declared features changed".to_string()
             }
             DirtyReason::TargetConfigurationChanged => {
-                s.dirty_because(unit, "the target configuration changed")
-            }
-            DirtyReason::PathToSourceChanged => {
-                s.dirty_because(unit, "the path to the source changed")
+                "the target configuration changed".to_string()
             }
+            DirtyReason::PathToSourceChanged => "the path to the source changed".to_string(),
             DirtyReason::ProfileConfigurationChanged => {
-                s.dirty_because(unit, "the profile configuration changed")
-            }
-            DirtyReason::RustflagsChanged { .. } => s.dirty_because(unit, "the rustflags changed"),
-            DirtyReason::MetadataChanged => s.dirty_because(unit, "the metadata changed"),
-            DirtyReason::ConfigSettingsChanged => {
-                s.dirty_because(unit, "the config settings changed")
-            }
-            DirtyReason::CompileKindChanged => {
-                s.dirty_because(unit, "the rustc compile kind changed")
-            }
-            DirtyReason::LocalLengthsChanged => {
-                s.dirty_because(unit, "the local lengths changed")?;
-                s.note(
-                    "this could happen because of added/removed `cargo::rerun-if` instructions in the build script",
-                )?;
-
-                Ok(())
+                "the profile configuration changed".to_string()
             }
+            DirtyReason::RustflagsChanged { .. } => "the rustflags changed".to_string(),
+            DirtyReason::MetadataChanged => "the metadata changed".to_string(),
+            DirtyReason::ConfigSettingsChanged => "the config settings changed".to_string(),
+            DirtyReason::CompileKindChanged => "the rustc compile kind changed".to_string(),
+            DirtyReason::LocalLengthsChanged => "the local lengths changed".to_string(),
             DirtyReason::PrecalculatedComponentsChanged { .. } => {
-                s.dirty_because(unit, "the precalculated components changed")
+                "the precalculated components changed".to_string()
             }
             DirtyReason::DepInfoOutputChanged { .. } => {
-                s.dirty_because(unit, "the dependency info output changed")
+                "the dependency info output changed".to_string()
             }
             DirtyReason::RerunIfChangedOutputFileChanged { .. } => {
-                s.dirty_because(unit, "rerun-if-changed output file path changed")
+                "rerun-if-changed output file path changed".to_string()
             }
             DirtyReason::RerunIfChangedOutputPathsChanged { .. } => {
-                s.dirty_because(unit, "the rerun-if-changed instructions changed")
-            }
-            DirtyReason::EnvVarsChanged { .. } => {
-                s.dirty_because(unit, "the environment variables changed")
-            }
-            DirtyReason::EnvVarChanged { name, .. } => {
-                s.dirty_because(unit, format_args!("the env variable {name} changed"))
+                "the rerun-if-changed instructions changed".to_string()
             }
+            DirtyReason::EnvVarsChanged { .. } => "the environment variables changed".to_string(),
+            DirtyReason::EnvVarChanged { name, .. } => format!("the env variable {name} changed"),
             DirtyReason::LocalFingerprintTypeChanged { .. } => {
-                s.dirty_because(unit, "the local fingerprint type changed")
+                "the local fingerprint type changed".to_string()
             }
-            DirtyReason::NumberOfDependenciesChanged { old, new } => s.dirty_because(
-                unit,
-                format_args!("number of dependencies changed ({old} => {new})",),
-            ),
-            DirtyReason::UnitDependencyNameChanged { old, new } => s.dirty_because(
-                unit,
-                format_args!("name of dependency changed ({old} => {new})"),
-            ),
-            DirtyReason::UnitDependencyInfoChanged { .. } => {
-                s.dirty_because(unit, "dependency info changed")
+            DirtyReason::NumberOfDependenciesChanged { old, new } => {
+                format!("number of dependencies changed ({old} => {new})")
             }
+            DirtyReason::UnitDependencyNameChanged { old, new } => {
+                format!("name of dependency changed ({old} => {new})")
+            }
+            DirtyReason::UnitDependencyInfoChanged { .. } => "dependency info changed".to_string(),
             DirtyReason::FsStatusOutdated(status) => match status {
-                FsStatus::Stale => s.dirty_because(unit, "stale, unknown reason"),
+                FsStatus::Stale => "stale, unknown reason".to_string(),
                 FsStatus::StaleItem(item) => match item {
                     StaleItem::MissingFile(missing_file) => {
                         let file = missing_file.strip_prefix(root).unwrap_or(&missing_file);
-                        s.dirty_because(
-                            unit,
-                            format_args!("the file `{}` is missing", file.display()),
-                        )
+                        format!("the file `{}` is missing", file.display())
                     }
                     StaleItem::ChangedFile {
                         stale,
@@ -230,15 +242,11 @@ pub fn present_to(&self, s: &mut Shell, unit: &Unit, root: &Path) -> CargoResult
                     } => {
                         let file = stale.strip_prefix(root).unwrap_or(&stale);
                         let after = Self::after(*reference_mtime, *stale_mtime, "last build");
-                        s.dirty_because(
-                            unit,
-                            format_args!("the file `{}` has changed ({after})", file.display()),
-                        )
+                        format!("the file `{}` has changed ({after})", file.display())
+                    }
+                    StaleItem::ChangedEnv { var, .. } => {
+                        format!("the environment variable {var} changed")
                     }
-                    StaleItem::ChangedEnv { var, .. } => s.dirty_because(
-                        unit,
-                        format_args!("the environment variable {var} changed"),
-                    ),
                 },
                 FsStatus::StaleDependency {
                     name,
@@ -247,13 +255,10 @@ pub fn present_to(&self, s: &mut Shell, unit: &Unit, root: &Path) -> CargoResult
                     ..
                 } => {
                     let after = Self::after(*max_mtime, *dep_mtime, "last build");
-                    s.dirty_because(
-                        unit,
-                        format_args!("the dependency {name} was rebuilt ({after})"),
-                    )
+                    format!("the dependency {name} was rebuilt ({after})")
                 }
                 FsStatus::StaleDepFingerprint { name } => {
-                    s.dirty_because(unit, format_args!("the dependency {name} was rebuilt"))
+                    format!("the dependency {name} was rebuilt")
                 }
                 FsStatus::UpToDate { .. } => {
                     unreachable!()
@@ -261,10 +266,141 @@ pub fn present_to(&self, s: &mut Shell, unit: &Unit, root: &Path) -> CargoResult
             },
             DirtyReason::NothingObvious => {
                 // See comment in fingerprint compare method.
-                s.dirty_because(unit, "the fingerprint comparison turned up nothing obvious")
+                "the fingerprint comparison turned up nothing obvious".to_string()
+            }
+            DirtyReason::Forced => "forced".to_string(),
+            DirtyReason::FreshBuild => "fresh build".to_string(),
+        }
+    }
+
+    /// The machine-readable form of this reason for `--explain-rebuilds`,
+    /// with a `kind` and the details depending on it.
+    pub fn to_json(&self, root: &Path) -> serde_json::Value {
+        let relative = |path: &Path| path.strip_prefix(root).unwrap_or(path).to_path_buf();
+        let seconds = |time: &FileTime| {
+            time.unix_seconds() as f64 + f64::from(time.nanoseconds()) / 1_000_000_000.0
+        };
+        match self {
+            DirtyReason::RustcChanged => json!({ "kind": "rustc-changed" }),
+            DirtyReason::FeaturesChanged { old, new } => {
+                json!({ "kind": "features-changed", "old": old, "new": new })
+            }
+            DirtyReason::DeclaredFeaturesChanged { old, new } => {
+                json!({ "kind": "declared-features-changed", "old": old, "new": new })
+            }
+            DirtyReason::TargetConfigurationChanged => {
+                json!({ "kind": "target-configuration-changed" })
             }
-            DirtyReason::Forced => s.dirty_because(unit, "forced"),
-            DirtyReason::FreshBuild => s.dirty_because(unit, "fresh build"),
+            DirtyReason::PathToSourceChanged => json!({ "kind": "path-to-source-changed" }),
+            DirtyReason::ProfileConfigurationChanged => {
+                json!({ "kind": "profile-configuration-changed" })
+            }
+            DirtyReason::RustflagsChanged { old, new } => {
+                json!({ "kind": "rustflags-changed", "old": old, "new": new })
+            }
+            DirtyReason::MetadataChanged => json!({ "kind": "metadata-changed" }),
+            DirtyReason::ConfigSettingsChanged => json!({ "kind": "config-settings-changed" }),
+            DirtyReason::CompileKindChanged => json!({ "kind": "compile-kind-changed" }),
+            DirtyReason::LocalLengthsChanged => json!({ "kind": "local-lengths-changed" }),
+            DirtyReason::PrecalculatedComponentsChanged { old, new } => {
+                json!({ "kind": "precalculated-components-changed", "old": old, "new": new })
+            }
+            DirtyReason::DepInfoOutputChanged { old, new } => {
+                json!({ "kind": "dep-info-output-changed", "old": old, "new": new })
+            }
+            DirtyReason::RerunIfChangedOutputFileChanged { old, new } => json!({
+                "kind": "rerun-if-changed-output-file-changed",
+                "old": old,
+                "new": new,
+            }),
+            DirtyReason::RerunIfChangedOutputPathsChanged { old, new } => json!({
+                "kind": "rerun-if-changed-output-paths-changed",
+                "old": old,
+                "new": new,
+            }),
+            DirtyReason::EnvVarsChanged { old, new } => {
+                json!({ "kind": "env-vars-changed", "old": old, "new": new })
+            }
+            DirtyReason::EnvVarChanged {
+                name,
+                old_value,
+                new_value,
+            } => json!({
+                "kind": "env-var-changed",
+                "name": name,
+                "old": old_value,
+                "new": new_value,
+            }),
+            DirtyReason::LocalFingerprintTypeChanged { old, new } => {
+                json!({ "kind": "local-fingerprint-type-changed", "old": old, "new": new })
+            }
+            DirtyReason::NumberOfDependenciesChanged { old, new } => {
+                json!({ "kind": "number-of-dependencies-changed", "old": old, "new": new })
+            }
+            DirtyReason::UnitDependencyNameChanged { old, new } => {
+                json!({ "kind": "dependency-name-changed", "old": old, "new": new })
+            }
+            DirtyReason::UnitDependencyInfoChanged {
+                old_name,
+                old_fingerprint,
+                new_name,
+                new_fingerprint,
+            } => json!({
+                "kind": "dependency-fingerprint-changed",
+                "old_name": old_name,
+                "old_fingerprint": util::to_hex(*old_fingerprint),
+                "new_name": new_name,
+                "new_fingerprint": util::to_hex(*new_fingerprint),
+            }),
+            DirtyReason::FsStatusOutdated(status) => match status {
+                FsStatus::Stale => json!({ "kind": "stale" }),
+                FsStatus::StaleItem(item) => match item {
+                    StaleItem::MissingFile(missing_file) => {
+                        json!({ "kind": "file-missing", "path": relative(missing_file) })
+                    }
+                    StaleItem::ChangedFile {
+                        reference,
+                        reference_mtime,
+                        stale,
+                        stale_mtime,
+                    } => json!({
+                        "kind": "file-changed",
+                        "path": relative(stale),
+                        "mtime": seconds(stale_mtime),
+                        "reference": relative(reference),
+                        "reference_mtime": seconds(reference_mtime),
+                    }),
+                    StaleItem::ChangedEnv {
+                        var,
+                        previous,
+                        current,
+                    } => json!({
+                        "kind": "env-var-changed",
+                        "name": var,
+                        "old": previous,
+                        "new": current,
+                    }),
+                },
+                FsStatus::StaleDependency {
+                    name,
+                    dep_mtime,
+                    max_mtime,
+                } => json!({
+                    "kind": "dependency-rebuilt",
+                    "name": name,
+                    "mtime": seconds(dep_mtime),
+                    "reference_mtime": seconds(max_mtime),
+                }),
+                FsStatus::StaleDepFingerprint { name } => {
+                    json!({ "kind": "stale-dependency", "name": name })
+                }
+                FsStatus::UpToDate { .. } => {
+                    unreachable!()
+                }
+            },
+            DirtyReason::NothingObvious => json!({ "kind": "nothing-obvious" }),
+            DirtyReason::Forced => json!({ "kind": "forced" }),
+            DirtyReason::FreshBuild => json!({ "kind": "fresh-build" }),
         }
     }
 }
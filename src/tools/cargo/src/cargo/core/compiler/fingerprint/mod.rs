@@ -377,6 +377,7 @@
 use crate::core::Package;
 use crate::util::errors::CargoResult;
 use crate::util::interning::InternedString;
+use crate::util::lints;
 use crate::util::{self, try_canonicalize};
 use crate::util::{internal, path_args, StableHasher};
 use crate::{GlobalContext, CARGO_ENV};
@@ -895,7 +896,9 @@ pub fn clear_memoized(&self) {
         *self.memoized_hash.lock().unwrap() = None;
     }
 
-    fn hash_u64(&self) -> u64 {
+    /// Returns the hash of this fingerprint, which is what gets written to
+    /// the fingerprint file of the unit.
+    pub fn hash_u64(&self) -> u64 {
         if let Some(s) = *self.memoized_hash.lock().unwrap() {
             return s;
         }
@@ -1448,6 +1451,7 @@ fn calculate_normal(
         build_runner.bcx.extra_args_for(unit),
         build_runner.lto[unit],
         unit.pkg.manifest().lint_rustflags(),
+        lints::unused_dependencies_rustc_args(build_runner.bcx.gctx, unit),
         lint_check_cfg,
     ));
     // Include metadata since it is exposed as environment variables.
@@ -197,4 +197,10 @@ pub fn future_incompat_report(&self, report: Vec<FutureBreakageItem>) {
         self.messages
             .push(Message::FutureIncompatReport(self.id, report));
     }
+
+    /// The names of the extern crates `rustc` reported as unused, for the
+    /// `unused_dependencies` lint.
+    pub fn unused_externs(&self, names: Vec<String>) {
+        self.messages.push(Message::UnusedExterns(self.id, names));
+    }
 }
@@ -142,6 +142,7 @@
 use crate::core::{PackageId, Shell, TargetKind};
 use crate::util::diagnostic_server::{self, DiagnosticPrinter};
 use crate::util::errors::AlreadyPrintedError;
+use crate::util::lints;
 use crate::util::machine_message::{self, Message as _};
 use crate::util::CargoResult;
 use crate::util::{self, internal};
@@ -200,6 +201,9 @@ struct DrainState<'gctx> {
     /// How many jobs we've finished
     finished: usize,
     per_package_future_incompat_reports: Vec<FutureIncompatReportPackage>,
+    /// The dependencies `rustc` reported as unused for each unit, for the
+    /// `unused_dependencies` lint.
+    unused_externs: HashMap<Unit, Vec<String>>,
 }
 
 /// Count of warnings, used to print a summary after the job succeeds
@@ -372,6 +376,7 @@ enum Message {
     Token(io::Result<Acquired>),
     Finish(JobId, Artifact, CargoResult<()>),
     FutureIncompatReport(JobId, Vec<FutureBreakageItem>),
+    UnusedExterns(JobId, Vec<String>),
 }
 
 impl<'gctx> JobQueue<'gctx> {
@@ -503,6 +508,7 @@ pub fn execute(
             ),
             finished: 0,
             per_package_future_incompat_reports: Vec::new(),
+            unused_externs: HashMap::new(),
         };
 
         // Create a helper thread for acquiring jobserver tokens
@@ -578,11 +584,15 @@ fn spawn_work_if_possible<'s>(
                 // NOTE: An error here will drop the job without starting it.
                 // That should be OK, since we want to exit as soon as
                 // possible during an error.
+                if build_runner.bcx.build_config.explain_rebuilds {
+                    self.explain_rebuild(build_runner, &unit, job.freshness())?;
+                }
                 self.note_working_on(
                     build_runner.bcx.gctx,
                     build_runner.bcx.ws.root(),
                     &unit,
                     job.freshness(),
+                    build_runner.bcx.build_config.explain_rebuilds,
                 )?;
             }
             self.run(&unit, job, build_runner, scope);
@@ -699,6 +709,10 @@ fn handle_event(
                 self.per_package_future_incompat_reports
                     .push(FutureIncompatReportPackage { package_id, items });
             }
+            Message::UnusedExterns(id, names) => {
+                let unit = self.active[&id].clone();
+                self.unused_externs.entry(unit).or_default().extend(names);
+            }
             Message::Token(acquired_token) => {
                 let token = acquired_token.context("failed to acquire jobserver token")?;
                 self.tokens.push(token);
@@ -826,6 +840,17 @@ fn drain_the_queue<'s>(
             // `display_error` inside `handle_error`.
             Some(anyhow::Error::new(AlreadyPrintedError::new(error)))
         } else if self.queue.is_empty() && self.pending_queue.is_empty() {
+            if build_runner.bcx.gctx.cli_unstable().cargo_lints {
+                match lints::check_unused_dependencies(build_runner, &self.unused_externs) {
+                    Ok(0) => {}
+                    Ok(error_count) => {
+                        return Some(anyhow::Error::new(AlreadyPrintedError::new(format_err!(
+                            "encountered {error_count} errors(s) while running lints"
+                        ))));
+                    }
+                    Err(e) => return Some(e),
+                }
+            }
             let profile_link = build_runner.bcx.gctx.shell().err_hyperlink(
                 "https://doc.rust-lang.org/cargo/reference/profiles.html#default-profiles",
             );
@@ -1124,6 +1149,7 @@ fn note_working_on(
         ws_root: &Path,
         unit: &Unit,
         fresh: &Freshness,
+        explain_rebuilds: bool,
     ) -> CargoResult<()> {
         if (self.compiled.contains(&unit.pkg.package_id())
             && !unit.mode.is_doc()
@@ -1138,7 +1164,8 @@ fn note_working_on(
             // Any dirty stage which runs at least one command gets printed as
             // being a compiled package.
             Dirty(dirty_reason) => {
-                if !dirty_reason.is_fresh_build() {
+                // With `--explain-rebuilds`, the reason has already been shown.
+                if !dirty_reason.is_fresh_build() && !explain_rebuilds {
                     gctx.shell()
                         .verbose(|shell| dirty_reason.present_to(shell, unit, ws_root))?;
                 }
@@ -1173,6 +1200,39 @@ fn note_working_on(
         Ok(())
     }
 
+    /// Explains why `unit` is rebuilt, for `--explain-rebuilds`.
+    ///
+    /// Unlike [`DrainState::note_working_on`], this is shown for every unit
+    /// rebuilt, in human form and, with `--message-format=json`, in JSON.
+    fn explain_rebuild(
+        &self,
+        build_runner: &BuildRunner<'_, '_>,
+        unit: &Unit,
+        fresh: &Freshness,
+    ) -> CargoResult<()> {
+        let Dirty(dirty_reason) = fresh else {
+            return Ok(());
+        };
+        if dirty_reason.is_fresh_build() {
+            return Ok(());
+        }
+        let bcx = build_runner.bcx;
+        let ws_root = bcx.ws.root();
+        dirty_reason.explain_to(&mut bcx.gctx.shell(), unit, ws_root)?;
+        if bcx.build_config.emit_json() {
+            let msg = machine_message::RebuildReason {
+                package_id: unit.pkg.package_id().to_spec(),
+                target: &unit.target,
+                mode: unit.mode,
+                message: dirty_reason.describe(ws_root),
+                dirty_reason: dirty_reason.to_json(ws_root),
+            }
+            .to_json_string();
+            crate::drop_println!(bcx.gctx, "{}", msg);
+        }
+        Ok(())
+    }
+
     fn back_compat_notice(
         &self,
         build_runner: &BuildRunner<'_, '_>,
@@ -32,6 +32,7 @@
 //! [`ops::cargo_compile::compile`]: crate::ops::compile
 
 pub mod artifact;
+pub(crate) mod artifact_cache;
 mod build_config;
 pub(crate) mod build_context;
 mod build_plan;
@@ -48,6 +49,8 @@
 mod lto;
 mod output_depinfo;
 pub mod rustdoc;
+mod sandbox;
+mod sbom;
 pub mod standard_lib;
 mod timings;
 mod unit;
@@ -84,6 +87,7 @@
 pub(crate) use self::layout::Layout;
 pub use self::lto::Lto;
 use self::output_depinfo::output_depinfo;
+pub use self::sandbox::{sandbox_exec, sandbox_get_policy};
 use self::unit_graph::UnitDep;
 use crate::core::compiler::future_incompat::FutureIncompatReport;
 pub use crate::core::compiler::unit::{Unit, UnitInterner};
@@ -92,6 +96,7 @@
 use crate::core::{Feature, PackageId, Target, Verbosity};
 use crate::util::errors::{CargoResult, VerboseError};
 use crate::util::interning::InternedString;
+use crate::util::lints;
 use crate::util::machine_message::{self, Message};
 use crate::util::{add_path_args, internal};
 use cargo_util::{paths, ProcessBuilder, ProcessError};
@@ -195,7 +200,8 @@ fn compile<'gctx>(
             let work = if unit.mode.is_doc() || unit.mode.is_doc_scrape() {
                 rustdoc(build_runner, unit)?
             } else {
-                rustc(build_runner, unit, exec)?
+                let work = rustc(build_runner, unit, exec)?;
+                artifact_cache::wrap_rustc(build_runner, unit, work)?
             };
             work.then(link_targets(build_runner, unit, false)?)
         } else {
@@ -708,6 +714,22 @@ fn prepare_rustc(build_runner: &BuildRunner<'_, '_>, unit: &Unit) -> CargoResult
         );
     }
 
+    // `cargo fix` proxies the compilation of primary units through itself,
+    // which needs to reach the fix server over the network. `rustc` wrappers
+    // like `sccache` also need the network and write to their own cache, see
+    // `sandbox::warn_about_rustc_wrapper`.
+    let is_fix_proxy = is_primary && build_runner.bcx.build_config.primary_unit_rustc.is_some();
+    let rustc = build_runner.bcx.rustc();
+    let is_wrapped = rustc.wrapper.is_some() || (is_workspace && rustc.workspace_wrapper.is_some());
+    if sandbox::is_enabled(build_runner.bcx.gctx)? && !is_fix_proxy && !is_wrapped {
+        // The linker writes its temporary files to `TMPDIR`.
+        let tmp = build_runner.files().layout(unit.kind).prepare_tmp()?;
+        if let Some(policy) = sandbox::proc_macro_policy(build_runner, unit, tmp)? {
+            base.env("TMPDIR", tmp);
+            base = sandbox::wrap(build_runner.bcx.gctx, base, &policy)?;
+        }
+    }
+
     Ok(base)
 }
 
@@ -1086,6 +1108,7 @@ fn build_base_args(
     }
 
     cmd.args(unit.pkg.manifest().lint_rustflags());
+    cmd.args(lints::unused_dependencies_rustc_args(bcx.gctx, unit));
     cmd.args(&profile_rustflags);
     if let Some(args) = build_runner.bcx.extra_args_for(unit) {
         cmd.args(args);
@@ -1744,6 +1767,31 @@ fn on_stderr_line_inner(
         return Ok(true);
     }
 
+    // Requested with `--json=unused-externs` for the `unused_dependencies`
+    // lint. `rustc` doesn't emit `unused_crate_dependencies` in this mode, so
+    // it is reported here when it is denied, in which case `rustc` fails.
+    #[derive(serde::Deserialize)]
+    struct UnusedExterns {
+        lint_level: String,
+        unused_extern_names: Vec<String>,
+    }
+
+    if let Ok(report) = serde_json::from_str::<UnusedExterns>(compiler_message.get()) {
+        if matches!(&*report.lint_level, "deny" | "forbid") && options.show_diagnostics {
+            for name in &report.unused_extern_names {
+                let diag = format!(
+                    "error: external crate `{name}` unused in `{}`: \
+                     remove the dependency or add `use {name} as _;`",
+                    target.crate_name()
+                );
+                count_diagnostic("error", options);
+                state.emit_diag("error".to_string(), diag, false)?;
+            }
+        }
+        state.unused_externs(report.unused_extern_names);
+        return Ok(true);
+    }
+
     // Depending on what we're emitting from Cargo itself, we figure out what to
     // do with this JSON message.
     match options.format {
@@ -0,0 +1,575 @@
+//! Sandboxing of build script and proc-macro executions (`-Zbuild-sandbox`).
+//!
+//! Cargo never restricts its own process. Instead, the command to run is
+//! wrapped with the `cargo` executable itself, and the [`SandboxPolicy`] is
+//! passed along in the [`SANDBOX_ENV_INTERNAL`] environment variable. When
+//! `cargo` starts with that variable set, it applies the policy to itself in
+//! [`sandbox_exec`] and then `exec`s the wrapped program, which inherits the
+//! restrictions.
+//!
+//! On Linux the policy is enforced with
+//!
+//! * [Landlock], restricting which paths can be read and written, and
+//! * a seccomp filter denying the creation of sockets.
+//!
+//! Build scripts may only write to their `OUT_DIR`, and read their package,
+//! the target directory, the toolchain and the usual system directories.
+//! The `rustc` invocations loading proc-macros may only write to the target
+//! directory. Neither has network access.
+//!
+//! Exceptions are only taken from the `[build-sandbox]` table of the Cargo
+//! configuration, keyed by package name, and never from the manifests of the
+//! sandboxed packages, which could otherwise grant themselves any access.
+//!
+//! [Landlock]: https://docs.kernel.org/userspace-api/landlock.html
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{bail, Context as _};
+use cargo_util::ProcessBuilder;
+use serde::{Deserialize, Serialize};
+
+use super::{BuildContext, BuildRunner, CompileKind, Unit};
+use crate::core::Package;
+use crate::util::context::ConfigRelativePath;
+use crate::util::errors::CargoResult;
+use crate::util::GlobalContext;
+
+/// Environment variable carrying the [`SandboxPolicy`] to the `cargo`
+/// process wrapping a sandboxed command.
+const SANDBOX_ENV_INTERNAL: &str = "__CARGO_BUILD_SANDBOX";
+
+/// Directories every sandboxed process may read, for the system toolchain
+/// (linkers, C compilers, `pkg-config`, ...) and shared libraries, including
+/// the stores of Nix and Guix. The ones which don't exist are skipped.
+const SYSTEM_READ_PATHS: &[&str] = &[
+    "/bin",
+    "/dev",
+    "/etc",
+    "/lib",
+    "/lib32",
+    "/lib64",
+    "/proc",
+    "/sbin",
+    "/sys",
+    "/usr",
+    "/nix/store",
+    "/gnu/store",
+];
+
+/// The `[build-sandbox]` config table.
+#[derive(Debug, Default, Deserialize)]
+struct SandboxConfig {
+    /// Additional paths every sandboxed process may read, for toolchains
+    /// installed outside of [`SYSTEM_READ_PATHS`].
+    read: Option<Vec<ConfigRelativePath>>,
+    /// The exceptions for the build scripts and proc-macros of each package.
+    package: Option<HashMap<String, PackageSandboxConfig>>,
+}
+
+/// A `[build-sandbox.package.<name>]` config table.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PackageSandboxConfig {
+    /// Paths, relative to the package root, that may additionally be read.
+    allow_read: Option<Vec<String>>,
+    /// Paths, relative to the package root, that may additionally be written.
+    allow_write: Option<Vec<String>>,
+    /// Whether network access is allowed.
+    allow_network: Option<bool>,
+}
+
+/// What a sandboxed process is allowed to access.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SandboxPolicy {
+    /// Paths, recursively, whose contents may be read and executed.
+    read: BTreeSet<PathBuf>,
+    /// Paths, recursively, whose contents may be read, created, modified and
+    /// removed.
+    write: BTreeSet<PathBuf>,
+    /// Whether sockets may be created.
+    network: bool,
+}
+
+impl SandboxPolicy {
+    /// Creates a policy allowing to read the system directories.
+    fn new(config: &SandboxConfig, gctx: &GlobalContext) -> Self {
+        let mut policy = SandboxPolicy::default();
+        policy
+            .read
+            .extend(SYSTEM_READ_PATHS.iter().map(PathBuf::from));
+        policy.read.extend(
+            config
+                .read
+                .iter()
+                .flatten()
+                .map(|path| path.resolve_path(gctx)),
+        );
+        policy
+    }
+
+    /// Adds the exceptions configured in `[build-sandbox.package.<name>]` for
+    /// `pkg`.
+    fn allow_configured(&mut self, config: &SandboxConfig, pkg: &Package) -> CargoResult<()> {
+        let name = pkg.name();
+        let Some(allowed) = config.package.as_ref().and_then(|p| p.get(name.as_str())) else {
+            return Ok(());
+        };
+        let resolve = |key: &str, path: &String| -> CargoResult<PathBuf> {
+            let is_relative = Path::new(path)
+                .components()
+                .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+            if !is_relative {
+                bail!(
+                    "`build-sandbox.package.{name}.{key}` contains `{path}`, \
+                     but only paths inside the package without `..` are allowed"
+                );
+            }
+            Ok(pkg.root().join(path))
+        };
+        for path in allowed.allow_read.iter().flatten() {
+            self.read.insert(resolve("allow-read", path)?);
+        }
+        for path in allowed.allow_write.iter().flatten() {
+            self.write.insert(resolve("allow-write", path)?);
+        }
+        self.network |= allowed.allow_network.unwrap_or(false);
+        Ok(())
+    }
+}
+
+/// Returns whether build scripts and proc-macros should be sandboxed.
+pub(crate) fn is_enabled(gctx: &GlobalContext) -> CargoResult<bool> {
+    if !gctx.cli_unstable().build_sandbox {
+        return Ok(false);
+    }
+    if !cfg!(target_os = "linux") {
+        bail!("`-Zbuild-sandbox` is only supported on Linux");
+    }
+    Ok(true)
+}
+
+fn config(gctx: &GlobalContext) -> CargoResult<SandboxConfig> {
+    Ok(gctx
+        .get::<Option<SandboxConfig>>("build-sandbox")?
+        .unwrap_or_default())
+}
+
+/// Creates the policy for running the build script of `unit`.
+///
+/// `tmp_dir` is the directory the build script gets as `TMPDIR`.
+pub(crate) fn build_script_policy(
+    build_runner: &BuildRunner<'_, '_>,
+    unit: &Unit,
+    script_out_dir: &Path,
+    tmp_dir: &Path,
+) -> CargoResult<SandboxPolicy> {
+    let bcx = build_runner.bcx;
+    let config = config(bcx.gctx)?;
+    let mut policy = SandboxPolicy::new(&config, bcx.gctx);
+    policy.read.insert(unit.pkg.root().to_path_buf());
+    // Scripts run from the target directory and may read the outputs of the
+    // build scripts and artifact dependencies they depend on.
+    policy.read.insert(bcx.ws.target_dir().into_path_unlocked());
+    policy
+        .read
+        .insert(bcx.target_data.info(CompileKind::Host).sysroot.clone());
+    let exes = [
+        bcx.rustc().path.clone(),
+        bcx.gctx.rustdoc()?.to_path_buf(),
+        bcx.gctx.cargo_exe()?.to_path_buf(),
+    ];
+    policy.read.extend(
+        exes.iter()
+            .filter_map(|exe| exe.parent())
+            .map(Path::to_path_buf),
+    );
+    // The toolchain may be run through `rustup` proxies.
+    if let Ok(rustup_home) = home::rustup_home() {
+        policy.read.insert(rustup_home);
+    }
+    policy.write.insert(script_out_dir.to_path_buf());
+    policy.write.insert(tmp_dir.to_path_buf());
+    policy.write.insert(PathBuf::from("/dev/null"));
+    policy.allow_configured(&config, &unit.pkg)?;
+    Ok(policy)
+}
+
+/// Warns that the `rustc` invocations loading proc-macros aren't sandboxed
+/// when they go through a `rustc` wrapper.
+///
+/// Wrappers like `sccache` talk to their server over the network and write to
+/// their own cache, which may even be done by a server they start.
+pub(crate) fn warn_about_rustc_wrapper(bcx: &BuildContext<'_, '_>) -> CargoResult<()> {
+    let rustc = bcx.rustc();
+    if rustc.wrapper.is_none() && rustc.workspace_wrapper.is_none() {
+        return Ok(());
+    }
+    bcx.gctx.shell().warn(
+        "proc-macros are not sandboxed when they are compiled through a `rustc` wrapper \
+         (`build.rustc-wrapper` or `build.rustc-workspace-wrapper`); build scripts still are",
+    )
+}
+
+/// Creates the policy for compiling `unit`, if it loads any proc-macros.
+///
+/// The proc-macros run inside `rustc`, which needs to read the whole
+/// toolchain and dependency sources, so only writes and network access are
+/// restricted. `tmp_dir` is the directory `rustc` gets as `TMPDIR`, for the
+/// linker.
+pub(crate) fn proc_macro_policy(
+    build_runner: &BuildRunner<'_, '_>,
+    unit: &Unit,
+    tmp_dir: &Path,
+) -> CargoResult<Option<SandboxPolicy>> {
+    let proc_macros: Vec<_> = build_runner
+        .unit_deps(unit)
+        .iter()
+        .filter(|dep| dep.unit.target.proc_macro())
+        .collect();
+    if proc_macros.is_empty() {
+        return Ok(None);
+    }
+    let config = config(build_runner.bcx.gctx)?;
+    let mut policy = SandboxPolicy::default();
+    policy.read.insert(PathBuf::from("/"));
+    policy
+        .write
+        .insert(build_runner.bcx.ws.target_dir().into_path_unlocked());
+    policy.write.insert(tmp_dir.to_path_buf());
+    policy.write.insert(PathBuf::from("/dev/null"));
+    for dep in proc_macros {
+        policy.allow_configured(&config, &dep.unit.pkg)?;
+    }
+    Ok(Some(policy))
+}
+
+/// Wraps `cmd` so that it runs with the restrictions of `policy`.
+pub(crate) fn wrap(
+    gctx: &GlobalContext,
+    mut cmd: ProcessBuilder,
+    policy: &SandboxPolicy,
+) -> CargoResult<ProcessBuilder> {
+    cmd.env(SANDBOX_ENV_INTERNAL, serde_json::to_string(policy)?);
+    Ok(cmd.wrapped(Some(gctx.cargo_exe()?)))
+}
+
+/// Explains a failure of a command run in the build sandbox.
+pub(crate) fn failure_note(pkg: &Package) -> String {
+    format!(
+        "note: the build script was run in a sandbox (`-Zbuild-sandbox`), \
+         which only allows writing to `OUT_DIR`, reading the package, the target \
+         directory and the toolchain, and denies network access\n\
+         help: if the build script needs more access, allow it in the \
+         `[build-sandbox.package.{}]` table of the Cargo configuration",
+        pkg.name()
+    )
+}
+
+/// Returns the sandbox policy if `cargo` is running as the wrapper of a
+/// sandboxed command.
+///
+/// See [`sandbox_exec`]
+pub fn sandbox_get_policy() -> Option<String> {
+    // ALLOWED: For the internal mechanism of `-Zbuild-sandbox` only.
+    // Shouldn't be set directly by anyone.
+    #[allow(clippy::disallowed_methods)]
+    std::env::var(SANDBOX_ENV_INTERNAL).ok()
+}
+
+/// Entry point for `cargo` running as the wrapper of a sandboxed command.
+///
+/// Restricts the current process according to `policy` and replaces it with
+/// the wrapped command. This only returns if that fails.
+pub fn sandbox_exec(policy: &str) -> CargoResult<()> {
+    let policy: SandboxPolicy =
+        serde_json::from_str(policy).context("failed to parse the build sandbox policy")?;
+    let mut args = std::env::args_os().skip(1);
+    let Some(program) = args.next() else {
+        bail!("no command to run in the build sandbox");
+    };
+    let mut cmd = std::process::Command::new(&program);
+    cmd.args(args).env_remove(SANDBOX_ENV_INTERNAL);
+    imp::restrict(&policy).context("failed to set up the build sandbox")?;
+    let error = imp::exec(&mut cmd);
+    Err(anyhow::Error::from(error).context(format!(
+        "failed to execute `{}` in the build sandbox",
+        Path::new(&program).display()
+    )))
+}
+
+#[cfg(target_os = "linux")]
+mod imp {
+    use std::fs::OpenOptions;
+    use std::io;
+    use std::os::fd::AsRawFd;
+    use std::os::unix::fs::OpenOptionsExt;
+    use std::os::unix::process::CommandExt;
+    use std::process::Command;
+
+    use anyhow::{bail, Context as _};
+
+    use super::SandboxPolicy;
+    use crate::util::errors::CargoResult;
+
+    const LANDLOCK_CREATE_RULESET_VERSION: u32 = 1 << 0;
+    const LANDLOCK_RULE_PATH_BENEATH: libc::c_int = 1;
+
+    const ACCESS_FS_EXECUTE: u64 = 1 << 0;
+    const ACCESS_FS_WRITE_FILE: u64 = 1 << 1;
+    const ACCESS_FS_READ_FILE: u64 = 1 << 2;
+    const ACCESS_FS_READ_DIR: u64 = 1 << 3;
+    /// All the access rights of the first Landlock ABI, from
+    /// `LANDLOCK_ACCESS_FS_EXECUTE` to `LANDLOCK_ACCESS_FS_MAKE_SYM`.
+    const ACCESS_FS_ABI_1: u64 = (1 << 13) - 1;
+    const ACCESS_FS_REFER: u64 = 1 << 13;
+    const ACCESS_FS_TRUNCATE: u64 = 1 << 14;
+
+    const ACCESS_FS_READ: u64 = ACCESS_FS_EXECUTE | ACCESS_FS_READ_FILE | ACCESS_FS_READ_DIR;
+    /// The access rights that can be granted on a regular file, as opposed to
+    /// a directory.
+    const ACCESS_FS_FILE: u64 =
+        ACCESS_FS_EXECUTE | ACCESS_FS_WRITE_FILE | ACCESS_FS_READ_FILE | ACCESS_FS_TRUNCATE;
+
+    #[repr(C)]
+    struct LandlockRulesetAttr {
+        handled_access_fs: u64,
+    }
+
+    #[repr(C, packed)]
+    struct LandlockPathBeneathAttr {
+        allowed_access: u64,
+        parent_fd: libc::c_int,
+    }
+
+    /// `BPF_LD | BPF_W | BPF_ABS`
+    const BPF_LD_W_ABS: u16 = 0x20;
+    /// `BPF_JMP | BPF_JEQ | BPF_K`
+    const BPF_JMP_JEQ_K: u16 = 0x15;
+    /// `BPF_JMP | BPF_JGE | BPF_K`
+    const BPF_JMP_JGE_K: u16 = 0x35;
+    /// `BPF_RET | BPF_K`
+    const BPF_RET_K: u16 = 0x06;
+    const SECCOMP_RET_ALLOW: u32 = 0x7fff_0000;
+    const SECCOMP_RET_ERRNO: u32 = 0x0005_0000;
+
+    /// Offsets into `struct seccomp_data`.
+    const SECCOMP_DATA_NR: u32 = 0;
+    const SECCOMP_DATA_ARCH: u32 = 4;
+
+    /// Set in the number of the syscalls of the x32 ABI, which share the
+    /// audit arch of x86_64.
+    #[cfg(target_arch = "x86_64")]
+    const X32_SYSCALL_BIT: Option<u32> = Some(0x4000_0000);
+    #[cfg(not(target_arch = "x86_64"))]
+    const X32_SYSCALL_BIT: Option<u32> = None;
+
+    #[cfg(target_arch = "x86_64")]
+    const AUDIT_ARCH: Option<u32> = Some(0xc000_003e);
+    #[cfg(target_arch = "aarch64")]
+    const AUDIT_ARCH: Option<u32> = Some(0xc000_00b7);
+    #[cfg(target_arch = "riscv64")]
+    const AUDIT_ARCH: Option<u32> = Some(0xc000_00f3);
+    #[cfg(not(any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "riscv64"
+    )))]
+    const AUDIT_ARCH: Option<u32> = None;
+
+    pub fn restrict(policy: &SandboxPolicy) -> CargoResult<()> {
+        // Required to install both the Landlock ruleset and the seccomp
+        // filter without privileges.
+        if unsafe { libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) } != 0 {
+            return Err(io::Error::last_os_error()).context("failed to set `no_new_privs`");
+        }
+        restrict_paths(policy)?;
+        if !policy.network {
+            deny_network()?;
+        }
+        Ok(())
+    }
+
+    pub fn exec(cmd: &mut Command) -> io::Error {
+        cmd.exec()
+    }
+
+    fn restrict_paths(policy: &SandboxPolicy) -> CargoResult<()> {
+        let abi = unsafe {
+            libc::syscall(
+                libc::SYS_landlock_create_ruleset,
+                std::ptr::null::<LandlockRulesetAttr>(),
+                0,
+                LANDLOCK_CREATE_RULESET_VERSION,
+            )
+        };
+        if abi < 1 {
+            bail!(
+                "Landlock is not available ({}); \
+                 it requires Linux 5.13 or newer with Landlock enabled",
+                io::Error::last_os_error()
+            );
+        }
+        let mut handled = ACCESS_FS_ABI_1;
+        if abi >= 2 {
+            handled |= ACCESS_FS_REFER;
+        }
+        if abi >= 3 {
+            handled |= ACCESS_FS_TRUNCATE;
+        }
+
+        let attr = LandlockRulesetAttr {
+            handled_access_fs: handled,
+        };
+        let ruleset = unsafe {
+            libc::syscall(
+                libc::SYS_landlock_create_ruleset,
+                &attr as *const LandlockRulesetAttr,
+                std::mem::size_of::<LandlockRulesetAttr>(),
+                0,
+            )
+        };
+        if ruleset < 0 {
+            return Err(io::Error::last_os_error()).context("failed to create Landlock ruleset");
+        }
+        let ruleset = ruleset as libc::c_int;
+
+        let rules = policy
+            .read
+            .iter()
+            .map(|path| (path, ACCESS_FS_READ & handled))
+            .chain(policy.write.iter().map(|path| (path, handled)));
+        let result = rules
+            .map(|(path, access)| {
+                // Paths that don't exist cannot be accessed anyway.
+                let Ok(file) = OpenOptions::new()
+                    .read(true)
+                    .custom_flags(libc::O_PATH | libc::O_CLOEXEC)
+                    .open(path)
+                else {
+                    return Ok(());
+                };
+                let mut access = access;
+                if !file.metadata().map_or(false, |m| m.is_dir()) {
+                    access &= ACCESS_FS_FILE;
+                }
+                let rule = LandlockPathBeneathAttr {
+                    allowed_access: access,
+                    parent_fd: file.as_raw_fd(),
+                };
+                let ret = unsafe {
+                    libc::syscall(
+                        libc::SYS_landlock_add_rule,
+                        ruleset,
+                        LANDLOCK_RULE_PATH_BENEATH,
+                        &rule as *const LandlockPathBeneathAttr,
+                        0,
+                    )
+                };
+                if ret != 0 {
+                    return Err(io::Error::last_os_error()).with_context(|| {
+                        format!("failed to allow access to `{}`", path.display())
+                    });
+                }
+                Ok(())
+            })
+            .collect::<CargoResult<()>>()
+            .and_then(|()| {
+                let ret = unsafe { libc::syscall(libc::SYS_landlock_restrict_self, ruleset, 0) };
+                if ret != 0 {
+                    return Err(io::Error::last_os_error())
+                        .context("failed to enforce Landlock ruleset");
+                }
+                Ok(())
+            });
+        unsafe { libc::close(ruleset) };
+        result
+    }
+
+    fn deny_network() -> CargoResult<()> {
+        let Some(arch) = AUDIT_ARCH else {
+            bail!(
+                "denying network access is not supported on {}",
+                std::env::consts::ARCH
+            );
+        };
+        let stmt = |code, k| libc::sock_filter {
+            code,
+            jt: 0,
+            jf: 0,
+            k,
+        };
+        let jump = |k, jt, jf| libc::sock_filter {
+            code: BPF_JMP_JEQ_K,
+            jt,
+            jf,
+            k,
+        };
+        // Jump offsets are relative to the next instruction, so this is the
+        // offset from the instruction at index `i` to the final one, which
+        // denies the syscall.
+        let len = if X32_SYSCALL_BIT.is_some() { 8 } else { 7 };
+        let to_deny = |i: usize| (len - 2 - i) as u8;
+        // Fail `socket` with `EACCES` whatever the address family, as Unix
+        // and netlink sockets also reach outside of the sandbox, as well as
+        // `io_uring_setup` since io_uring can create sockets too. The
+        // syscalls of other ABIs, like `int 0x80` on x86_64, have other
+        // numbers, so they are all denied. Allow everything else.
+        let mut filter = vec![
+            stmt(BPF_LD_W_ABS, SECCOMP_DATA_ARCH),
+            jump(arch, 0, to_deny(1)),
+            stmt(BPF_LD_W_ABS, SECCOMP_DATA_NR),
+        ];
+        if let Some(x32_bit) = X32_SYSCALL_BIT {
+            filter.push(libc::sock_filter {
+                code: BPF_JMP_JGE_K,
+                jt: to_deny(3),
+                jf: 0,
+                k: x32_bit,
+            });
+        }
+        let i = filter.len();
+        filter.extend([
+            jump(libc::SYS_socket as u32, to_deny(i), 0),
+            jump(libc::SYS_io_uring_setup as u32, to_deny(i + 1), 0),
+            stmt(BPF_RET_K, SECCOMP_RET_ALLOW),
+            stmt(BPF_RET_K, SECCOMP_RET_ERRNO | libc::EACCES as u32),
+        ]);
+        debug_assert_eq!(filter.len(), len);
+        let prog = libc::sock_fprog {
+            len: filter.len() as u16,
+            filter: filter.as_mut_ptr(),
+        };
+        let ret = unsafe {
+            libc::prctl(
+                libc::PR_SET_SECCOMP,
+                libc::SECCOMP_MODE_FILTER,
+                &prog as *const libc::sock_fprog,
+            )
+        };
+        if ret != 0 {
+            return Err(io::Error::last_os_error()).context("failed to install seccomp filter");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod imp {
+    use std::io;
+    use std::process::Command;
+
+    use anyhow::bail;
+
+    use super::SandboxPolicy;
+    use crate::util::errors::CargoResult;
+
+    pub fn restrict(_policy: &SandboxPolicy) -> CargoResult<()> {
+        bail!("`-Zbuild-sandbox` is only supported on Linux")
+    }
+
+    pub fn exec(_cmd: &mut Command) -> io::Error {
+        unreachable!("the build sandbox is never set up outside of Linux")
+    }
+}
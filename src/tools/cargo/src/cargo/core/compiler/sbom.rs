@@ -0,0 +1,253 @@
+//! Software Bill of Materials generation for the unstable `--sbom` flag.
+//!
+//! For each artifact of the root units, Cargo writes a [CycloneDX] JSON
+//! document next to it, named after the artifact with a `.cdx.json`
+//! extension. The document is built from the [`UnitGraph`] rather than from
+//! the dependency resolution, so that it only lists the packages which are
+//! actually compiled into the artifact, for the target and with the features
+//! of this build. Build scripts, proc-macros and artifact dependencies run or
+//! are copied at build time, but aren't linked into the artifact, and are thus
+//! left out.
+//!
+//! [CycloneDX]: https://cyclonedx.org/docs/1.5/json/
+//! [`UnitGraph`]: super::unit_graph::UnitGraph
+
+use std::collections::HashSet;
+
+use cargo_util::paths;
+use serde::Serialize;
+
+use crate::core::compiler::{BuildRunner, FileFlavor, Unit};
+use crate::util::errors::CargoResult;
+
+/// The root of a CycloneDX document.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Bom {
+    bom_format: &'static str,
+    spec_version: &'static str,
+    version: u32,
+    metadata: BomMetadata,
+    components: Vec<Component>,
+    dependencies: Vec<Dependency>,
+}
+
+#[derive(Serialize)]
+struct BomMetadata {
+    tools: Tools,
+    component: Component,
+}
+
+#[derive(Serialize)]
+struct Tools {
+    components: Vec<Tool>,
+}
+
+#[derive(Serialize)]
+struct Tool {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    name: &'static str,
+    version: String,
+}
+
+/// A package compiled into the artifact.
+#[derive(Serialize)]
+struct Component {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    #[serde(rename = "bom-ref")]
+    bom_ref: String,
+    name: String,
+    version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    purl: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    hashes: Vec<Hash>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    licenses: Vec<License>,
+    properties: Vec<Property>,
+}
+
+#[derive(Serialize)]
+struct Hash {
+    alg: &'static str,
+    content: String,
+}
+
+#[derive(Serialize)]
+struct License {
+    expression: String,
+}
+
+#[derive(Serialize)]
+struct Property {
+    name: &'static str,
+    value: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Dependency {
+    #[serde(rename = "ref")]
+    bom_ref: String,
+    depends_on: Vec<String>,
+}
+
+/// Writes the SBOM of the artifacts of the root `unit`.
+pub fn output_sbom(build_runner: &BuildRunner<'_, '_>, unit: &Unit) -> CargoResult<()> {
+    let bom = build_bom(build_runner, unit);
+    let json = serde_json::to_string_pretty(&bom)?;
+    for output in build_runner.outputs(unit)?.iter() {
+        if !matches!(output.flavor, FileFlavor::Normal | FileFlavor::Linkable) {
+            continue;
+        }
+        let mut path = output.bin_dst().clone().into_os_string();
+        path.push(".cdx.json");
+        paths::write(&path, &json)?;
+    }
+    Ok(())
+}
+
+fn build_bom(build_runner: &BuildRunner<'_, '_>, root: &Unit) -> Bom {
+    let mut components = Vec::new();
+    let mut dependencies = Vec::new();
+    let mut visited = HashSet::new();
+    let mut queue = vec![root];
+    while let Some(unit) = queue.pop() {
+        if !visited.insert(unit) {
+            continue;
+        }
+        let deps = linked_deps(build_runner, unit);
+        let mut depends_on: Vec<_> = deps.iter().map(|dep| bom_ref(build_runner, dep)).collect();
+        depends_on.sort();
+        depends_on.dedup();
+        dependencies.push(Dependency {
+            bom_ref: bom_ref(build_runner, unit),
+            depends_on,
+        });
+        if unit != root {
+            components.push(component(build_runner, unit));
+        }
+        queue.extend(deps);
+    }
+    components.sort_by(|a, b| a.bom_ref.cmp(&b.bom_ref));
+    dependencies.sort_by(|a, b| a.bom_ref.cmp(&b.bom_ref));
+
+    Bom {
+        bom_format: "CycloneDX",
+        spec_version: "1.5",
+        version: 1,
+        metadata: BomMetadata {
+            tools: Tools {
+                components: vec![Tool {
+                    kind: "application",
+                    name: "cargo",
+                    version: crate::version().version,
+                }],
+            },
+            component: component(build_runner, root),
+        },
+        components,
+        dependencies,
+    }
+}
+
+/// Returns the dependencies of `unit` which are compiled into its artifacts.
+fn linked_deps<'a>(build_runner: &'a BuildRunner<'_, '_>, unit: &Unit) -> Vec<&'a Unit> {
+    build_runner
+        .unit_deps(unit)
+        .iter()
+        .map(|dep| &dep.unit)
+        .filter(|dep| {
+            !dep.mode.is_run_custom_build() && !dep.target.proc_macro() && !dep.artifact.is_true()
+        })
+        .collect()
+}
+
+/// Returns the identifier of `unit` in the document.
+///
+/// The same package can be compiled several times with different settings,
+/// for example with other features for the host, so the metadata hash of the
+/// unit is part of it.
+fn bom_ref(build_runner: &BuildRunner<'_, '_>, unit: &Unit) -> String {
+    format!(
+        "{}@{}-{}",
+        unit.pkg.name(),
+        unit.pkg.version(),
+        build_runner.files().metadata(unit)
+    )
+}
+
+fn component(build_runner: &BuildRunner<'_, '_>, unit: &Unit) -> Component {
+    let pkg_id = unit.pkg.package_id();
+    let source_id = pkg_id.source_id();
+    let purl = source_id.is_crates_io().then(|| {
+        format!(
+            "pkg:cargo/{}@{}",
+            pkg_id.name(),
+            pkg_id.version().to_string().replace('+', "%2B")
+        )
+    });
+    let hashes = unit
+        .pkg
+        .summary()
+        .checksum()
+        .map(|checksum| Hash {
+            alg: "SHA-256",
+            content: checksum.to_string(),
+        })
+        .into_iter()
+        .collect();
+    let licenses = unit
+        .pkg
+        .manifest()
+        .metadata()
+        .license
+        .iter()
+        .map(|license| License {
+            expression: license.clone(),
+        })
+        .collect();
+
+    let mut properties = vec![
+        Property {
+            name: "cargo:source",
+            value: source_id.as_url().to_string(),
+        },
+        Property {
+            name: "cargo:target",
+            value: build_runner
+                .bcx
+                .target_data
+                .short_name(&unit.kind)
+                .to_string(),
+        },
+    ];
+    if !unit.features.is_empty() {
+        properties.push(Property {
+            name: "cargo:features",
+            value: unit
+                .features
+                .iter()
+                .map(|f| f.as_str())
+                .collect::<Vec<_>>()
+                .join(","),
+        });
+    }
+
+    Component {
+        kind: if unit.target.is_executable() {
+            "application"
+        } else {
+            "library"
+        },
+        bom_ref: bom_ref(build_runner, unit),
+        name: pkg_id.name().to_string(),
+        version: pkg_id.version().to_string(),
+        purl,
+        hashes,
+        licenses,
+        properties,
+    }
+}
@@ -81,6 +81,7 @@
 pub use self::encode::{EncodableDependency, EncodablePackageId, EncodableResolve};
 pub use self::errors::{ActivateError, ActivateResult, ResolveError};
 pub use self::features::{CliFeatures, ForceAllTargets, HasDevUnits};
+pub use self::policy::DependencyPolicy;
 pub use self::resolve::{Resolve, ResolveVersion};
 pub use self::types::{ResolveBehavior, ResolveOpts};
 pub use self::version_prefs::{VersionOrdering, VersionPreferences};
@@ -91,6 +92,7 @@
 pub(crate) mod encode;
 pub(crate) mod errors;
 pub mod features;
+mod policy;
 mod resolve;
 mod types;
 mod version_prefs;
@@ -0,0 +1,283 @@
+//! Enforcement of the `[policy]` config table, enabled with
+//! `-Zdependency-policy`.
+//!
+//! The policy restricts which packages a workspace may depend on:
+//!
+//! ```toml
+//! [policy]
+//! deny = ["openssl", "time@<0.2"]
+//! allowed-licenses = ["MIT", "Apache-2.0"]
+//! allow-git = false
+//! allowed-registries = ["crates-io"]
+//! max-versions = 1
+//! ```
+//!
+//! It is checked against the packages used by the build once the resolve is
+//! done, so dependencies of other platforms, or dev-dependencies of a
+//! `cargo build`, don't count. Only the commands which compile the workspace
+//! check it, so `cargo metadata` or `cargo tree` can still be used to
+//! investigate a violation. Workspace members are never checked, and the
+//! license and source rules only apply to packages which don't come from a
+//! local path.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt::Write as _;
+
+use anyhow::bail;
+use serde::Deserialize;
+
+use super::errors::describe_path;
+use super::Resolve;
+use crate::core::{Package, PackageId, PackageSet};
+use crate::util::errors::CargoResult;
+use crate::util::GlobalContext;
+
+/// The `[policy]` config table.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DependencyPolicy {
+    /// Packages which must not be used, as a name optionally followed by
+    /// `@` and a version requirement, like `time@<0.2`.
+    #[serde(default)]
+    deny: Vec<String>,
+    /// SPDX license identifiers the `license` of each package must be
+    /// satisfiable with. Any license is accepted if this isn't set.
+    allowed_licenses: Option<Vec<String>>,
+    /// Whether git dependencies are allowed, which they are by default.
+    allow_git: Option<bool>,
+    /// Names of the registries packages may come from, with `crates-io` for
+    /// crates.io. Any registry is accepted if this isn't set.
+    allowed_registries: Option<Vec<String>>,
+    /// The maximum number of versions of a single package.
+    max_versions: Option<u32>,
+}
+
+/// An entry of `policy.deny`.
+struct DenyEntry<'a> {
+    entry: &'a str,
+    name: &'a str,
+    req: Option<semver::VersionReq>,
+}
+
+impl DependencyPolicy {
+    /// Returns the policy of the `[policy]` config table, or `None` if it
+    /// isn't set or `-Zdependency-policy` isn't passed.
+    pub fn load(gctx: &GlobalContext) -> CargoResult<Option<DependencyPolicy>> {
+        let policy = gctx.get::<Option<DependencyPolicy>>("policy");
+        if gctx.cli_unstable().dependency_policy {
+            return policy;
+        }
+        if !matches!(policy, Ok(None)) {
+            gctx.shell()
+                .warn("ignoring `policy` config table without `-Zdependency-policy`")?;
+        }
+        Ok(None)
+    }
+
+    /// Checks the packages of `pkg_set` which have been downloaded, that is
+    /// those the build uses, against the policy.
+    ///
+    /// All the violations are reported at once, each with the chain of
+    /// dependencies which introduced the offending package.
+    pub fn check(
+        &self,
+        resolve: &Resolve,
+        pkg_set: &PackageSet<'_>,
+        member_ids: &[PackageId],
+    ) -> CargoResult<()> {
+        let deny = self
+            .deny
+            .iter()
+            .map(|entry| {
+                let (name, req) = match entry.split_once('@') {
+                    Some((name, req)) => match semver::VersionReq::parse(req) {
+                        Ok(req) => (name, Some(req)),
+                        Err(e) => bail!(
+                            "invalid version requirement in `policy.deny` entry `{entry}`: {e}"
+                        ),
+                    },
+                    None => (entry.as_str(), None),
+                };
+                Ok(DenyEntry { entry, name, req })
+            })
+            .collect::<CargoResult<Vec<_>>>()?;
+
+        let members: HashSet<_> = member_ids.iter().collect();
+        let mut pkgs: Vec<&Package> = pkg_set
+            .packages()
+            .filter(|pkg| !members.contains(&pkg.package_id()))
+            .collect();
+        pkgs.sort_by_key(|pkg| pkg.package_id());
+
+        let mut violations = Vec::new();
+        for pkg in &pkgs {
+            let pkg_id = pkg.package_id();
+            if let Some(deny) = deny.iter().find(|deny| {
+                deny.name == pkg_id.name().as_str()
+                    && deny
+                        .req
+                        .as_ref()
+                        .map_or(true, |req| req.matches(pkg_id.version()))
+            }) {
+                violations.push((
+                    pkg_id,
+                    format!("is denied by the `policy.deny` entry `{}`", deny.entry),
+                ));
+            }
+
+            let source_id = pkg_id.source_id();
+            if source_id.is_path() {
+                continue;
+            }
+            if source_id.is_git() && self.allow_git == Some(false) {
+                violations.push((
+                    pkg_id,
+                    format!(
+                        "comes from the git repository `{}`, but `policy.allow-git` is false",
+                        source_id.url()
+                    ),
+                ));
+            }
+            if let Some(allowed) = &self.allowed_registries {
+                if source_id.is_registry() {
+                    let registry = if source_id.is_crates_io() {
+                        "crates-io".to_string()
+                    } else {
+                        source_id.display_registry_name()
+                    };
+                    if !allowed.contains(&registry) {
+                        violations.push((
+                            pkg_id,
+                            format!(
+                                "comes from the registry `{registry}`, \
+                                 which isn't in `policy.allowed-registries`"
+                            ),
+                        ));
+                    }
+                }
+            }
+            if let Some(allowed) = &self.allowed_licenses {
+                match &pkg.manifest().metadata().license {
+                    Some(license) if is_license_allowed(license, allowed) => {}
+                    Some(license) => violations.push((
+                        pkg_id,
+                        format!(
+                            "has the license `{license}`, \
+                             which isn't allowed by `policy.allowed-licenses`"
+                        ),
+                    )),
+                    None => violations.push((
+                        pkg_id,
+                        "has no `license` field, so `policy.allowed-licenses` can't be checked"
+                            .to_string(),
+                    )),
+                }
+            }
+        }
+
+        if let Some(max) = self.max_versions {
+            let mut versions: BTreeMap<_, BTreeSet<_>> = BTreeMap::new();
+            for pkg in &pkgs {
+                versions
+                    .entry(pkg.name())
+                    .or_default()
+                    .insert(pkg.package_id());
+            }
+            for (name, ids) in versions {
+                if ids.len() > max as usize {
+                    let list = ids
+                        .iter()
+                        .map(|id| format!("v{}", id.version()))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    for id in ids {
+                        violations.push((
+                            id,
+                            format!(
+                                "is one of the versions {list} of `{name}`, \
+                                 more than the {max} allowed by `policy.max-versions`"
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+
+        if violations.is_empty() {
+            return Ok(());
+        }
+        let mut msg = String::from("the dependencies don't comply with the `[policy]` config");
+        for (pkg_id, violation) in violations {
+            let path = resolve
+                .path_to_top(&pkg_id)
+                .into_iter()
+                .map(|(p, d)| (p, d.and_then(|d| d.iter().next())));
+            write!(
+                msg,
+                "\n\npackage `{pkg_id}` {violation}\n{}",
+                describe_path(path)
+            )
+            .unwrap();
+        }
+        bail!(msg)
+    }
+}
+
+/// Returns whether the SPDX license `expression` is satisfied by the
+/// `allowed` licenses.
+///
+/// `OR` requires either side to be allowed, `AND` both of them, and an
+/// exception added with `WITH` is allowed if either the license with the
+/// exception or the license alone is. The deprecated `/` separator is
+/// treated as `OR`.
+fn is_license_allowed(expression: &str, allowed: &[String]) -> bool {
+    let expression = expression
+        .replace('/', " OR ")
+        .replace('(', " ( ")
+        .replace(')', " ) ");
+    let tokens: Vec<&str> = expression.split_whitespace().collect();
+    let mut pos = 0;
+    let result = parse_or(&tokens, &mut pos, allowed);
+    result == Some(true) && pos == tokens.len()
+}
+
+fn parse_or(tokens: &[&str], pos: &mut usize, allowed: &[String]) -> Option<bool> {
+    let mut result = parse_and(tokens, pos, allowed)?;
+    while tokens.get(*pos) == Some(&"OR") {
+        *pos += 1;
+        result |= parse_and(tokens, pos, allowed)?;
+    }
+    Some(result)
+}
+
+fn parse_and(tokens: &[&str], pos: &mut usize, allowed: &[String]) -> Option<bool> {
+    let mut result = parse_license(tokens, pos, allowed)?;
+    while tokens.get(*pos) == Some(&"AND") {
+        *pos += 1;
+        result &= parse_license(tokens, pos, allowed)?;
+    }
+    Some(result)
+}
+
+fn parse_license(tokens: &[&str], pos: &mut usize, allowed: &[String]) -> Option<bool> {
+    let token = *tokens.get(*pos)?;
+    *pos += 1;
+    if token == "(" {
+        let result = parse_or(tokens, pos, allowed)?;
+        if tokens.get(*pos) != Some(&")") {
+            return None;
+        }
+        *pos += 1;
+        return Some(result);
+    }
+    if matches!(token, ")" | "AND" | "OR" | "WITH") {
+        return None;
+    }
+    let is_allowed = |license: &str| allowed.iter().any(|a| a == license);
+    if tokens.get(*pos) == Some(&"WITH") {
+        let exception = *tokens.get(*pos + 1)?;
+        *pos += 2;
+        return Some(is_allowed(&format!("{token} WITH {exception}")) || is_allowed(token));
+    }
+    Some(is_allowed(token.trim_end_matches('+')) || is_allowed(token))
+}
@@ -13,8 +13,8 @@
 use crate::core::compiler::Unit;
 use crate::core::features::Features;
 use crate::core::registry::PackageRegistry;
-use crate::core::resolver::features::CliFeatures;
-use crate::core::resolver::ResolveBehavior;
+use crate::core::resolver::features::{CliFeatures, ResolvedFeatures};
+use crate::core::resolver::{Resolve, ResolveBehavior};
 use crate::core::{
     Dependency, Edition, FeatureValue, PackageId, PackageIdSpec, PackageIdSpecQuery,
 };
@@ -25,7 +25,9 @@
 use crate::util::errors::{CargoResult, ManifestError};
 use crate::util::interning::InternedString;
 use crate::util::lints::{
-    analyze_cargo_lints_table, check_im_a_teapot, check_implicit_features, unused_dependencies,
+    analyze_cargo_lints_table, cargo_lints, check_duplicate_versions, check_im_a_teapot,
+    check_implicit_features, check_ineffective_default_features,
+    check_uninherited_workspace_dependencies, unused_dependencies,
 };
 use crate::util::toml::{read_manifest, InheritableFields};
 use crate::util::{
@@ -1238,6 +1240,56 @@ pub fn emit_lints(&self, pkg: &Package, path: &Path) -> CargoResult<()> {
         check_im_a_teapot(pkg, &path, &cargo_lints, &mut error_count, self.gctx)?;
         check_implicit_features(pkg, &path, &cargo_lints, &mut error_count, self.gctx)?;
         unused_dependencies(pkg, &path, &cargo_lints, &mut error_count, self.gctx)?;
+        check_uninherited_workspace_dependencies(
+            pkg,
+            &path,
+            &cargo_lints,
+            ws_document,
+            &mut error_count,
+            self.gctx,
+        )?;
+        if error_count > 0 {
+            Err(crate::util::errors::AlreadyPrintedError::new(anyhow!(
+                "encountered {error_count} errors(s) while running lints"
+            ))
+            .into())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Emits the lints of the members which need the resolve of the
+    /// dependencies, such as `duplicate_versions`.
+    pub fn emit_resolve_lints(
+        &self,
+        resolve: &Resolve,
+        resolved_features: &ResolvedFeatures,
+    ) -> CargoResult<()> {
+        let mut error_count = 0;
+        for pkg in self.members() {
+            if !resolve.contains(&pkg.package_id()) {
+                continue;
+            }
+            let path = pkg.manifest_path();
+            let cargo_lints = cargo_lints(pkg);
+            check_duplicate_versions(
+                pkg,
+                path,
+                &cargo_lints,
+                resolve,
+                &mut error_count,
+                self.gctx,
+            )?;
+            check_ineffective_default_features(
+                pkg,
+                path,
+                &cargo_lints,
+                resolve,
+                resolved_features,
+                &mut error_count,
+                self.gctx,
+            )?;
+        }
         if error_count > 0 {
             Err(crate::util::errors::AlreadyPrintedError::new(anyhow!(
                 "encountered {error_count} errors(s) while running lints"
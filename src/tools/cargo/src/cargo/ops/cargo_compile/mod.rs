@@ -47,7 +47,7 @@
 use crate::core::compiler::{DefaultExecutor, Executor, UnitInterner};
 use crate::core::profiles::Profiles;
 use crate::core::resolver::features::{self, CliFeatures, FeaturesFor};
-use crate::core::resolver::{HasDevUnits, Resolve};
+use crate::core::resolver::{DependencyPolicy, HasDevUnits, Resolve};
 use crate::core::{PackageId, PackageSet, SourceId, TargetKind, Workspace};
 use crate::drop_println;
 use crate::ops;
@@ -281,6 +281,15 @@ pub fn create_bcx<'a, 'gctx>(
         resolved_features,
     } = resolve;
 
+    if gctx.cli_unstable().cargo_lints {
+        ws.emit_resolve_lints(&resolve, &resolved_features)?;
+    }
+
+    if let Some(policy) = DependencyPolicy::load(gctx)? {
+        let member_ids: Vec<_> = ws.members().map(|pkg| pkg.package_id()).collect();
+        policy.check(&resolve, &pkg_set, &member_ids)?;
+    }
+
     let std_resolve_features = if let Some(crates) = &gctx.cli_unstable().build_std {
         let (std_package_set, std_resolve, std_features) =
             standard_lib::resolve_std(ws, &mut target_data, &build_config, crates)?;
@@ -4,12 +4,20 @@
 use crate::core::{TargetKind, Workspace};
 use crate::ops;
 use crate::util::errors::CargoResult;
-use crate::util::{add_path_args, CliError, CliResult, GlobalContext};
-use anyhow::format_err;
-use cargo_util::{ProcessBuilder, ProcessError};
+use crate::util::{add_path_args, style, CliError, CliResult, GlobalContext};
+use anyhow::{format_err, Context as _};
+use cargo_util::{paths, ProcessBuilder, ProcessError};
+use jobserver::Client;
+use serde::Serialize;
 use std::ffi::OsString;
 use std::fmt::Write;
+use std::io::Write as _;
+use std::mem;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
 
 pub struct TestOptions {
     pub compile_opts: ops::CompileOptions,
@@ -72,7 +80,13 @@ pub fn run_tests(ws: &Workspace<'_>, options: &TestOptions, test_args: &[&str])
         }
         return Ok(());
     }
-    let mut errors = run_unit_tests(ws, options, test_args, &compilation, TestKind::Test)?;
+    let unstable = ws.gctx().cli_unstable();
+    let mut errors = if unstable.parallel_tests.is_some() || unstable.test_report {
+        let jobs = unstable.parallel_tests.unwrap_or(1);
+        run_unit_tests_concurrently(ws, options, test_args, &compilation, jobs)?
+    } else {
+        run_unit_tests(ws, options, test_args, &compilation, TestKind::Test)?
+    };
 
     let doctest_errors = run_doc_tests(ws, options, test_args, &compilation)?;
     errors.extend(doctest_errors);
@@ -163,6 +177,241 @@ fn run_unit_tests(
     Ok(errors)
 }
 
+/// Runs the unit and integration tests of a package like [`run_unit_tests`],
+/// but up to `jobs` test binaries at a time, for `-Zparallel-tests`.
+///
+/// The number of binaries running at once is limited by a jobserver, either
+/// the one inherited from the environment or one with `jobs` tokens, which
+/// the binaries inherit in turn.
+///
+/// The output of each test binary is captured, and printed at once when it
+/// exits so that the outputs of concurrent binaries aren't interleaved. The
+/// results written by libtest to a `--logfile` are then added up in a summary
+/// and, with `-Ztest-report`, written to JSON and JUnit reports.
+///
+/// Without `--no-fail-fast`, no more test binaries are started after the
+/// first failure, but the ones already running are waited for.
+fn run_unit_tests_concurrently(
+    ws: &Workspace<'_>,
+    options: &TestOptions,
+    test_args: &[&str],
+    compilation: &Compilation<'_>,
+    jobs: u32,
+) -> Result<Vec<UnitTestError>, CliError> {
+    let gctx = ws.gctx();
+    let cwd = gctx.cwd();
+    let start = Instant::now();
+
+    // Like for the build, one token is ourself, and is used by the first
+    // worker below.
+    let jobserver = match gctx.jobserver_from_env() {
+        Some(client) => client.clone(),
+        None => {
+            let client = Client::new(jobs as usize).context("failed to create jobserver")?;
+            client.acquire_raw()?;
+            client
+        }
+    };
+
+    let log_dir = ws
+        .target_dir()
+        .as_path_unlocked()
+        .join("cargo-test-report")
+        .join("logs");
+    let mut exe_displays = Vec::new();
+    let mut cmds = Vec::new();
+    let mut logs = Vec::new();
+    for UnitOutput {
+        unit,
+        path,
+        script_meta,
+    } in compilation.tests.iter()
+    {
+        let (exe_display, mut cmd) = cmd_builds(
+            gctx,
+            cwd,
+            unit,
+            path,
+            script_meta,
+            test_args,
+            compilation,
+            "unittests",
+        )?;
+        cmd.inherit_jobserver(&jobserver);
+        let log = if can_use_logfile(unit, test_args) {
+            let log = log_dir
+                .join(path.file_name().unwrap())
+                .with_extension("log");
+            // A binary crashing before it creates the file mustn't leave the
+            // results of a previous run behind.
+            if log.exists() {
+                paths::remove_file(&log)?;
+            }
+            paths::create_dir_all(&log_dir)?;
+            cmd.arg("--logfile").arg(&log);
+            Some(log)
+        } else {
+            None
+        };
+        if gctx.extra_verbose() {
+            cmd.display_env_vars();
+        }
+        exe_displays.push(exe_display);
+        cmds.push(cmd);
+        logs.push(log);
+    }
+
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let mut errors = Vec::new();
+    let mut binaries = Vec::new();
+    let mut first_failure_code = None;
+    thread::scope(|s| -> CargoResult<()> {
+        let (tx, rx) = mpsc::channel();
+        let mut workers = Vec::new();
+        for worker in 0..cmds.len().min(jobs.max(1) as usize) {
+            let tx = tx.clone();
+            let (cmds, next, stop, jobserver) = (&cmds, &next, &stop, &jobserver);
+            let no_fail_fast = options.no_fail_fast;
+            workers.push(s.spawn(move || -> CargoResult<()> {
+                while !stop.load(Ordering::SeqCst) && next.load(Ordering::SeqCst) < cmds.len() {
+                    // The first worker runs on our own token, the other ones
+                    // hold one from the jobserver while a binary runs.
+                    let _token = if worker == 0 {
+                        None
+                    } else {
+                        let token = jobserver
+                            .acquire()
+                            .context("failed to acquire jobserver token")?;
+                        Some(token)
+                    };
+                    if stop.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    let i = next.fetch_add(1, Ordering::SeqCst);
+                    let Some(cmd) = cmds.get(i) else { break };
+                    let start = Instant::now();
+                    let result = cmd.exec_with_output();
+                    // Stop before the next binary is picked up by this or
+                    // another worker, rather than once the main thread gets
+                    // to the result.
+                    if result.is_err() && !no_fail_fast {
+                        stop.store(true, Ordering::SeqCst);
+                    }
+                    if tx.send((i, start.elapsed(), result)).is_err() {
+                        break;
+                    }
+                }
+                Ok(())
+            }));
+        }
+        drop(tx);
+
+        for (i, duration, mut result) in rx {
+            let unit = &compilation.tests[i].unit;
+            gctx.shell()
+                .concise(|shell| shell.status("Running", &exe_displays[i]))?;
+            gctx.shell()
+                .verbose(|shell| shell.status("Running", &cmds[i]))?;
+
+            let (stdout, stderr) = match &mut result {
+                Ok(output) => (mem::take(&mut output.stdout), mem::take(&mut output.stderr)),
+                Err(e) => match e.downcast_mut::<ProcessError>() {
+                    Some(e) => {
+                        // The output is printed below like for a successful
+                        // run, so drop it from the error message.
+                        if let Some(end) = e.desc.find("\n--- std") {
+                            e.desc.truncate(end);
+                        }
+                        (
+                            e.stdout.take().unwrap_or_default(),
+                            e.stderr.take().unwrap_or_default(),
+                        )
+                    }
+                    None => (Vec::new(), Vec::new()),
+                },
+            };
+            gctx.shell().out().write_all(&stdout)?;
+            gctx.shell().err().write_all(&stderr)?;
+            // A missing log file means that the binary didn't get to run any
+            // test.
+            let log = match &logs[i] {
+                Some(log) if log.exists() => Some(paths::read(log)?),
+                Some(_) => Some(String::new()),
+                None => None,
+            };
+            binaries.push((
+                i,
+                BinaryReport::new(
+                    unit,
+                    &exe_displays[i],
+                    duration,
+                    result.is_ok(),
+                    log.as_deref(),
+                    String::from_utf8_lossy(&stdout).into_owned(),
+                    String::from_utf8_lossy(&stderr).into_owned(),
+                ),
+            ));
+
+            if let Err(e) = result {
+                let code = fail_fast_code(&e);
+                let unit_err = UnitTestError {
+                    unit: unit.clone(),
+                    kind: TestKind::Test,
+                };
+                report_test_error(ws, test_args, &options.compile_opts, &unit_err, e);
+                errors.push(unit_err);
+                if !options.no_fail_fast && first_failure_code.is_none() {
+                    first_failure_code = Some(code);
+                }
+            }
+        }
+        for worker in workers {
+            worker.join().unwrap()?;
+        }
+        Ok(())
+    })?;
+
+    binaries.sort_by_key(|(i, _)| *i);
+    let binaries: Vec<_> = binaries.into_iter().map(|(_, binary)| binary).collect();
+    let report = TestReport::new(start.elapsed(), &binaries);
+    let mut summary = format!(
+        "{} test binaries run in {:.2}s: {} passed; {} failed; {} ignored; {} measured",
+        binaries.len(),
+        report.duration,
+        report.counts.passed,
+        report.counts.failed,
+        report.counts.ignored,
+        report.counts.measured,
+    );
+    let without_results = binaries.iter().filter(|b| b.counts.is_none()).count();
+    if without_results > 0 {
+        write!(summary, "; no results for {without_results} of them").unwrap();
+    }
+    gctx.shell().status("Summary", summary)?;
+    if gctx.cli_unstable().test_report {
+        report.write(ws)?;
+    }
+
+    if let Some(code) = first_failure_code {
+        return Err(CliError::code(code));
+    }
+    Ok(errors)
+}
+
+/// Whether libtest can be asked to write the results of a test binary to a
+/// `--logfile`.
+///
+/// This isn't the case of binaries with `harness = false`, which may not
+/// accept the flag, nor when the test arguments already have a `--logfile`,
+/// or end their options with `--`.
+fn can_use_logfile(unit: &Unit, test_args: &[&str]) -> bool {
+    unit.target.harness()
+        && !test_args
+            .iter()
+            .any(|arg| *arg == "--" || *arg == "--logfile" || arg.starts_with("--logfile="))
+}
+
 /// Runs doc tests.
 ///
 /// Returns a `Vec` of tests that failed when `--no-fail-fast` is used.
@@ -453,3 +702,305 @@ fn report_test_error(
         ));
     }
 }
+
+/// The results of the test binaries run with `-Zparallel-tests`, written to
+/// `target/cargo-test-report` with `-Ztest-report`.
+#[derive(Serialize)]
+struct TestReport<'a> {
+    /// The time it took to run all the test binaries, in seconds.
+    duration: f64,
+    /// The sum of the counts of the binaries whose results are known.
+    #[serde(flatten)]
+    counts: TestCounts,
+    binaries: &'a [BinaryReport],
+}
+
+/// The number of tests of each outcome in the results of a test binary.
+#[derive(Default, Serialize, Clone, Copy)]
+struct TestCounts {
+    passed: u64,
+    failed: u64,
+    ignored: u64,
+    measured: u64,
+}
+
+/// The results of a single test binary.
+#[derive(Serialize)]
+struct BinaryReport {
+    package: String,
+    target: String,
+    executable: String,
+    success: bool,
+    duration: f64,
+    /// `None`, like `tests`, when libtest couldn't be asked for the results,
+    /// see [`can_use_logfile`].
+    #[serde(flatten)]
+    counts: Option<TestCounts>,
+    tests: Option<Vec<TestCase>>,
+    stdout: String,
+    stderr: String,
+}
+
+#[derive(Serialize)]
+struct TestCase {
+    name: String,
+    outcome: TestOutcome,
+    /// The message of a failed test, for a `should_panic` test which didn't
+    /// panic with the expected message for example.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+    /// The output captured from a failed test.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output: Option<String>,
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum TestOutcome {
+    Ok,
+    Failed,
+    Ignored,
+}
+
+impl<'a> TestReport<'a> {
+    fn new(duration: Duration, binaries: &'a [BinaryReport]) -> TestReport<'a> {
+        let mut counts = TestCounts::default();
+        for binary_counts in binaries.iter().filter_map(|binary| binary.counts.as_ref()) {
+            counts.add(binary_counts);
+        }
+        TestReport {
+            duration: duration.as_secs_f64(),
+            counts,
+            binaries,
+        }
+    }
+
+    /// Writes the report in the JSON and JUnit formats.
+    fn write(&self, ws: &Workspace<'_>) -> CargoResult<()> {
+        let dir = ws.target_dir().as_path_unlocked().join("cargo-test-report");
+        paths::create_dir_all(&dir)?;
+        paths::write(dir.join("report.json"), serde_json::to_string_pretty(self)?)?;
+        paths::write(dir.join("report.xml"), self.junit())?;
+
+        let msg = format!("saved to {}", dir.display());
+        ws.gctx()
+            .shell()
+            .status_with_color("Report", msg, &style::NOTE)?;
+        Ok(())
+    }
+
+    fn junit(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let total = self.counts;
+        writeln!(
+            xml,
+            "<testsuites name=\"cargo test\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\">",
+            total.passed + total.failed + total.ignored,
+            total.failed,
+            total.ignored,
+            self.duration,
+        )
+        .unwrap();
+        for binary in self.binaries {
+            let counts = binary.counts.unwrap_or_default();
+            // A test binary can fail without any failing test, when it
+            // crashes for example.
+            let errors = u64::from(!binary.success && counts.failed == 0);
+            writeln!(
+                xml,
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.3}\">",
+                xml_escape(&binary.executable),
+                counts.passed + counts.failed + counts.ignored,
+                counts.failed,
+                errors,
+                counts.ignored,
+                binary.duration,
+            )
+            .unwrap();
+            for test in binary.tests.iter().flatten() {
+                let (name, classname) = (xml_escape(&test.name), xml_escape(&binary.target));
+                match test.outcome {
+                    TestOutcome::Ok => {
+                        writeln!(xml, "    <testcase name=\"{name}\" classname=\"{classname}\"/>")
+                    }
+                    TestOutcome::Failed => writeln!(
+                        xml,
+                        "    <testcase name=\"{name}\" classname=\"{classname}\">\
+                         <failure message=\"{}\">{}</failure></testcase>",
+                        xml_escape(test.message.as_deref().unwrap_or("test failed")),
+                        xml_escape(test.output.as_deref().unwrap_or_default()),
+                    ),
+                    TestOutcome::Ignored => writeln!(
+                        xml,
+                        "    <testcase name=\"{name}\" classname=\"{classname}\"><skipped/></testcase>"
+                    ),
+                }
+                .unwrap();
+            }
+            writeln!(
+                xml,
+                "    <system-out>{}</system-out>\n    <system-err>{}</system-err>\n  </testsuite>",
+                xml_escape(&binary.stdout),
+                xml_escape(&binary.stderr),
+            )
+            .unwrap();
+        }
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+}
+
+impl TestCounts {
+    fn add(&mut self, other: &TestCounts) {
+        self.passed += other.passed;
+        self.failed += other.failed;
+        self.ignored += other.ignored;
+        self.measured += other.measured;
+    }
+}
+
+impl BinaryReport {
+    /// Creates the report of a test binary from the contents of the
+    /// `--logfile` it wrote, if any.
+    fn new(
+        unit: &Unit,
+        exe_display: &str,
+        duration: Duration,
+        success: bool,
+        log: Option<&str>,
+        stdout: String,
+        stderr: String,
+    ) -> BinaryReport {
+        let (counts, tests) = match log {
+            Some(log) => {
+                let (counts, tests) = parse_logfile(log, &stdout);
+                (Some(counts), Some(tests))
+            }
+            None => (None, None),
+        };
+
+        BinaryReport {
+            package: unit.pkg.name().to_string(),
+            target: unit.target.name().to_string(),
+            executable: exe_display.to_string(),
+            success,
+            duration: duration.as_secs_f64(),
+            counts,
+            tests,
+            stdout,
+            stderr,
+        }
+    }
+}
+
+/// Parses the results written by libtest to a `--logfile`, one per test:
+///
+/// ```text
+/// ok tests::a
+/// failed tests::b
+/// failed: panic did not contain expected string
+///       panic message: `"oh no"`,
+///  expected substring: `"expected"` tests::c <0.001s>
+/// ignored: not yet implemented tests::d
+/// ```
+///
+/// Test names don't contain spaces, unlike the optional messages which may
+/// even span several lines, and the results of benchmarks are only counted.
+/// The output of failed tests is looked up in `stdout`.
+fn parse_logfile(log: &str, stdout: &str) -> (TestCounts, Vec<TestCase>) {
+    let mut entries: Vec<String> = Vec::new();
+    for line in log.lines() {
+        let starts_entry = ["ok ", "failed", "ignored"]
+            .iter()
+            .any(|start| line.starts_with(start))
+            || line.contains(" ns/iter ");
+        match entries.last_mut() {
+            Some(entry) if !starts_entry => {
+                entry.push('\n');
+                entry.push_str(line);
+            }
+            _ => entries.push(line.to_string()),
+        }
+    }
+
+    let mut counts = TestCounts::default();
+    let mut tests = Vec::new();
+    for entry in &entries {
+        // The execution time is only written with `--report-time`.
+        let entry = match entry.rsplit_once(' ') {
+            Some((entry, time)) if time.starts_with('<') && time.ends_with("s>") => entry,
+            _ => entry,
+        };
+        let Some((result, name)) = entry.rsplit_once(' ') else {
+            continue;
+        };
+        let (outcome, message) = if result == "ok" {
+            counts.passed += 1;
+            (TestOutcome::Ok, None)
+        } else if let Some(message) = result.strip_prefix("failed") {
+            counts.failed += 1;
+            let message = message
+                .strip_prefix(": ")
+                .or_else(|| message.strip_prefix(" (")?.strip_suffix(')'));
+            (TestOutcome::Failed, message)
+        } else if result == "ignored" || result.starts_with("ignored: ") {
+            counts.ignored += 1;
+            (TestOutcome::Ignored, None)
+        } else {
+            // Also skips the tests listed with `--list`.
+            if result.contains(" ns/iter ") {
+                counts.measured += 1;
+            }
+            continue;
+        };
+        let output = match outcome {
+            TestOutcome::Failed => failed_test_output(stdout, name),
+            _ => None,
+        };
+        tests.push(TestCase {
+            name: name.to_string(),
+            outcome,
+            message: message.map(str::to_string),
+            output,
+        });
+    }
+    (counts, tests)
+}
+
+/// Returns the output captured from the failed test `name`, which libtest
+/// prints in a `---- name stdout ----` section after running the tests.
+///
+/// This is only a best effort, the section isn't there with `--format json`
+/// for example.
+fn failed_test_output(stdout: &str, name: &str) -> Option<String> {
+    let header = format!("---- {name} stdout ----");
+    let mut lines = stdout.lines().skip_while(|line| *line != header);
+    lines.next()?;
+    let output: Vec<_> = lines
+        .take_while(|line| {
+            *line != "failures:" && !(line.starts_with("---- ") && line.ends_with(" stdout ----"))
+        })
+        .collect();
+    Some(output.join("\n").trim_end().to_string())
+}
+
+/// Escapes `s` to be used in XML text or attribute values.
+///
+/// The characters which can't appear in XML 1.0 documents, such as the ANSI
+/// escape codes of colored output, are dropped.
+fn xml_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            '\u{0}'..='\u{1f}' | '\u{fffe}' | '\u{ffff}' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -1,15 +1,16 @@
-use crate::core::dependency::Dependency;
+use crate::core::dependency::{DepKind, Dependency};
 use crate::core::registry::PackageRegistry;
 use crate::core::resolver::features::{CliFeatures, HasDevUnits};
 use crate::core::shell::Verbosity;
 use crate::core::Registry as _;
 use crate::core::{PackageId, PackageIdSpec, PackageIdSpecQuery};
 use crate::core::{Resolve, SourceId, Workspace};
-use crate::ops;
+use crate::ops::{self, Packages};
 use crate::sources::source::QueryKind;
 use crate::sources::IndexSummary;
 use crate::util::cache_lock::CacheLockMode;
 use crate::util::context::GlobalContext;
+use crate::util::interning::InternedString;
 use crate::util::toml_mut::dependency::{MaybeWorkspace, Source};
 use crate::util::toml_mut::manifest::LocalManifest;
 use crate::util::toml_mut::upgrade::upgrade_requirement;
@@ -19,8 +20,10 @@
 use cargo_util_schemas::core::PartialVersion;
 use itertools::Itertools;
 use semver::{Op, Version, VersionReq};
+use serde::Serialize;
 use std::cmp::Ordering;
 use std::collections::{BTreeMap, HashMap, HashSet};
+use std::str::FromStr;
 use tracing::{debug, trace};
 
 pub type UpgradeMap = HashMap<(String, SourceId), Version>;
@@ -812,6 +815,213 @@ fn is_latest(candidate: &semver::Version, current: &semver::Version) -> bool {
                         && candidate.patch == current.patch))
 }
 
+/// Output format of [`report_outdated`].
+pub enum OutdatedFormat {
+    Human,
+    Json,
+}
+
+impl OutdatedFormat {
+    /// For clap.
+    pub const POSSIBLE_VALUES: [&'static str; 2] = ["human", "json"];
+}
+
+impl FromStr for OutdatedFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> CargoResult<Self> {
+        match s {
+            "human" => Ok(OutdatedFormat::Human),
+            "json" => Ok(OutdatedFormat::Json),
+            f => anyhow::bail!("unknown outdated report format `{f}`"),
+        }
+    }
+}
+
+pub struct OutdatedOptions {
+    pub to_report: Packages,
+    pub format: OutdatedFormat,
+}
+
+/// A direct registry dependency of a workspace member, along with the newest
+/// versions its index knows about.
+#[derive(Serialize)]
+struct OutdatedDependency {
+    name: InternedString,
+    package: InternedString,
+    kind: DepKind,
+    target: Option<String>,
+    req: String,
+    source: String,
+    locked: Version,
+    /// The newest version matching `req`.
+    compatible: Option<Version>,
+    /// The newest version, regardless of `req`.
+    latest: Option<Version>,
+}
+
+impl OutdatedDependency {
+    fn is_outdated(&self) -> bool {
+        [&self.compatible, &self.latest]
+            .into_iter()
+            .flatten()
+            .any(|v| v > &self.locked)
+    }
+}
+
+#[derive(Serialize)]
+struct OutdatedMember {
+    #[serde(skip)]
+    id: PackageId,
+    package_id: PackageIdSpec,
+    dependencies: Vec<OutdatedDependency>,
+}
+
+/// Reports, for every direct registry dependency of the selected members, the
+/// locked version, the newest version matching its requirement, and the newest
+/// version overall.
+///
+/// Versions are looked up through the configured sources, so this honors
+/// `--offline`, source replacement, and local-registry or directory sources.
+pub fn report_outdated(ws: &Workspace<'_>, opts: &OutdatedOptions) -> CargoResult<()> {
+    let gctx = ws.gctx();
+    let members = opts.to_report.get_packages(ws)?;
+
+    let _lock = gctx.acquire_package_cache_lock(CacheLockMode::DownloadExclusive)?;
+    let (_, resolve) = ops::resolve_ws(ws, true)?;
+    let mut registry = ws.package_registry()?;
+    registry.lock_patches();
+
+    let mut report = Vec::new();
+    for member in members.into_iter().sorted_by_key(|m| m.package_id()) {
+        let member_id = member.package_id();
+        let mut dependencies = Vec::new();
+        for dep in member.dependencies() {
+            if !dep.source_id().is_registry() {
+                trace!("skipping non-registry dependency: {}", dep.package_name());
+                continue;
+            }
+            // A member can depend on several versions of the same package,
+            // under different names.
+            let Some(locked) = resolve
+                .deps(member_id)
+                .map(|(id, _)| id)
+                .find(|id| dep.matches_id(*id) && dep.version_req().matches(id.version()))
+            else {
+                trace!("skipping unresolved dependency: {}", dep.package_name());
+                continue;
+            };
+
+            let query = Dependency::parse(dep.package_name(), None, dep.source_id())?;
+            let possibilities = loop {
+                match registry.query_vec(&query, QueryKind::Exact) {
+                    std::task::Poll::Ready(res) => break res?,
+                    std::task::Poll::Pending => registry.block_until_ready()?,
+                }
+            };
+            let candidates = possibilities
+                .iter()
+                .filter(|s| matches!(s, IndexSummary::Candidate(_) | IndexSummary::Offline(_)))
+                .map(|s| s.as_summary().version())
+                .filter(|v| {
+                    // Only consider pre-releases of the locked major.minor.patch
+                    let current = locked.version();
+                    v.pre.is_empty()
+                        || (v.major == current.major
+                            && v.minor == current.minor
+                            && v.patch == current.patch)
+                })
+                .collect::<Vec<_>>();
+
+            dependencies.push(OutdatedDependency {
+                name: dep.name_in_toml(),
+                package: dep.package_name(),
+                kind: dep.kind(),
+                target: dep.platform().map(|p| p.to_string()),
+                req: dep.version_req().to_string(),
+                source: locked.source_id().as_url().to_string(),
+                locked: locked.version().clone(),
+                compatible: candidates
+                    .iter()
+                    .filter(|v| dep.version_req().matches(v))
+                    .max()
+                    .map(|v| (*v).clone()),
+                latest: candidates.iter().max().map(|v| (*v).clone()),
+            });
+        }
+        report.push(OutdatedMember {
+            id: member_id,
+            package_id: member_id.to_spec(),
+            dependencies,
+        });
+    }
+
+    match opts.format {
+        OutdatedFormat::Json => {
+            crate::drop_println!(gctx, "{}", serde_json::to_string(&report)?);
+        }
+        OutdatedFormat::Human => print_outdated(gctx, &report)?,
+    }
+    Ok(())
+}
+
+fn print_outdated(gctx: &GlobalContext, report: &[OutdatedMember]) -> CargoResult<()> {
+    let fmt = |v: &Option<Version>, locked: &Version| match v {
+        Some(v) if v > locked => v.to_string(),
+        _ => "-".to_owned(),
+    };
+    let mut any_outdated = false;
+    for member in report {
+        let mut rows = vec![[
+            "Name".to_owned(),
+            "Requirement".to_owned(),
+            "Locked".to_owned(),
+            "Compatible".to_owned(),
+            "Latest".to_owned(),
+            "Kind".to_owned(),
+        ]];
+        for dep in member.dependencies.iter().filter(|d| d.is_outdated()) {
+            let mut kind = dep.kind.kind_table().to_owned();
+            if let Some(target) = &dep.target {
+                kind = format!("target.'{target}'.{kind}");
+            }
+            rows.push([
+                dep.name.to_string(),
+                dep.req.clone(),
+                dep.locked.to_string(),
+                fmt(&dep.compatible, &dep.locked),
+                fmt(&dep.latest, &dep.locked),
+                kind,
+            ]);
+        }
+        if rows.len() == 1 {
+            continue;
+        }
+        any_outdated = true;
+
+        let mut widths = [0; 6];
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+        crate::drop_println!(gctx, "{}", member.id);
+        for row in &rows {
+            let line = row
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{cell:width$}"))
+                .join("  ");
+            crate::drop_println!(gctx, "  {}", line.trim_end());
+        }
+    }
+    if !any_outdated {
+        gctx.shell()
+            .note("all direct registry dependencies are up to date")?;
+    }
+    Ok(())
+}
+
 fn fill_with_deps<'a>(
     resolve: &'a Resolve,
     dep: PackageId,
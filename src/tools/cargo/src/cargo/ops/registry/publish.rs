@@ -3,8 +3,11 @@
 //! [1]: https://doc.rust-lang.org/nightly/cargo/reference/registry-web-api.html#publish
 
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashSet;
 use std::fs::File;
+use std::io::Seek;
+use std::io::SeekFrom;
 use std::time::Duration;
 
 use anyhow::bail;
@@ -21,10 +24,13 @@
 use crate::core::resolver::CliFeatures;
 use crate::core::Dependency;
 use crate::core::Package;
+use crate::core::PackageId;
 use crate::core::PackageIdSpecQuery;
 use crate::core::SourceId;
 use crate::core::Workspace;
 use crate::ops;
+use crate::ops::cargo_package::local_deps;
+use crate::ops::cargo_package::package_with_dep_graph;
 use crate::ops::PackageOpts;
 use crate::ops::Packages;
 use crate::sources::source::QueryKind;
@@ -34,6 +40,7 @@
 use crate::util::cache_lock::CacheLockMode;
 use crate::util::context::JobsConfig;
 use crate::util::toml::prepare_for_publish;
+use crate::util::Graph;
 use crate::util::Progress;
 use crate::util::ProgressStyle;
 use crate::CargoResult;
@@ -57,16 +64,21 @@ pub struct PublishOpts<'gctx> {
 }
 
 pub fn publish(ws: &Workspace<'_>, opts: &PublishOpts<'_>) -> CargoResult<()> {
+    let multi_package_mode = ws.gctx().cli_unstable().package_workspace;
     let specs = opts.to_publish.to_package_id_specs(ws)?;
-    if specs.len() > 1 {
-        bail!("the `-p` argument must be specified to select a single package to publish")
+    if !multi_package_mode {
+        if specs.len() > 1 {
+            bail!("the `-p` argument must be specified to select a single package to publish")
+        }
+        if Packages::Default == opts.to_publish && ws.is_virtual() {
+            bail!("the `-p` argument must be specified in the root of a virtual workspace")
+        }
     }
-    if Packages::Default == opts.to_publish && ws.is_virtual() {
-        bail!("the `-p` argument must be specified in the root of a virtual workspace")
+    let member_ids: Vec<_> = ws.members().map(|p| p.package_id()).collect();
+    // Check that the specs match members.
+    for spec in &specs {
+        spec.query(member_ids.clone())?;
     }
-    let member_ids = ws.members().map(|p| p.package_id());
-    // Check that the spec matches exactly one member.
-    specs[0].query(member_ids)?;
     let mut pkgs = ws.members_with_features(&specs, &opts.cli_features)?;
     // In `members_with_features_old`, it will add "current" package (determined by the cwd)
     // So we need filter
@@ -75,51 +87,34 @@ pub fn publish(ws: &Workspace<'_>, opts: &PublishOpts<'_>) -> CargoResult<()> {
         .filter(|(m, _)| specs.iter().any(|spec| spec.matches(m.package_id())))
         .collect();
     // Double check. It is safe theoretically, unless logic has updated.
-    assert_eq!(pkgs.len(), 1);
-
-    let (pkg, cli_features) = pkgs.pop().unwrap();
-
-    let mut publish_registry = match opts.reg_or_index.as_ref() {
-        Some(RegistryOrIndex::Registry(registry)) => Some(registry.clone()),
-        _ => None,
-    };
-    if let Some(ref allowed_registries) = *pkg.publish() {
-        if publish_registry.is_none() && allowed_registries.len() == 1 {
-            // If there is only one allowed registry, push to that one directly,
-            // even though there is no registry specified in the command.
-            let default_registry = &allowed_registries[0];
-            if default_registry != CRATES_IO_REGISTRY {
-                // Don't change the registry for crates.io and don't warn the user.
-                // crates.io will be defaulted even without this.
-                opts.gctx.shell().note(&format!(
-                    "found `{}` as only allowed registry. Publishing to it automatically.",
-                    default_registry
-                ))?;
-                publish_registry = Some(default_registry.clone());
+    if !multi_package_mode {
+        assert_eq!(pkgs.len(), 1);
+    }
+    if matches!(opts.to_publish, Packages::All | Packages::OptOut(_)) {
+        // Members which are never published, like internal tools, are
+        // skipped rather than failing the whole workspace.
+        let mut skipped = Vec::new();
+        pkgs.retain(|(pkg, _)| {
+            let publishable = pkg.publish().as_ref().map_or(true, |r| !r.is_empty());
+            if !publishable {
+                skipped.push(pkg.name());
             }
+            publishable
+        });
+        for name in skipped {
+            opts.gctx.shell().note(format!(
+                "skipping `{name}`, as `package.publish` is set to `false`"
+            ))?;
         }
-
-        let reg_name = publish_registry
-            .clone()
-            .unwrap_or_else(|| CRATES_IO_REGISTRY.to_string());
-        if allowed_registries.is_empty() {
-            bail!(
-                "`{}` cannot be published.\n\
-                 `package.publish` must be set to `true` or a non-empty list in Cargo.toml to publish.",
-                pkg.name(),
-            );
-        } else if !allowed_registries.contains(&reg_name) {
-            bail!(
-                "`{}` cannot be published.\n\
-                 The registry `{}` is not listed in the `package.publish` value in Cargo.toml.",
-                pkg.name(),
-                reg_name
-            );
-        }
     }
+    if pkgs.is_empty() {
+        bail!("no packages were selected to publish");
+    }
+
+    let just_pkgs: Vec<_> = pkgs.iter().map(|p| p.0).collect();
+    let publish_registry = publish_registry(opts.gctx, &just_pkgs, opts.reg_or_index.as_ref())?;
     // This is only used to confirm that we can create a token before we build the package.
     // This causes the credential provider to be called an extra time, but keeps the same order of errors.
-    let ver = pkg.version().to_string();
     let operation = Operation::Read;
 
     let reg_or_index = match opts.reg_or_index.clone() {
@@ -137,74 +132,266 @@ pub fn publish(ws: &Workspace<'_>, opts: &PublishOpts<'_>) -> CargoResult<()> {
         true,
         Some(operation).filter(|_| !opts.dry_run),
     )?;
-    verify_dependencies(pkg, &registry, source_ids.original)?;
+    for (pkg, _) in &pkgs {
+        verify_dependencies(pkg, &registry, source_ids.original)?;
+    }
 
-    // Prepare a tarball, with a non-suppressible warning if metadata
+    // Prepare the tarballs, with a non-suppressible warning if metadata
     // is missing since this is being put online.
-    let tarball = ops::package_one(
-        ws,
-        pkg,
-        &PackageOpts {
-            gctx: opts.gctx,
-            verify: opts.verify,
-            list: false,
-            check_metadata: true,
-            allow_dirty: opts.allow_dirty,
-            to_package: Packages::Default,
-            targets: opts.targets.clone(),
-            jobs: opts.jobs.clone(),
-            keep_going: opts.keep_going,
-            cli_features,
-            reg_or_index,
-        },
-    )?;
+    let package_opts = PackageOpts {
+        gctx: opts.gctx,
+        verify: opts.verify,
+        list: false,
+        check_metadata: true,
+        allow_dirty: opts.allow_dirty,
+        to_package: Packages::Default,
+        targets: opts.targets.clone(),
+        jobs: opts.jobs.clone(),
+        keep_going: opts.keep_going,
+        cli_features: opts.cli_features.clone(),
+        reg_or_index,
+    };
+    let pkg_dep_graph = if multi_package_mode {
+        package_with_dep_graph(ws, &package_opts, pkgs)?
+    } else {
+        let (pkg, cli_features) = pkgs.pop().unwrap();
+        let tarball = ops::package_one(
+            ws,
+            pkg,
+            &PackageOpts {
+                cli_features: cli_features.clone(),
+                ..package_opts
+            },
+        )?;
+        local_deps(std::iter::once((pkg.clone(), (cli_features, tarball))))
+    };
+
+    const DEFAULT_TIMEOUT: u64 = 60;
+    let timeout = if opts.dry_run {
+        0
+    } else if opts.gctx.cli_unstable().publish_timeout {
+        let timeout: Option<u64> = opts.gctx.get("publish.timeout")?;
+        timeout.unwrap_or(DEFAULT_TIMEOUT)
+    } else {
+        DEFAULT_TIMEOUT
+    };
+
+    // Packages are uploaded as soon as all their dependencies are available
+    // in the registry.
+    let mut plan = PublishPlan::new(&pkg_dep_graph.graph);
+    let mut uploaded = Vec::new();
+    let result = (|| -> CargoResult<()> {
+        while !plan.is_empty() {
+            let ready = plan.take_ready();
+            for pkg_id in &ready {
+                let (pkg, (_features, tarball)) = &pkg_dep_graph.packages[pkg_id];
+                if !opts.dry_run {
+                    let ver = pkg.version().to_string();
+                    tarball.file().seek(SeekFrom::Start(0))?;
+                    let hash = cargo_util::Sha256::new()
+                        .update_file(tarball.file())?
+                        .finish_hex();
+                    let operation = Operation::Publish {
+                        name: pkg.name().as_str(),
+                        vers: &ver,
+                        cksum: &hash,
+                    };
+                    registry.set_token(Some(auth::auth_token(
+                        &opts.gctx,
+                        &source_ids.original,
+                        None,
+                        operation,
+                        vec![],
+                        false,
+                    )?));
+                }
+
+                opts.gctx
+                    .shell()
+                    .status("Uploading", pkg.package_id().to_string())?;
+                transmit(
+                    opts.gctx,
+                    ws,
+                    pkg,
+                    tarball.file(),
+                    &mut registry,
+                    source_ids.original,
+                    opts.dry_run,
+                )?;
+                if !opts.dry_run {
+                    uploaded.push(*pkg_id);
+                }
+            }
+
+            let mut unavailable = Vec::new();
+            if 0 < timeout {
+                let timeout = Duration::from_secs(timeout);
+                for pkg_id in &ready {
+                    let (pkg, _) = &pkg_dep_graph.packages[pkg_id];
+                    if !wait_for_publish(opts.gctx, source_ids.original, pkg, timeout)? {
+                        unavailable.push(format!("`{} v{}`", pkg.name(), pkg.version()));
+                    }
+                }
+            }
+            if !unavailable.is_empty() {
+                if !plan.is_empty() {
+                    let remaining = plan
+                        .pending()
+                        .map(|id| format!("`{} v{}`", id.name(), id.version()))
+                        .collect::<Vec<_>>();
+                    bail!(
+                        "unable to publish {}, as waiting for {} to be available in the registry timed out",
+                        remaining.join(", "),
+                        unavailable.join(", ")
+                    );
+                } else if multi_package_mode {
+                    opts.gctx.shell().warn(format!(
+                        "all the packages were uploaded, but waiting for {} to be available \
+                         in the registry timed out",
+                        unavailable.join(", ")
+                    ))?;
+                }
+            }
+            plan.mark_confirmed(ready);
+        }
+        Ok(())
+    })();
+
+    // Uploads can't be undone, so tell how to publish the packages which
+    // weren't uploaded once the problem is solved.
+    let mut remaining: Vec<_> = pkg_dep_graph
+        .packages
+        .keys()
+        .filter(|id| !uploaded.contains(id))
+        .collect();
+    remaining.sort();
+    if result.is_err() && multi_package_mode && !uploaded.is_empty() && !remaining.is_empty() {
+        let published: Vec<_> = uploaded
+            .iter()
+            .map(|id| format!("`{} v{}`", id.name(), id.version()))
+            .collect();
+        let remaining: Vec<_> = remaining
+            .iter()
+            .map(|id| format!("-p {}@{}", id.name(), id.version()))
+            .collect();
+        opts.gctx.shell().note(format!(
+            "{} already published; to publish the remaining packages, run \
+             `cargo publish -Zpackage-workspace {}`",
+            if published.len() == 1 {
+                format!("{} was", published[0])
+            } else {
+                format!("{} were", published.join(", "))
+            },
+            remaining.join(" "),
+        ))?;
+    }
+    result
+}
 
-    if !opts.dry_run {
-        let hash = cargo_util::Sha256::new()
-            .update_file(tarball.file())?
-            .finish_hex();
-        let operation = Operation::Publish {
-            name: pkg.name().as_str(),
-            vers: &ver,
-            cksum: &hash,
-        };
-        registry.set_token(Some(auth::auth_token(
-            &opts.gctx,
-            &source_ids.original,
-            None,
-            operation,
-            vec![],
-            false,
-        )?));
+/// Determines the registry to publish `pkgs` to when `--registry` isn't
+/// given, and checks that `package.publish` allows publishing them there.
+fn publish_registry(
+    gctx: &GlobalContext,
+    pkgs: &[&Package],
+    reg_or_index: Option<&RegistryOrIndex>,
+) -> CargoResult<Option<String>> {
+    let mut publish_registry = match reg_or_index {
+        Some(RegistryOrIndex::Registry(registry)) => Some(registry.clone()),
+        _ => None,
+    };
+    if publish_registry.is_none() {
+        // If all the packages only allow the same registry, push to that one directly,
+        // even though there is no registry specified in the command.
+        if let Some([default_registry]) = pkgs[0].publish().as_deref() {
+            if pkgs[1..]
+                .iter()
+                .all(|pkg| pkg.publish() == pkgs[0].publish())
+                && default_registry != CRATES_IO_REGISTRY
+            {
+                // Don't change the registry for crates.io and don't warn the user.
+                // crates.io will be defaulted even without this.
+                gctx.shell().note(&format!(
+                    "found `{}` as only allowed registry. Publishing to it automatically.",
+                    default_registry
+                ))?;
+                publish_registry = Some(default_registry.clone());
+            }
+        }
     }
 
-    opts.gctx
-        .shell()
-        .status("Uploading", pkg.package_id().to_string())?;
-    transmit(
-        opts.gctx,
-        ws,
-        pkg,
-        tarball.file(),
-        &mut registry,
-        source_ids.original,
-        opts.dry_run,
-    )?;
-    if !opts.dry_run {
-        const DEFAULT_TIMEOUT: u64 = 60;
-        let timeout = if opts.gctx.cli_unstable().publish_timeout {
-            let timeout: Option<u64> = opts.gctx.get("publish.timeout")?;
-            timeout.unwrap_or(DEFAULT_TIMEOUT)
-        } else {
-            DEFAULT_TIMEOUT
-        };
-        if 0 < timeout {
-            let timeout = Duration::from_secs(timeout);
-            wait_for_publish(opts.gctx, source_ids.original, pkg, timeout)?;
+    let reg_name = publish_registry
+        .clone()
+        .unwrap_or_else(|| CRATES_IO_REGISTRY.to_string());
+    for pkg in pkgs {
+        if let Some(ref allowed_registries) = *pkg.publish() {
+            if allowed_registries.is_empty() {
+                bail!(
+                    "`{}` cannot be published.\n\
+                     `package.publish` must be set to `true` or a non-empty list in Cargo.toml to publish.",
+                    pkg.name(),
+                );
+            } else if !allowed_registries.contains(&reg_name) {
+                bail!(
+                    "`{}` cannot be published.\n\
+                     The registry `{}` is not listed in the `package.publish` value in Cargo.toml.",
+                    pkg.name(),
+                    reg_name
+                );
+            }
         }
     }
+    Ok(publish_registry)
+}
 
-    Ok(())
+/// The order in which the packages of `cargo publish --workspace` are
+/// uploaded.
+///
+/// A package is only uploaded once all the packages it depends on are
+/// available in the registry, as the registry rejects packages with
+/// dependencies it doesn't know about.
+struct PublishPlan {
+    /// The dependencies of each package which still have to be published.
+    dependencies: BTreeMap<PackageId, BTreeSet<PackageId>>,
+}
+
+impl PublishPlan {
+    fn new(graph: &Graph<PackageId, ()>) -> Self {
+        let dependencies = graph
+            .iter()
+            .map(|id| (*id, graph.edges(id).map(|(dep, _)| *dep).collect()))
+            .collect();
+        Self { dependencies }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.dependencies.is_empty()
+    }
+
+    /// The packages which haven't been published yet.
+    fn pending(&self) -> impl Iterator<Item = PackageId> + '_ {
+        self.dependencies.keys().copied()
+    }
+
+    /// Removes and returns the packages whose dependencies are all published.
+    fn take_ready(&mut self) -> BTreeSet<PackageId> {
+        let ready: BTreeSet<_> = self
+            .dependencies
+            .iter()
+            .filter(|(_, deps)| deps.is_empty())
+            .map(|(id, _)| *id)
+            .collect();
+        for id in &ready {
+            self.dependencies.remove(id);
+        }
+        ready
+    }
+
+    /// Marks the packages as published, making their dependents ready.
+    fn mark_confirmed(&mut self, published: BTreeSet<PackageId>) {
+        for deps in self.dependencies.values_mut() {
+            deps.retain(|id| !published.contains(id));
+        }
+    }
 }
 
 fn wait_for_publish(
@@ -212,7 +399,7 @@ fn wait_for_publish(
     registry_src: SourceId,
     pkg: &Package,
     timeout: Duration,
-) -> CargoResult<()> {
+) -> CargoResult<bool> {
     let version_req = format!("={}", pkg.version());
     let mut source = SourceConfigMap::empty(gctx)?.load(registry_src, &HashSet::new())?;
     // Disable the source's built-in progress bars. Repeatedly showing a bunch
@@ -283,7 +470,7 @@ fn wait_for_publish(
         )?;
     }
 
-    Ok(())
+    Ok(is_available)
 }
 
 fn verify_dependencies(
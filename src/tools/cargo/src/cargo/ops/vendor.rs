@@ -1,5 +1,9 @@
+use crate::core::compiler::{CompileKind, RustcTargetData};
+use crate::core::dependency::DepKind;
+use crate::core::manifest::MANIFEST_PREAMBLE;
+use crate::core::resolver::Resolve;
 use crate::core::shell::Verbosity;
-use crate::core::{GitReference, Package, Workspace};
+use crate::core::{GitReference, Package, PackageId, PackageSet, Workspace};
 use crate::ops;
 use crate::sources::path::PathSource;
 use crate::sources::CRATES_IO_REGISTRY;
@@ -7,6 +11,7 @@
 use crate::util::{try_canonicalize, CargoResult, GlobalContext};
 use anyhow::{bail, Context as _};
 use cargo_util::{paths, Sha256};
+use cargo_util_schemas::manifest::{PathValue, StringOrBool, TomlTarget};
 use serde::Serialize;
 use std::collections::HashSet;
 use std::collections::{BTreeMap, BTreeSet, HashMap};
@@ -20,8 +25,15 @@ pub struct VendorOptions<'a> {
     pub versioned_dirs: bool,
     pub destination: &'a Path,
     pub extra: Vec<PathBuf>,
+    /// The target triples to vendor for. When not empty, the packages which are only
+    /// reachable through dependencies for other platforms are replaced by a stub.
+    pub platforms: Vec<String>,
 }
 
+/// The first line of the library of the stubs written for unused packages, which is used to
+/// recognize them.
+const STUB_HEADER: &str = "// This package is only used on platforms which weren't vendored.";
+
 pub fn vendor(ws: &Workspace<'_>, opts: &VendorOptions<'_>) -> CargoResult<()> {
     let gctx = ws.gctx();
     let mut extra_workspaces = Vec::new();
@@ -140,6 +152,7 @@ fn sync(
 
     let mut checksums = HashMap::new();
     let mut ids = BTreeMap::new();
+    let mut used = HashSet::new();
 
     // Next up let's actually download all crates and start storing internal
     // tables about them.
@@ -151,6 +164,10 @@ fn sync(
             .get_many(resolve.iter())
             .context("failed to download packages")?;
 
+        if !opts.platforms.is_empty() {
+            collect_used_packages(ws, &packages, &resolve, &opts.platforms, &mut used)?;
+        }
+
         for pkg in resolve.iter() {
             // No need to vendor path crates since they're already in the
             // repository
@@ -207,22 +224,36 @@ fn sync(
         let dst = canonical_destination.join(&dst_name);
         to_remove.remove(&dst);
         let cksum = dst.join(".cargo-checksum.json");
-        if dir_has_version_suffix && cksum.exists() {
+        let is_stub = !opts.platforms.is_empty() && !used.contains(id);
+        if dir_has_version_suffix && cksum.exists() && is_stub == is_vendored_stub(&dst) {
             // Always re-copy directory without version suffix in case the version changed
             continue;
         }
 
-        gctx.shell().status(
-            "Vendoring",
-            &format!("{} ({}) to {}", id, src.to_string_lossy(), dst.display()),
-        )?;
-
         let _ = fs::remove_dir_all(&dst);
-        let pathsource = PathSource::new(src, id.source_id(), gctx);
-        let paths = pathsource.list_files(pkg)?;
         let mut map = BTreeMap::new();
-        cp_sources(pkg, src, &paths, &dst, &mut map, &mut tmp_buf, gctx)
-            .with_context(|| format!("failed to copy over vendored sources for: {}", id))?;
+        if is_stub {
+            gctx.shell().status(
+                "Stubbing",
+                &format!(
+                    "{} (unused on the vendored platforms) to {}",
+                    id,
+                    dst.display()
+                ),
+            )?;
+            write_stub(pkg, &dst, &mut map, &mut tmp_buf)
+                .with_context(|| format!("failed to write vendored stub for: {}", id))?;
+        } else {
+            gctx.shell().status(
+                "Vendoring",
+                &format!("{} ({}) to {}", id, src.to_string_lossy(), dst.display()),
+            )?;
+
+            let pathsource = PathSource::new(src, id.source_id(), gctx);
+            let paths = pathsource.list_files(pkg)?;
+            cp_sources(pkg, src, &paths, &dst, &mut map, &mut tmp_buf, gctx)
+                .with_context(|| format!("failed to copy over vendored sources for: {}", id))?;
+        }
 
         // Finally, emit the metadata about this package
         let json = serde_json::json!({
@@ -310,6 +341,117 @@ fn sync(
     Ok(VendorConfig { source: config })
 }
 
+/// Collects the packages of `resolve` reachable from the members of `ws` through
+/// dependencies enabled on at least one of `platforms`.
+///
+/// Build dependencies and proc-macros are compiled for the host, so their own dependencies
+/// are checked against the host platform instead, whether or not it was passed.
+fn collect_used_packages(
+    ws: &Workspace<'_>,
+    packages: &PackageSet<'_>,
+    resolve: &Resolve,
+    platforms: &[String],
+    used: &mut HashSet<PackageId>,
+) -> CargoResult<()> {
+    let requested_kinds = CompileKind::from_requested_targets(ws.gctx(), platforms)?;
+    let target_data = RustcTargetData::new(ws, &requested_kinds)?;
+
+    // The packages to visit, and whether they are compiled for the host.
+    let mut queue = ws
+        .members()
+        .map(|member| (member.package_id(), false))
+        .collect::<Vec<_>>();
+    let mut visited = HashSet::new();
+    while let Some((pkg_id, for_host)) = queue.pop() {
+        if !visited.insert((pkg_id, for_host)) {
+            continue;
+        }
+        used.insert(pkg_id);
+        for (dep_id, deps) in resolve.deps(pkg_id) {
+            let proc_macro = packages.get_one(dep_id)?.proc_macro();
+            for dep in deps {
+                let dep_for_host = for_host || dep.kind() == DepKind::Build;
+                let activated = if dep_for_host {
+                    target_data.dep_platform_activated(dep, CompileKind::Host)
+                } else {
+                    requested_kinds
+                        .iter()
+                        .any(|kind| target_data.dep_platform_activated(dep, *kind))
+                };
+                if activated {
+                    queue.push((dep_id, dep_for_host || proc_macro));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether the vendored package in `dst` is a stub written by [`write_stub`].
+fn is_vendored_stub(dst: &Path) -> bool {
+    fs::read_to_string(dst.join("src/lib.rs")).map_or(false, |lib| lib.starts_with(STUB_HEADER))
+}
+
+/// Writes a stub for a package which isn't used on any of the platforms passed to
+/// `--platform`.
+///
+/// The stub keeps the dependencies and features of the package, so the lockfile still
+/// resolves against the vendored sources, but its library fails to build in case it
+/// ends up being used anyway.
+fn write_stub(
+    pkg: &Package,
+    dst: &Path,
+    cksums: &mut BTreeMap<String, String>,
+    tmp_buf: &mut [u8],
+) -> CargoResult<()> {
+    let mut me = pkg.manifest().normalized_toml().clone();
+    let package = me
+        .package
+        .as_mut()
+        .expect("vendored manifests must have packages");
+    // Without any build script, the package can't link to a native library either.
+    package.build = Some(StringOrBool::Bool(false));
+    package.metabuild = None;
+    package.links = None;
+    package.autobins = Some(false);
+    package.autoexamples = Some(false);
+    package.autotests = Some(false);
+    package.autobenches = Some(false);
+    let lib = me.lib.take().unwrap_or_default();
+    me.lib = Some(TomlTarget {
+        path: Some(PathValue(PathBuf::from("src/lib.rs"))),
+        ..lib
+    });
+    me.bin = None;
+    me.example = None;
+    me.test = None;
+    me.bench = None;
+
+    let manifest = format!("{}\n{}", MANIFEST_PREAMBLE, toml::to_string_pretty(&me)?);
+    let lib = format!(
+        "{STUB_HEADER}\n\
+         compile_error!(\"`{} v{}` was not vendored, as it isn't used on the platforms \
+         passed to `cargo vendor --platform`\");\n",
+        pkg.name(),
+        pkg.version(),
+    );
+    for (relative, contents) in [("Cargo.toml", manifest), ("src/lib.rs", lib)] {
+        let path = dst.join(relative);
+        paths::create_dir_all(path.parent().unwrap())?;
+        let mut dst_opts = OpenOptions::new();
+        dst_opts.write(true).create(true).truncate(true);
+        let cksum = copy_and_checksum(
+            &path,
+            &mut dst_opts,
+            &mut contents.as_bytes(),
+            "Generated stub",
+            tmp_buf,
+        )?;
+        cksums.insert(relative.to_string(), cksum);
+    }
+    Ok(())
+}
+
 fn cp_sources(
     pkg: &Package,
     src: &Path,
@@ -322,6 +322,26 @@ fn arg_unit_graph(self) -> Self {
         )
     }
 
+    fn arg_sbom(self) -> Self {
+        self._arg(
+            flag(
+                "sbom",
+                "Output a CycloneDX SBOM next to each artifact (unstable)",
+            )
+            .help_heading(heading::COMPILATION_OPTIONS),
+        )
+    }
+
+    fn arg_explain_rebuilds(self) -> Self {
+        self._arg(
+            flag(
+                "explain-rebuilds",
+                "Explain why each unit that is not fresh is rebuilt (unstable)",
+            )
+            .help_heading(heading::COMPILATION_OPTIONS),
+        )
+    }
+
     fn arg_new_opts(self) -> Self {
         self._arg(
             opt(
@@ -722,6 +742,8 @@ fn compile_options(
         build_config.requested_profile = self.get_profile_name("dev", profile_checking)?;
         build_config.build_plan = self.flag("build-plan");
         build_config.unit_graph = self.flag("unit-graph");
+        build_config.sbom = self.flag("sbom");
+        build_config.explain_rebuilds = self.flag("explain-rebuilds");
         build_config.future_incompat_report = self.flag("future-incompat-report");
 
         if self._contains("timings") {
@@ -757,6 +779,13 @@ fn compile_options(
             gctx.cli_unstable()
                 .fail_if_stable_opt("--unit-graph", 8002)?;
         }
+        if build_config.sbom {
+            gctx.cli_unstable().fail_if_stable_opt("--sbom", 13709)?;
+        }
+        if build_config.explain_rebuilds {
+            gctx.cli_unstable()
+                .fail_if_stable_opt("--explain-rebuilds", 2904)?;
+        }
 
         let opts = CompileOptions {
             build_config,
@@ -1,12 +1,17 @@
+use crate::core::compiler::{BuildRunner, Unit};
 use crate::core::dependency::DepKind;
+use crate::core::resolver::features::{FeaturesFor, ResolvedFeatures};
 use crate::core::FeatureValue::Dep;
-use crate::core::{Edition, Feature, FeatureValue, Features, Manifest, Package};
+use crate::core::{
+    Dependency, Edition, Feature, FeatureValue, Features, Manifest, Package, PackageId, Resolve,
+    Target,
+};
 use crate::util::interning::InternedString;
 use crate::{CargoResult, GlobalContext};
 use annotate_snippets::{Level, Snippet};
-use cargo_util_schemas::manifest::{TomlLintLevel, TomlToolLints};
+use cargo_util_schemas::manifest::{InheritableDependency, TomlLintLevel, TomlToolLints};
 use pathdiff::diff_paths;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt::Display;
 use std::ops::Range;
 use std::path::Path;
@@ -14,9 +19,13 @@
 
 const LINT_GROUPS: &[LintGroup] = &[TEST_DUMMY_UNSTABLE];
 pub const LINTS: &[Lint] = &[
+    DUPLICATE_VERSIONS,
     IMPLICIT_FEATURES,
     IM_A_TEAPOT,
+    INEFFECTIVE_DEFAULT_FEATURES,
+    UNINHERITED_WORKSPACE_DEPENDENCY,
     UNKNOWN_LINTS,
+    UNUSED_DEPENDENCIES,
     UNUSED_OPTIONAL_DEPENDENCY,
 ];
 
@@ -880,6 +889,619 @@ pub fn unused_dependencies(
     Ok(())
 }
 
+/// Returns the `[lints.cargo]` table of `pkg`, after workspace inheritance.
+pub fn cargo_lints(pkg: &Package) -> TomlToolLints {
+    pkg.manifest()
+        .normalized_toml()
+        .lints
+        .as_ref()
+        .and_then(|lints| lints.lints.get("cargo"))
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Returns the path of the table of `dep` in its manifest, such as
+/// `["target", "cfg(unix)", "dependencies", "bar"]`.
+fn dependency_toml_path(dep: &Dependency) -> Vec<String> {
+    let mut toml_path = Vec::new();
+    if let Some(platform) = dep.platform() {
+        toml_path.push("target".to_string());
+        toml_path.push(platform.to_string());
+    }
+    toml_path.push(dep.kind().kind_table().to_string());
+    toml_path.push(dep.name_in_toml().to_string());
+    toml_path
+}
+
+fn dependency_span(manifest: &Manifest, dep: &Dependency) -> Option<Range<usize>> {
+    let toml_path = dependency_toml_path(dep);
+    let toml_path = toml_path.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+    get_span(manifest.document(), &toml_path, false)
+}
+
+const DUPLICATE_VERSIONS: Lint = Lint {
+    name: "duplicate_versions",
+    desc: "multiple versions of the same package are depended on",
+    groups: &[],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: None,
+    feature_gate: None,
+    docs: Some(
+        r#"
+### What it does
+Checks for packages which are depended on, directly or transitively, in more
+than one semver-incompatible version.
+
+### Why it is bad
+Each version is compiled separately, which increases build times and the size
+of the artifacts. The types of one version also can't be used where the
+types of another version are expected, which leads to confusing errors.
+
+### Example
+```toml
+[dependencies]
+rand = "0.8"
+# `rand_core 0.5` is used by `some-crate`, while `rand 0.8` uses `rand_core 0.6`
+some-crate = "1.0"
+```
+"#,
+    ),
+};
+
+/// Checks the packages depended on by `pkg` in `resolve` for duplicate
+/// versions, pointing at the dependencies of `pkg` which pull each of them in.
+pub fn check_duplicate_versions(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    resolve: &Resolve,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason) =
+        DUPLICATE_VERSIONS.level(pkg_lints, manifest.edition(), manifest.unstable_features());
+    if lint_level == LintLevel::Allow {
+        return Ok(());
+    }
+
+    fn collect(resolve: &Resolve, pkg_id: PackageId, used: &mut BTreeSet<PackageId>) {
+        if used.insert(pkg_id) {
+            for (dep_id, _) in resolve.deps(pkg_id) {
+                collect(resolve, dep_id, used);
+            }
+        }
+    }
+
+    // The packages pulled in by each direct dependency of `pkg`.
+    let direct_deps = resolve
+        .deps(pkg.package_id())
+        .map(|(dep_id, deps)| {
+            let mut used = BTreeSet::new();
+            collect(resolve, dep_id, &mut used);
+            (deps, used)
+        })
+        .collect::<Vec<_>>();
+    let mut versions: BTreeMap<InternedString, BTreeSet<PackageId>> = BTreeMap::new();
+    for (_, used) in &direct_deps {
+        for &id in used {
+            versions.entry(id.name()).or_default().insert(id);
+        }
+    }
+
+    let mut emitted_source = None;
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    for (name, ids) in versions {
+        if ids.len() < 2 {
+            continue;
+        }
+        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+            *error_count += 1;
+        }
+        let level = lint_level.to_diagnostic_level();
+        let title = format!(
+            "multiple versions of `{name}` are depended on: {}",
+            ids.iter()
+                .map(|id| format!("v{}", id.version()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        let labels = direct_deps
+            .iter()
+            .flat_map(|(deps, used)| {
+                let pulled_in = ids
+                    .iter()
+                    .filter(|id| used.contains(id))
+                    .map(|id| format!("`{name} v{}`", id.version()))
+                    .collect::<Vec<_>>()
+                    .join(" and ");
+                deps.iter().filter_map(move |dep| {
+                    let span = dependency_span(manifest, dep)?;
+                    (!pulled_in.is_empty()).then(|| (span, format!("pulls in {pulled_in}")))
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut snippet = Snippet::source(manifest.contents())
+            .origin(&manifest_path)
+            .fold(true);
+        for (span, label) in &labels {
+            snippet = snippet.annotation(level.span(span.clone()).label(label));
+        }
+        let mut message = level.title(&title).snippet(snippet);
+        if emitted_source.is_none() {
+            emitted_source = Some(format!(
+                "`cargo::{}` is set to `{lint_level}` {reason}",
+                DUPLICATE_VERSIONS.name
+            ));
+            message = message.footer(Level::Note.title(emitted_source.as_ref().unwrap()));
+        }
+        gctx.shell().print_message(message)?;
+    }
+    Ok(())
+}
+
+const INEFFECTIVE_DEFAULT_FEATURES: Lint = Lint {
+    name: "ineffective_default_features",
+    desc: "`default-features = false` has no effect, as the default features are enabled by another dependent",
+    groups: &[],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: None,
+    feature_gate: None,
+    docs: Some(
+        r#"
+### What it does
+Checks for dependencies declared with `default-features = false`, whose
+default features end up being enabled anyway because another package depends
+on them with the default features.
+
+### Why it is bad
+Features are unified across all the dependents of a package, so the default
+features are built in all cases. This makes it likely that the package
+relies on a default feature without noticing, and breaks once the other
+dependent disables them as well.
+
+### Example
+```toml
+[workspace]
+members = ["a", "b"]
+```
+
+```toml
+# a/Cargo.toml
+[dependencies]
+serde = { version = "1.0", default-features = false }
+```
+
+```toml
+# b/Cargo.toml
+[dependencies]
+serde = "1.0"
+```
+"#,
+    ),
+};
+
+/// Checks the dependencies of `pkg` declared with `default-features = false`
+/// for default features enabled through feature unification.
+pub fn check_ineffective_default_features(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    resolve: &Resolve,
+    resolved_features: &ResolvedFeatures,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason) = INEFFECTIVE_DEFAULT_FEATURES.level(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+    );
+    if lint_level == LintLevel::Allow {
+        return Ok(());
+    }
+
+    let mut emitted_source = None;
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    for (dep_id, deps) in resolve.deps(pkg.package_id()) {
+        let has_default_features = resolve
+            .summary(dep_id)
+            .features()
+            .get("default")
+            .is_some_and(|default| !default.is_empty());
+        if !has_default_features {
+            continue;
+        }
+        for dep in deps.iter().filter(|dep| !dep.uses_default_features()) {
+            let features_for = if dep.is_build() {
+                FeaturesFor::HostDep
+            } else {
+                FeaturesFor::NormalOrDev
+            };
+            let default_enabled = resolved_features
+                .activated_features_unverified(dep_id, features_for)
+                .is_some_and(|features| features.iter().any(|f| f == "default"));
+            if !default_enabled {
+                continue;
+            }
+            let Some(span) = dependency_span(manifest, dep) else {
+                continue;
+            };
+            if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+                *error_count += 1;
+            }
+            let mut enabled_by = resolve
+                .iter()
+                .filter(|&id| id != pkg.package_id())
+                .filter(|&id| {
+                    resolve.deps(id).any(|(id, deps)| {
+                        id == dep_id && deps.iter().any(|d| d.uses_default_features())
+                    })
+                })
+                .map(|id| format!("`{id}`"))
+                .collect::<Vec<_>>();
+            enabled_by.sort();
+            let enabled_by = format!(
+                "the default features of `{dep_id}` are enabled by {}",
+                enabled_by.join(", ")
+            );
+            let level = lint_level.to_diagnostic_level();
+            let mut message = level.title(INEFFECTIVE_DEFAULT_FEATURES.desc).snippet(
+                Snippet::source(manifest.contents())
+                    .origin(&manifest_path)
+                    .annotation(level.span(span))
+                    .fold(true),
+            );
+            if emitted_source.is_none() {
+                emitted_source = Some(format!(
+                    "`cargo::{}` is set to `{lint_level}` {reason}",
+                    INEFFECTIVE_DEFAULT_FEATURES.name
+                ));
+                message = message.footer(Level::Note.title(emitted_source.as_ref().unwrap()));
+            }
+            message = message.footer(Level::Note.title(&enabled_by));
+            gctx.shell().print_message(message)?;
+        }
+    }
+    Ok(())
+}
+
+const UNINHERITED_WORKSPACE_DEPENDENCY: Lint = Lint {
+    name: "uninherited_workspace_dependency",
+    desc: "dependency does not inherit the `[workspace.dependencies]` entry of the same name",
+    groups: &[],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: None,
+    feature_gate: None,
+    docs: Some(
+        r#"
+### What it does
+Checks for dependencies of a workspace member which are declared in full,
+while the workspace declares them in `[workspace.dependencies]`.
+
+### Why it is bad
+The version, source and features of the dependency have to be kept in sync
+by hand with the rest of the workspace, which defeats the purpose of
+`[workspace.dependencies]`.
+
+### Example
+```toml
+[workspace.dependencies]
+regex = "1.10"
+```
+
+```toml
+[dependencies]
+regex = "1.9"
+```
+
+Should be written as:
+
+```toml
+[dependencies]
+regex.workspace = true
+```
+"#,
+    ),
+};
+
+pub fn check_uninherited_workspace_dependencies(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    ws_document: &ImDocument<String>,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason) = UNINHERITED_WORKSPACE_DEPENDENCY.level(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+    );
+    if lint_level == LintLevel::Allow {
+        return Ok(());
+    }
+    let Some(ws_deps) = ws_document
+        .as_item()
+        .get("workspace")
+        .and_then(|ws| ws.get("dependencies"))
+        .and_then(|deps| deps.as_table_like())
+    else {
+        return Ok(());
+    };
+
+    let original_toml = manifest.original_toml();
+    let mut orig_deps = vec![
+        (
+            original_toml.dependencies.as_ref(),
+            vec![DepKind::Normal.kind_table()],
+        ),
+        (
+            original_toml.dev_dependencies(),
+            vec![DepKind::Development.kind_table()],
+        ),
+        (
+            original_toml.build_dependencies(),
+            vec![DepKind::Build.kind_table()],
+        ),
+    ];
+    for (name, platform) in original_toml.target.iter().flatten() {
+        orig_deps.push((
+            platform.dependencies.as_ref(),
+            vec!["target", name, DepKind::Normal.kind_table()],
+        ));
+        orig_deps.push((
+            platform.dev_dependencies(),
+            vec!["target", name, DepKind::Development.kind_table()],
+        ));
+        orig_deps.push((
+            platform.build_dependencies(),
+            vec!["target", name, DepKind::Build.kind_table()],
+        ));
+    }
+
+    let mut emitted_source = None;
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    for (deps, toml_path) in orig_deps {
+        for (name, dep) in deps.into_iter().flatten() {
+            if matches!(dep, InheritableDependency::Inherit(_)) || !ws_deps.contains_key(name) {
+                continue;
+            }
+            let toml_path = toml_path
+                .iter()
+                .copied()
+                .chain(std::iter::once(name.as_str()))
+                .collect::<Vec<_>>();
+            let Some(span) = get_span(manifest.document(), &toml_path, false) else {
+                continue;
+            };
+            if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+                *error_count += 1;
+            }
+            let level = lint_level.to_diagnostic_level();
+            let mut message = level.title(UNINHERITED_WORKSPACE_DEPENDENCY.desc).snippet(
+                Snippet::source(manifest.contents())
+                    .origin(&manifest_path)
+                    .annotation(level.span(span))
+                    .fold(true),
+            );
+            if emitted_source.is_none() {
+                emitted_source = Some(format!(
+                    "`cargo::{}` is set to `{lint_level}` {reason}",
+                    UNINHERITED_WORKSPACE_DEPENDENCY.name
+                ));
+                message = message.footer(Level::Note.title(emitted_source.as_ref().unwrap()));
+            }
+            let help = format!("consider inheriting it with `{name}.workspace = true`");
+            message = message.footer(Level::Help.title(&help));
+            gctx.shell().print_message(message)?;
+        }
+    }
+    Ok(())
+}
+
+const UNUSED_DEPENDENCIES: Lint = Lint {
+    name: "unused_dependencies",
+    desc: "unused dependency",
+    groups: &[],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: None,
+    feature_gate: None,
+    docs: Some(
+        r#"
+### What it does
+Checks for dependencies which none of the targets of the package use, based
+on the `unused_crate_dependencies` information of `rustc`.
+
+A dependency is only reported once all the targets which can use it have been
+built, for example with `cargo check --all-targets`. Dependencies with a
+`links` key are never reported, as they may only be needed for their native
+library.
+
+This requires a nightly `rustc`.
+
+### Why it is bad
+Unused dependencies needlessly increase build times, and the amount of code
+which has to be audited and kept up to date.
+
+### Example
+```toml
+[dependencies]
+# Not used by any target of the package
+regex = "1.10"
+```
+"#,
+    ),
+};
+
+/// Returns the extra `rustc` flags needed to collect the unused externs of
+/// `unit` for the `unused_dependencies` lint.
+///
+/// `rustc` only collects the unused externs when the `unused_crate_dependencies`
+/// lint isn't allowed, so it is enabled as a warning when the package doesn't
+/// configure it. In this mode, `rustc` doesn't emit the lint itself, but
+/// reports its level along with the unused externs, and Cargo fails the
+/// compilation of the unit if the lint is denied.
+pub fn unused_dependencies_rustc_args(gctx: &GlobalContext, unit: &Unit) -> Vec<&'static str> {
+    if !gctx.cli_unstable().cargo_lints
+        || !unit.is_local()
+        || unit.mode.is_doc()
+        || unit.mode.is_doc_test()
+        || unit.mode.is_doc_scrape()
+        || unit.mode.is_run_custom_build()
+    {
+        return Vec::new();
+    }
+    let manifest = unit.pkg.manifest();
+    let (lint_level, _) = UNUSED_DEPENDENCIES.level(
+        &cargo_lints(&unit.pkg),
+        manifest.edition(),
+        manifest.unstable_features(),
+    );
+    if lint_level == LintLevel::Allow {
+        return Vec::new();
+    }
+    let mut args = vec!["-Zunstable-options", "--json=unused-externs"];
+    let rust_lint_configured = manifest
+        .normalized_toml()
+        .lints
+        .as_ref()
+        .and_then(|lints| lints.lints.get("rust"))
+        .is_some_and(|lints| lints.contains_key("unused_crate_dependencies"));
+    if !rust_lint_configured {
+        args.push("-Wunused-crate-dependencies");
+    }
+    args
+}
+
+/// Reports the dependencies of the workspace members which no unit of the
+/// build uses, given the `unused_externs` reported by `rustc` for each unit.
+///
+/// Returns the number of errors emitted.
+pub fn check_unused_dependencies(
+    build_runner: &BuildRunner<'_, '_>,
+    unused_externs: &HashMap<Unit, Vec<String>>,
+) -> CargoResult<usize> {
+    let gctx = build_runner.bcx.gctx;
+    let resolve = &build_runner.bcx.resolve;
+    let mut error_count = 0;
+
+    // The units of each package which `rustc` could report unused externs for.
+    let mut units_by_pkg: BTreeMap<PackageId, Vec<&Unit>> = BTreeMap::new();
+    for unit in build_runner.bcx.unit_graph.keys() {
+        if !unused_dependencies_rustc_args(gctx, unit).is_empty() {
+            units_by_pkg
+                .entry(unit.pkg.package_id())
+                .or_default()
+                .push(unit);
+        }
+    }
+
+    for (pkg_id, units) in units_by_pkg {
+        let pkg = &units[0].pkg;
+        let manifest = pkg.manifest();
+        let (lint_level, reason) = UNUSED_DEPENDENCIES.level(
+            &cargo_lints(pkg),
+            manifest.edition(),
+            manifest.unstable_features(),
+        );
+
+        // Whether each dependency is used by any unit, along with the targets
+        // of the units which were given the dependency.
+        let mut dep_usage: HashMap<PackageId, (bool, HashSet<(&Target, bool)>)> = HashMap::new();
+        for unit in &units {
+            let unused = unused_externs.get(*unit);
+            for dep in build_runner.unit_deps(unit) {
+                if dep.unit.pkg.package_id() == pkg_id || dep.unit.mode.is_run_custom_build() {
+                    continue;
+                }
+                let usage = dep_usage.entry(dep.unit.pkg.package_id()).or_default();
+                // Units which reported nothing, for example because they
+                // failed to compile, are assumed to use all their dependencies.
+                usage.0 |= unused.map_or(true, |unused| {
+                    !unused
+                        .iter()
+                        .any(|name| name == dep.extern_crate_name.as_str())
+                });
+                usage.1.insert((&unit.target, unit.mode.is_any_test()));
+            }
+        }
+
+        let mut emitted_source = None;
+        let manifest_path = rel_cwd_manifest_path(pkg.manifest_path(), gctx);
+        for (dep_id, deps) in resolve.deps(pkg_id) {
+            let Some((used, targets)) = dep_usage.get(&dep_id) else {
+                continue;
+            };
+            if *used || resolve.summary(dep_id).links().is_some() {
+                continue;
+            }
+            for dep in deps {
+                if !all_eligible_targets_built(pkg, dep.kind(), targets) {
+                    continue;
+                }
+                let Some(span) = dependency_span(manifest, dep) else {
+                    continue;
+                };
+                if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+                    error_count += 1;
+                }
+                let level = lint_level.to_diagnostic_level();
+                let mut message = level.title(UNUSED_DEPENDENCIES.desc).snippet(
+                    Snippet::source(manifest.contents())
+                        .origin(&manifest_path)
+                        .annotation(level.span(span))
+                        .fold(true),
+                );
+                if emitted_source.is_none() {
+                    emitted_source = Some(format!(
+                        "`cargo::{}` is set to `{lint_level}` {reason}",
+                        UNUSED_DEPENDENCIES.name
+                    ));
+                    message = message.footer(Level::Note.title(emitted_source.as_ref().unwrap()));
+                }
+                let help = format!(
+                    "remove the dependency, or add `use {} as _;` if it is only needed for linking",
+                    dep.name_in_toml().replace('-', "_")
+                );
+                message = message.footer(Level::Help.title(&help));
+                gctx.shell().print_message(message)?;
+            }
+        }
+    }
+    Ok(error_count)
+}
+
+/// Returns whether the build compiled all the targets of `pkg` which can use
+/// a dependency of the given `kind`, out of the `built` targets which were
+/// given the dependency along with whether they were built as tests.
+fn all_eligible_targets_built(
+    pkg: &Package,
+    kind: DepKind,
+    built: &HashSet<(&Target, bool)>,
+) -> bool {
+    let is_built = |target: &Target, test: bool| {
+        built
+            .iter()
+            .any(|(t, is_test)| *t == target && (!test || *is_test))
+    };
+    pkg.targets().iter().all(|target| match kind {
+        DepKind::Normal => target.is_custom_build() || is_built(target, false),
+        DepKind::Development => {
+            if target.is_custom_build() {
+                true
+            } else if target.is_test() || target.is_bench() || target.is_example() {
+                is_built(target, false)
+            } else {
+                !target.tested() || is_built(target, true)
+            }
+        }
+        DepKind::Build => !target.is_custom_build() || is_built(target, false),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use itertools::Itertools;
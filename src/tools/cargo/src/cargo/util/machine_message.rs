@@ -103,6 +103,21 @@ fn reason(&self) -> &str {
     }
 }
 
+#[derive(Serialize)]
+pub struct RebuildReason<'a> {
+    pub package_id: PackageIdSpec,
+    pub target: &'a Target,
+    pub mode: CompileMode,
+    pub message: String,
+    pub dirty_reason: serde_json::Value,
+}
+
+impl<'a> Message for RebuildReason<'a> {
+    fn reason(&self) -> &str {
+        "rebuild-reason"
+    }
+}
+
 #[derive(Serialize)]
 pub struct BuildFinished {
     pub success: bool,
@@ -0,0 +1,310 @@
+//! Tests for -Zartifact-cache.
+
+use std::fs;
+
+use cargo_test_support::prelude::*;
+use cargo_test_support::registry::Package;
+use cargo_test_support::str;
+use cargo_test_support::{basic_manifest, paths, project_in, Project};
+
+fn workspace(dir: &str) -> Project {
+    project_in(dir)
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.1.0"
+                edition = "2015"
+
+                [dependencies]
+                dep = "1.0"
+            "#,
+        )
+        .file(
+            "src/main.rs",
+            "fn main() { println!(\"{}\", dep::generated()); }",
+        )
+        .build()
+}
+
+#[cargo_test]
+fn reuses_outputs_across_workspaces() {
+    Package::new("dep", "1.0.0")
+        .file(
+            "build.rs",
+            r#"
+                use std::env;
+                use std::fs;
+                use std::path::Path;
+
+                fn main() {
+                    println!("cargo::rerun-if-changed=build.rs");
+                    let out_dir = env::var("OUT_DIR").unwrap();
+                    fs::write(
+                        Path::new(&out_dir).join("generated.rs"),
+                        "pub fn generated() -> &'static str { \"generated\" }",
+                    )
+                    .unwrap();
+                }
+            "#,
+        )
+        .file(
+            "src/lib.rs",
+            r#"include!(concat!(env!("OUT_DIR"), "/generated.rs"));"#,
+        )
+        .publish();
+
+    let first = workspace("first");
+    first
+        .cargo("run -v -Zartifact-cache")
+        .masquerade_as_nightly_cargo(&["artifact-cache"])
+        .with_stdout_data(str![[r#"
+generated
+
+"#]])
+        .with_stderr_contains("[RUNNING] `rustc --crate-name dep [..]")
+        .with_stderr_contains("[RUNNING] `[..]/build/dep-[..]/build-script-build`")
+        .run();
+    assert!(paths::home().join(".cargo/artifact-cache").is_dir());
+
+    let second = workspace("second");
+    second
+        .cargo("run -v -Zartifact-cache")
+        .masquerade_as_nightly_cargo(&["artifact-cache"])
+        .with_stdout_data(str![[r#"
+generated
+
+"#]])
+        .with_stderr_does_not_contain("[RUNNING] `rustc --crate-name dep [..]")
+        .with_stderr_does_not_contain("[RUNNING] `rustc --crate-name build_script_build [..]")
+        .with_stderr_does_not_contain("[RUNNING] `[..]/build-script-build`")
+        .run();
+
+    // A clean target directory is restored from the cache as well.
+    second.cargo("clean").run();
+    second
+        .cargo("build -v -Zartifact-cache")
+        .masquerade_as_nightly_cargo(&["artifact-cache"])
+        .with_stderr_does_not_contain("[RUNNING] `rustc --crate-name dep [..]")
+        .run();
+
+    // Without the flag, everything is built again.
+    let third = workspace("third");
+    third
+        .cargo("build -v")
+        .with_stderr_contains("[RUNNING] `rustc --crate-name dep [..]")
+        .run();
+}
+
+#[cargo_test]
+fn custom_cache_dir() {
+    Package::new("dep", "1.0.0")
+        .file(
+            "src/lib.rs",
+            "pub fn generated() -> &'static str { \"generated\" }",
+        )
+        .publish();
+
+    let p = workspace("foo");
+    p.cargo("build -Zartifact-cache --config build.artifact-cache-dir='artifacts'")
+        .masquerade_as_nightly_cargo(&["artifact-cache"])
+        .run();
+
+    assert!(!paths::home().join(".cargo/artifact-cache").exists());
+    let entries = p
+        .root()
+        .join("artifacts")
+        .read_dir()
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(entries.len(), 1);
+    assert!(entries[0].starts_with("dep-"));
+}
+
+#[cargo_test]
+fn incomplete_entries_are_rebuilt() {
+    Package::new("dep", "1.0.0")
+        .file(
+            "src/lib.rs",
+            "pub fn generated() -> &'static str { \"generated\" }",
+        )
+        .publish();
+
+    let first = workspace("first");
+    first
+        .cargo("build -Zartifact-cache")
+        .masquerade_as_nightly_cargo(&["artifact-cache"])
+        .run();
+
+    let cache = paths::home().join(".cargo/artifact-cache");
+    let entry = cache.read_dir().unwrap().next().unwrap().unwrap().path();
+    let rlib = entry
+        .join("files")
+        .read_dir()
+        .unwrap()
+        .map(|file| file.unwrap().path())
+        .find(|file| file.extension().map_or(false, |ext| ext == "rlib"))
+        .unwrap();
+    fs::remove_file(rlib).unwrap();
+
+    let second = workspace("second");
+    second
+        .cargo("run -v -Zartifact-cache")
+        .masquerade_as_nightly_cargo(&["artifact-cache"])
+        .with_stdout_data(str![[r#"
+generated
+
+"#]])
+        .with_stderr_contains("[RUNNING] `rustc --crate-name dep [..]")
+        .run();
+}
+
+#[cargo_test]
+fn build_script_depending_on_env_is_not_cached() {
+    Package::new("dep", "1.0.0")
+        .file(
+            "build.rs",
+            r#"
+                use std::env;
+                use std::fs;
+                use std::path::Path;
+
+                fn main() {
+                    println!("cargo::rerun-if-env-changed=DEP_GREETING");
+                    let greeting = env::var("DEP_GREETING").unwrap_or_default();
+                    let out_dir = env::var("OUT_DIR").unwrap();
+                    fs::write(
+                        Path::new(&out_dir).join("generated.rs"),
+                        format!("pub fn generated() -> &'static str {{ {greeting:?} }}"),
+                    )
+                    .unwrap();
+                }
+            "#,
+        )
+        .file(
+            "src/lib.rs",
+            r#"include!(concat!(env!("OUT_DIR"), "/generated.rs"));"#,
+        )
+        .publish();
+
+    let first = workspace("first");
+    first
+        .cargo("run -Zartifact-cache")
+        .masquerade_as_nightly_cargo(&["artifact-cache"])
+        .env("DEP_GREETING", "hello")
+        .with_stdout_data(str![[r#"
+hello
+
+"#]])
+        .run();
+
+    let second = workspace("second");
+    second
+        .cargo("run -v -Zartifact-cache")
+        .masquerade_as_nightly_cargo(&["artifact-cache"])
+        .env("DEP_GREETING", "bonjour")
+        .with_stdout_data(str![[r#"
+bonjour
+
+"#]])
+        .with_stderr_contains("[RUNNING] `[..]/build-script-build`")
+        .run();
+}
+
+#[cargo_test]
+fn build_script_without_rerun_if_is_not_cached() {
+    Package::new("dep", "1.0.0")
+        .file(
+            "build.rs",
+            r#"
+                fn main() {
+                    println!("cargo::rustc-env=GENERATED=generated");
+                }
+            "#,
+        )
+        .file(
+            "src/lib.rs",
+            r#"pub fn generated() -> &'static str { env!("GENERATED") }"#,
+        )
+        .publish();
+
+    let first = workspace("first");
+    first
+        .cargo("build -Zartifact-cache")
+        .masquerade_as_nightly_cargo(&["artifact-cache"])
+        .run();
+
+    let second = workspace("second");
+    second
+        .cargo("build -v -Zartifact-cache")
+        .masquerade_as_nightly_cargo(&["artifact-cache"])
+        .with_stderr_contains("[RUNNING] `[..]/build-script-build`")
+        .run();
+}
+
+#[cargo_test]
+fn units_depending_on_local_packages_are_not_cached() {
+    Package::new("leaf", "1.0.0")
+        .file(
+            "src/lib.rs",
+            "pub fn generated() -> &'static str { \"leaf\" }",
+        )
+        .publish();
+    Package::new("dep", "1.0.0")
+        .dep("leaf", "1.0")
+        .file("src/lib.rs", "pub use leaf::generated;")
+        .publish();
+
+    let patched = |dir: &str, generated: &str| {
+        project_in(dir)
+            .file(
+                "Cargo.toml",
+                r#"
+                    [package]
+                    name = "foo"
+                    version = "0.1.0"
+                    edition = "2015"
+
+                    [dependencies]
+                    dep = "1.0"
+
+                    [patch.crates-io]
+                    leaf = { path = "leaf" }
+                "#,
+            )
+            .file(
+                "src/main.rs",
+                "fn main() { println!(\"{}\", dep::generated()); }",
+            )
+            .file("leaf/Cargo.toml", &basic_manifest("leaf", "1.0.0"))
+            .file(
+                "leaf/src/lib.rs",
+                &format!("pub fn generated() -> &'static str {{ {generated:?} }}"),
+            )
+            .build()
+    };
+
+    let first = patched("first", "patched");
+    first
+        .cargo("run -Zartifact-cache")
+        .masquerade_as_nightly_cargo(&["artifact-cache"])
+        .with_stdout_data(str![[r#"
+patched
+
+"#]])
+        .run();
+
+    // `dep` is the same registry package, but the crate it depends on changed.
+    let second = patched("second", "changed");
+    second
+        .cargo("run -v -Zartifact-cache")
+        .masquerade_as_nightly_cargo(&["artifact-cache"])
+        .with_stdout_data(str![[r#"
+changed
+
+"#]])
+        .with_stderr_contains("[RUNNING] `rustc --crate-name dep [..]")
+        .run();
+}
@@ -0,0 +1,471 @@
+//! Tests for `-Zbuild-sandbox`.
+
+use cargo_test_support::prelude::*;
+use cargo_test_support::{project, str, tools};
+
+/// Whether the kernel the tests run on can enforce the sandbox.
+#[cfg(target_os = "linux")]
+fn landlock_enabled() -> bool {
+    std::fs::read_to_string("/sys/kernel/security/lsm")
+        .map_or(false, |lsm| lsm.trim().split(',').any(|m| m == "landlock"))
+}
+
+#[cfg(target_os = "linux")]
+#[cargo_test]
+fn build_script_writes_out_dir() {
+    if !landlock_enabled() {
+        return;
+    }
+    let p = project()
+        .file(
+            "src/main.rs",
+            r#"
+                include!(concat!(env!("OUT_DIR"), "/hello.rs"));
+                fn main() { println!("{}", message()); }
+            "#,
+        )
+        .file(
+            "build.rs",
+            r#"
+                fn main() {
+                    let out_dir = std::env::var("OUT_DIR").unwrap();
+                    std::fs::write(
+                        format!("{out_dir}/hello.rs"),
+                        "fn message() -> &'static str { \"hello\" }",
+                    )
+                    .unwrap();
+                    let tmp = std::env::temp_dir().join("scratch");
+                    std::fs::write(&tmp, "").unwrap();
+                    std::fs::remove_file(&tmp).unwrap();
+                }
+            "#,
+        )
+        .build();
+
+    p.cargo("run -Zbuild-sandbox")
+        .masquerade_as_nightly_cargo(&["build-sandbox"])
+        .with_stdout_data(str![[r#"
+hello
+
+"#]])
+        .run();
+}
+
+#[cfg(target_os = "linux")]
+#[cargo_test]
+fn build_script_write_outside_out_dir_is_denied() {
+    if !landlock_enabled() {
+        return;
+    }
+    let p = project()
+        .file("src/lib.rs", "")
+        .file(
+            "build.rs",
+            r#"
+                fn main() {
+                    let dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+                    std::fs::write(format!("{dir}/generated.rs"), "").unwrap();
+                }
+            "#,
+        )
+        .build();
+
+    p.cargo("check -Zbuild-sandbox")
+        .masquerade_as_nightly_cargo(&["build-sandbox"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[COMPILING] foo v0.0.1 ([ROOT]/foo)
+[ERROR] failed to run custom build command for `foo v0.0.1 ([ROOT]/foo)`
+[NOTE] the build script was run in a sandbox (`-Zbuild-sandbox`), which only allows writing to `OUT_DIR`, reading the package, the target directory and the toolchain, and denies network access
+[HELP] if the build script needs more access, allow it in the `[build-sandbox.package.foo]` table of the Cargo configuration
+
+Caused by:
+  process didn't exit successfully: `[..] [ROOT]/foo/target/debug/build/foo-[HASH]/build-script-build` ([EXIT_STATUS]: 101)
+  --- stderr
+...
+[..]Permission denied[..]
+...
+"#]])
+        .run();
+    assert!(!p.root().join("generated.rs").exists());
+
+    p.cargo("check").run();
+    assert!(p.root().join("generated.rs").exists());
+}
+
+#[cfg(target_os = "linux")]
+#[cargo_test]
+fn build_script_network_is_denied() {
+    if !landlock_enabled() {
+        return;
+    }
+    let p = project()
+        .file("src/lib.rs", "")
+        .file(
+            "build.rs",
+            r#"
+                fn main() {
+                    let err = std::net::TcpListener::bind("127.0.0.1:0").unwrap_err();
+                    assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied);
+                }
+            "#,
+        )
+        .build();
+
+    p.cargo("check -Zbuild-sandbox")
+        .masquerade_as_nightly_cargo(&["build-sandbox"])
+        .run();
+}
+
+#[cfg(target_os = "linux")]
+#[cargo_test]
+fn build_script_unix_socket_is_denied() {
+    if !landlock_enabled() {
+        return;
+    }
+    let p = project()
+        .file("src/lib.rs", "")
+        .file(
+            "build.rs",
+            r#"
+                fn main() {
+                    let out_dir = std::env::var("OUT_DIR").unwrap();
+                    let path = format!("{out_dir}/socket");
+                    let err = std::os::unix::net::UnixListener::bind(&path).unwrap_err();
+                    assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied);
+                }
+            "#,
+        )
+        .build();
+
+    p.cargo("check -Zbuild-sandbox")
+        .masquerade_as_nightly_cargo(&["build-sandbox"])
+        .run();
+}
+
+#[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+#[cargo_test]
+fn build_script_foreign_abi_socket_is_denied() {
+    if !landlock_enabled() {
+        return;
+    }
+    let p = project()
+        .file("src/lib.rs", "")
+        .file(
+            "build.rs",
+            r#"
+                use std::arch::asm;
+
+                // `socket(AF_INET, SOCK_STREAM, 0)` through the i386 ABI.
+                fn socket_i386() -> i32 {
+                    let ret: i32;
+                    unsafe {
+                        asm!(
+                            "xchg {domain:r}, rbx",
+                            "int 0x80",
+                            "xchg {domain:r}, rbx",
+                            domain = inout(reg) 2u64 => _,
+                            inlateout("eax") 359 => ret,
+                            in("ecx") 1,
+                            in("edx") 0,
+                            lateout("r8") _,
+                            lateout("r9") _,
+                            lateout("r10") _,
+                            lateout("r11") _,
+                        );
+                    }
+                    ret
+                }
+
+                // `socket(AF_INET, SOCK_STREAM, 0)` through the x32 ABI.
+                fn socket_x32() -> i64 {
+                    let ret: i64;
+                    unsafe {
+                        asm!(
+                            "syscall",
+                            inlateout("rax") 41 | 0x4000_0000i64 => ret,
+                            in("rdi") 2,
+                            in("rsi") 1,
+                            in("rdx") 0,
+                            lateout("rcx") _,
+                            lateout("r11") _,
+                        );
+                    }
+                    ret
+                }
+
+                fn main() {
+                    assert_eq!(socket_i386(), -13);
+                    assert_eq!(socket_x32(), -13);
+                }
+            "#,
+        )
+        .build();
+
+    p.cargo("check -Zbuild-sandbox")
+        .masquerade_as_nightly_cargo(&["build-sandbox"])
+        .run();
+}
+
+#[cfg(target_os = "linux")]
+#[cargo_test]
+fn config_allow_list() {
+    if !landlock_enabled() {
+        return;
+    }
+    let p = project()
+        .file("src/lib.rs", "")
+        .file("generated/.keep", "")
+        .file(
+            "build.rs",
+            r#"
+                fn main() {
+                    let dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+                    std::fs::write(format!("{dir}/generated/lib.rs"), "").unwrap();
+                    std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+                }
+            "#,
+        )
+        .file(
+            ".cargo/config.toml",
+            r#"
+                [build-sandbox.package.foo]
+                allow-write = ["generated"]
+                allow-network = true
+            "#,
+        )
+        .build();
+
+    p.cargo("check -Zbuild-sandbox")
+        .masquerade_as_nightly_cargo(&["build-sandbox"])
+        .run();
+    assert!(p.root().join("generated/lib.rs").exists());
+}
+
+#[cfg(target_os = "linux")]
+#[cargo_test]
+fn config_allow_list_is_limited_to_the_package() {
+    let p = project()
+        .file("src/lib.rs", "")
+        .file("build.rs", "fn main() {}")
+        .file(
+            ".cargo/config.toml",
+            r#"
+                [build-sandbox.package.foo]
+                allow-write = ["../outside"]
+            "#,
+        )
+        .build();
+
+    p.cargo("check -Zbuild-sandbox")
+        .masquerade_as_nightly_cargo(&["build-sandbox"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+...
+[ERROR] `build-sandbox.package.foo.allow-write` contains `../outside`, but only paths inside the package without `..` are allowed
+...
+"#]])
+        .run();
+
+    p.change_file(
+        ".cargo/config.toml",
+        r#"
+            [build-sandbox.package.foo]
+            allow-read = ["/"]
+        "#,
+    );
+    p.cargo("check -Zbuild-sandbox")
+        .masquerade_as_nightly_cargo(&["build-sandbox"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+...
+[ERROR] `build-sandbox.package.foo.allow-read` contains `/`, but only paths inside the package without `..` are allowed
+...
+"#]])
+        .run();
+}
+
+#[cfg(target_os = "linux")]
+#[cargo_test]
+fn dependency_manifest_cannot_allow_itself() {
+    if !landlock_enabled() {
+        return;
+    }
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+
+                [dependencies]
+                dep = { path = "dep" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file(
+            "dep/Cargo.toml",
+            r#"
+                [package]
+                name = "dep"
+                version = "0.0.1"
+                edition = "2015"
+
+                [package.build-sandbox]
+                allow-write = ["/"]
+                allow-network = true
+            "#,
+        )
+        .file("dep/src/lib.rs", "")
+        .file(
+            "dep/build.rs",
+            r#"
+                fn main() {
+                    std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+                }
+            "#,
+        )
+        .build();
+
+    p.cargo("check -Zbuild-sandbox")
+        .masquerade_as_nightly_cargo(&["build-sandbox"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+...
+[ERROR] failed to run custom build command for `dep v0.0.1 ([ROOT]/foo/dep)`
+[NOTE] the build script was run in a sandbox (`-Zbuild-sandbox`), which only allows writing to `OUT_DIR`, reading the package, the target directory and the toolchain, and denies network access
+[HELP] if the build script needs more access, allow it in the `[build-sandbox.package.dep]` table of the Cargo configuration
+...
+"#]])
+        .run();
+}
+
+#[cfg(target_os = "linux")]
+#[cargo_test]
+fn proc_macro_is_sandboxed() {
+    if !landlock_enabled() {
+        return;
+    }
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+
+                [dependencies]
+                pm = { path = "pm" }
+            "#,
+        )
+        .file(
+            "src/lib.rs",
+            r#"
+                #[macro_use]
+                extern crate pm;
+                check!();
+            "#,
+        )
+        .file(
+            "pm/Cargo.toml",
+            r#"
+                [package]
+                name = "pm"
+                version = "0.0.1"
+                edition = "2015"
+
+                [lib]
+                proc-macro = true
+            "#,
+        )
+        .file(
+            "pm/src/lib.rs",
+            r#"
+                extern crate proc_macro;
+                use proc_macro::TokenStream;
+
+                #[proc_macro]
+                pub fn check(_input: TokenStream) -> TokenStream {
+                    let dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+                    assert!(std::fs::write(format!("{dir}/leak"), "").is_err());
+                    assert!(std::net::TcpListener::bind("127.0.0.1:0").is_err());
+                    TokenStream::new()
+                }
+            "#,
+        )
+        .build();
+
+    p.cargo("check -Zbuild-sandbox")
+        .masquerade_as_nightly_cargo(&["build-sandbox"])
+        .run();
+    assert!(!p.root().join("leak").exists());
+}
+
+#[cfg(target_os = "linux")]
+#[cargo_test]
+fn proc_macro_through_rustc_wrapper_is_not_sandboxed() {
+    if !landlock_enabled() {
+        return;
+    }
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+
+                [dependencies]
+                pm = { path = "pm" }
+            "#,
+        )
+        .file(
+            "src/lib.rs",
+            r#"
+                #[macro_use]
+                extern crate pm;
+                check!();
+            "#,
+        )
+        .file(
+            "pm/Cargo.toml",
+            r#"
+                [package]
+                name = "pm"
+                version = "0.0.1"
+                edition = "2015"
+
+                [lib]
+                proc-macro = true
+            "#,
+        )
+        .file(
+            "pm/src/lib.rs",
+            r#"
+                extern crate proc_macro;
+                use proc_macro::TokenStream;
+
+                // Like wrappers talking to their server, e.g. `sccache`.
+                #[proc_macro]
+                pub fn check(_input: TokenStream) -> TokenStream {
+                    std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+                    TokenStream::new()
+                }
+            "#,
+        )
+        .build();
+
+    p.cargo("check -Zbuild-sandbox")
+        .env("RUSTC_WRAPPER", tools::echo_wrapper())
+        .masquerade_as_nightly_cargo(&["build-sandbox"])
+        .with_stderr_contains(
+            "[WARNING] proc-macros are not sandboxed when they are compiled through a `rustc` \
+             wrapper (`build.rustc-wrapper` or `build.rustc-workspace-wrapper`); build scripts \
+             still are",
+        )
+        .run();
+}
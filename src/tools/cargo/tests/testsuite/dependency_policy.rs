@@ -0,0 +1,229 @@
+//! Tests for the `[policy]` config table, enabled with -Zdependency-policy.
+
+use cargo_test_support::prelude::*;
+use cargo_test_support::registry::Package;
+use cargo_test_support::{git, project, str, Project};
+
+fn foo_depending_on_bar() -> Project {
+    Package::new("baz", "1.0.0").publish();
+    Package::new("bar", "1.0.0").dep("baz", "1.0").publish();
+
+    project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.1.0"
+                edition = "2015"
+
+                [dependencies]
+                bar = "1.0"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build()
+}
+
+#[cargo_test]
+fn policy_requires_unstable_flag() {
+    let p = foo_depending_on_bar();
+    p.cargo("check")
+        .arg("--config")
+        .arg(r#"policy.deny=["baz"]"#)
+        .with_stderr_data(str![[r#"
+...
+[WARNING] ignoring `policy` config table without `-Zdependency-policy`
+...
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn policy_is_not_checked_by_tree() {
+    let p = foo_depending_on_bar();
+    p.cargo("tree -Zdependency-policy")
+        .arg("--config")
+        .arg(r#"policy.deny=["baz"]"#)
+        .masquerade_as_nightly_cargo(&["dependency-policy"])
+        .with_stdout_data(str![[r#"
+foo v0.1.0 ([ROOT]/foo)
+└── bar v1.0.0
+    └── baz v1.0.0
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn deny_shows_dependency_path() {
+    let p = foo_depending_on_bar();
+    p.cargo("check -Zdependency-policy")
+        .arg("--config")
+        .arg(r#"policy.deny=["baz@<1.0"]"#)
+        .masquerade_as_nightly_cargo(&["dependency-policy"])
+        .run();
+
+    p.cargo("check -Zdependency-policy")
+        .arg("--config")
+        .arg(r#"policy.deny=["baz@1"]"#)
+        .masquerade_as_nightly_cargo(&["dependency-policy"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] the dependencies don't comply with the `[policy]` config
+
+package `baz v1.0.0` is denied by the `policy.deny` entry `baz@1`
+package `baz v1.0.0`
+    ... which satisfies dependency `baz = "^1.0"` of package `bar v1.0.0`
+    ... which satisfies dependency `bar = "^1.0"` of package `foo v0.1.0 ([ROOT]/foo)`
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn allowed_licenses() {
+    Package::new("mit", "1.0.0")
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "mit"
+                version = "1.0.0"
+                edition = "2015"
+                license = "(MIT OR GPL-3.0) AND Apache-2.0 WITH LLVM-exception"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .publish();
+    Package::new("gpl", "1.0.0")
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "gpl"
+                version = "1.0.0"
+                edition = "2015"
+                license = "GPL-3.0-only"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .publish();
+    Package::new("unlicensed", "1.0.0").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.1.0"
+                edition = "2015"
+
+                [dependencies]
+                mit = "1.0"
+
+                [target.'cfg(any())'.dependencies]
+                gpl = "1.0"
+
+                [dev-dependencies]
+                unlicensed = "1.0"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file(
+            ".cargo/config.toml",
+            r#"
+                [policy]
+                allowed-licenses = ["MIT", "Apache-2.0"]
+            "#,
+        )
+        .build();
+
+    // `gpl` is only used on other platforms, and `unlicensed` by the tests.
+    p.cargo("build -Zdependency-policy")
+        .masquerade_as_nightly_cargo(&["dependency-policy"])
+        .run();
+
+    p.cargo("test --no-run -Zdependency-policy")
+        .masquerade_as_nightly_cargo(&["dependency-policy"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+...
+[ERROR] the dependencies don't comply with the `[policy]` config
+
+package `unlicensed v1.0.0` has no `license` field, so `policy.allowed-licenses` can't be checked
+package `unlicensed v1.0.0`
+    ... which satisfies dependency `unlicensed = "^1.0"` of package `foo v0.1.0 ([ROOT]/foo)`
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn sources_and_duplicate_versions() {
+    Package::new("dup", "1.0.0").publish();
+    Package::new("dup", "2.0.0").publish();
+    Package::new("bar", "1.0.0").dep("dup", "1.0").publish();
+    let git_dep = git::new("gitdep", |project| {
+        project
+            .file(
+                "Cargo.toml",
+                &cargo_test_support::basic_lib_manifest("gitdep"),
+            )
+            .file("src/lib.rs", "")
+    });
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            &format!(
+                r#"
+                    [package]
+                    name = "foo"
+                    version = "0.1.0"
+                    edition = "2015"
+
+                    [dependencies]
+                    bar = "1.0"
+                    dup = "2.0"
+                    gitdep = {{ git = "{}" }}
+                "#,
+                git_dep.url()
+            ),
+        )
+        .file("src/lib.rs", "")
+        .file(
+            ".cargo/config.toml",
+            r#"
+                [policy]
+                allow-git = false
+                allowed-registries = ["crates-io"]
+                max-versions = 1
+            "#,
+        )
+        .build();
+
+    p.cargo("check -Zdependency-policy")
+        .masquerade_as_nightly_cargo(&["dependency-policy"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+...
+[ERROR] the dependencies don't comply with the `[policy]` config
+
+package `gitdep v0.5.0 ([ROOTURL]/gitdep#[..])` comes from the git repository `[ROOTURL]/gitdep`, but `policy.allow-git` is false
+package `gitdep v0.5.0 ([ROOTURL]/gitdep#[..])`
+    ... which satisfies git dependency `gitdep` of package `foo v0.1.0 ([ROOT]/foo)`
+
+package `dup v1.0.0` is one of the versions v1.0.0, v2.0.0 of `dup`, more than the 1 allowed by `policy.max-versions`
+package `dup v1.0.0`
+    ... which satisfies dependency `dup = "^1.0"` of package `bar v1.0.0`
+    ... which satisfies dependency `bar = "^1.0"` of package `foo v0.1.0 ([ROOT]/foo)`
+
+package `dup v2.0.0` is one of the versions v1.0.0, v2.0.0 of `dup`, more than the 1 allowed by `policy.max-versions`
+package `dup v2.0.0`
+    ... which satisfies dependency `dup = "^2.0"` of package `foo v0.1.0 ([ROOT]/foo)`
+
+"#]])
+        .run();
+}
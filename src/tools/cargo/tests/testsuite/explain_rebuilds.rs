@@ -0,0 +1,113 @@
+//! Tests for `--explain-rebuilds`.
+
+use cargo_test_support::prelude::*;
+use cargo_test_support::{project, str};
+
+#[cargo_test]
+fn gated() {
+    let p = project().file("src/lib.rs", "").build();
+
+    p.cargo("check --explain-rebuilds")
+        .masquerade_as_nightly_cargo(&["explain-rebuilds"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] the `--explain-rebuilds` flag is unstable, pass `-Z unstable-options` to enable it
+See https://github.com/rust-lang/cargo/issues/2904 for more information about the `--explain-rebuilds` flag.
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn fresh_units_are_not_explained() {
+    let p = project().file("src/lib.rs", "").build();
+
+    p.cargo("check --explain-rebuilds -Zunstable-options")
+        .masquerade_as_nightly_cargo(&["explain-rebuilds"])
+        .with_stderr_data(str![[r#"
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+
+    p.cargo("check --explain-rebuilds -Zunstable-options")
+        .masquerade_as_nightly_cargo(&["explain-rebuilds"])
+        .with_stderr_data(str![[r#"
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn file_changed() {
+    let p = project()
+        .file("src/lib.rs", "")
+        .file("src/main.rs", "fn main() {}")
+        .build();
+
+    p.cargo("check").run();
+    p.change_file("src/lib.rs", "pub fn f() {}");
+
+    p.cargo("check --explain-rebuilds -Zunstable-options")
+        .masquerade_as_nightly_cargo(&["explain-rebuilds"])
+        .with_stderr_data(
+            str![[r#"
+[DIRTY] foo v0.0.1 ([ROOT]/foo) (lib): the file `src/lib.rs` has changed ([TIME_DIFF_AFTER_LAST_BUILD])
+[DIRTY] foo v0.0.1 ([ROOT]/foo) (bin "foo"): the dependency foo was rebuilt ([TIME_DIFF_AFTER_LAST_BUILD])
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]]
+            .unordered(),
+        )
+        .run();
+}
+
+#[cargo_test]
+fn env_var_changed_json() {
+    let p = project()
+        .file("src/lib.rs", "")
+        .file(
+            "build.rs",
+            r#"
+                fn main() {
+                    println!("cargo::rerun-if-env-changed=FOO");
+                }
+            "#,
+        )
+        .build();
+
+    p.cargo("check").run();
+
+    p.cargo("check --explain-rebuilds -Zunstable-options --message-format=json")
+        .masquerade_as_nightly_cargo(&["explain-rebuilds"])
+        .env("FOO", "1")
+        .with_stderr_data(str![[r#"
+[DIRTY] foo v0.0.1 ([ROOT]/foo) (build script run): the env variable FOO changed
+...
+"#]])
+        .with_stdout_contains(
+            r#"{"reason":"rebuild-reason",[..]"message":"the env variable FOO changed","dirty_reason":{"kind":"env-var-changed","name":"FOO","new":"1","old":null}}"#,
+        )
+        .run();
+}
+
+#[cargo_test]
+fn rustflags_changed() {
+    let p = project().file("src/lib.rs", "").build();
+
+    p.cargo("check").run();
+
+    p.cargo("check --explain-rebuilds -Zunstable-options")
+        .masquerade_as_nightly_cargo(&["explain-rebuilds"])
+        .env("RUSTFLAGS", "--cfg foo")
+        .with_stderr_data(str![[r#"
+[DIRTY] foo v0.0.1 ([ROOT]/foo) (lib): the rustflags changed
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
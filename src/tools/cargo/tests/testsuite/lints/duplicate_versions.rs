@@ -0,0 +1,82 @@
+use cargo_test_support::prelude::*;
+use cargo_test_support::project;
+use cargo_test_support::registry::Package;
+use cargo_test_support::str;
+
+#[cargo_test]
+fn warn() {
+    Package::new("dup", "1.0.0").publish();
+    Package::new("dup", "2.0.0").publish();
+    Package::new("bar", "1.0.0").dep("dup", "1.0").publish();
+    Package::new("baz", "1.0.0").dep("dup", "2.0").publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = "1.0"
+baz = "1.0"
+dup = "2.0"
+
+[lints.cargo]
+duplicate_versions = "warn"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+...
+[WARNING] multiple versions of `dup` are depended on: v1.0.0, v2.0.0
+  --> Cargo.toml:8:1
+   |
+ 8 | bar = "1.0"
+   | --- pulls in `dup v1.0.0`
+ 9 | baz = "1.0"
+   | --- pulls in `dup v2.0.0`
+10 | dup = "2.0"
+   | --- pulls in `dup v2.0.0`
+   |
+   = [NOTE] `cargo::duplicate_versions` is set to `warn` in `[lints]`
+...
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn single_version() {
+    Package::new("dup", "1.0.0").publish();
+    Package::new("dup", "1.1.0").publish();
+    Package::new("bar", "1.0.0").dep("dup", "1.0").publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = "1.0"
+dup = "1.1"
+
+[lints.cargo]
+duplicate_versions = "deny"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_does_not_contain("[..]multiple versions[..]")
+        .run();
+}
@@ -0,0 +1,73 @@
+use cargo_test_support::prelude::*;
+use cargo_test_support::project;
+use cargo_test_support::registry::Package;
+use cargo_test_support::str;
+
+#[cargo_test]
+fn warn() {
+    Package::new("dep", "1.0.0")
+        .feature("default", &["std"])
+        .feature("std", &[])
+        .publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[workspace]
+members = ["a", "b"]
+resolver = "2"
+"#,
+        )
+        .file(
+            "a/Cargo.toml",
+            r#"
+[package]
+name = "a"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+dep = { version = "1.0", default-features = false }
+
+[lints.cargo]
+ineffective_default_features = "warn"
+"#,
+        )
+        .file("a/src/lib.rs", "")
+        .file(
+            "b/Cargo.toml",
+            r#"
+[package]
+name = "b"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+dep = "1.0"
+"#,
+        )
+        .file("b/src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+...
+[WARNING] `default-features = false` has no effect, as the default features are enabled by another dependent
+ --> a/Cargo.toml:8:1
+  |
+8 | dep = { version = "1.0", default-features = false }
+  | ---
+  |
+  = [NOTE] `cargo::ineffective_default_features` is set to `warn` in `[lints]`
+  = [NOTE] the default features of `dep v1.0.0` are enabled by `b v0.1.0 ([ROOT]/foo/b)`
+...
+"#]])
+        .run();
+
+    // Without `b`, the default features of `dep` stay disabled.
+    p.cargo("check -p a -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_does_not_contain("[..]`default-features = false` has no effect[..]")
+        .run();
+}
@@ -3,10 +3,14 @@
 use cargo_test_support::registry::Package;
 use cargo_test_support::str;
 
+mod duplicate_versions;
 mod error;
 mod implicit_features;
+mod ineffective_default_features;
 mod inherited;
+mod uninherited_workspace_dependency;
 mod unknown_lints;
+mod unused_dependencies;
 mod unused_optional_dependencies;
 mod warning;
 
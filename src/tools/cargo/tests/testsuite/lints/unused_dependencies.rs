@@ -0,0 +1,177 @@
+use cargo_test_support::basic_manifest;
+use cargo_test_support::prelude::*;
+use cargo_test_support::project;
+use cargo_test_support::str;
+
+#[cargo_test(
+    nightly,
+    reason = "`--json=unused-externs` requires `-Zunstable-options`"
+)]
+fn warn() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { path = "bar" }
+baz = { path = "baz" }
+
+[dev-dependencies]
+qux = { path = "qux" }
+
+[lints.cargo]
+unused_dependencies = "warn"
+"#,
+        )
+        .file("src/lib.rs", "pub fn f() { bar::f() }")
+        .file("bar/Cargo.toml", &basic_manifest("bar", "0.1.0"))
+        .file("bar/src/lib.rs", "pub fn f() {}")
+        .file("baz/Cargo.toml", &basic_manifest("baz", "0.1.0"))
+        .file("baz/src/lib.rs", "")
+        .file("qux/Cargo.toml", &basic_manifest("qux", "0.1.0"))
+        .file("qux/src/lib.rs", "")
+        .build();
+
+    // The dev-dependencies are only reported once the tests are built.
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(
+            str![[r#"
+[LOCKING] 4 packages to latest compatible versions
+[CHECKING] bar v0.1.0 ([ROOT]/foo/bar)
+[CHECKING] baz v0.1.0 ([ROOT]/foo/baz)
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[WARNING] unused dependency
+ --> Cargo.toml:9:1
+  |
+9 | baz = { path = "baz" }
+  | ---
+  |
+  = [NOTE] `cargo::unused_dependencies` is set to `warn` in `[lints]`
+  = [HELP] remove the dependency, or add `use baz as _;` if it is only needed for linking
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]]
+            .unordered(),
+        )
+        .run();
+
+    p.cargo("check --all-targets -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[CHECKING] qux v0.1.0 ([ROOT]/foo/qux)
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[WARNING] unused dependency
+ --> Cargo.toml:9:1
+  |
+9 | baz = { path = "baz" }
+  | ---
+  |
+  = [NOTE] `cargo::unused_dependencies` is set to `warn` in `[lints]`
+  = [HELP] remove the dependency, or add `use baz as _;` if it is only needed for linking
+[WARNING] unused dependency
+  --> Cargo.toml:12:1
+   |
+12 | qux = { path = "qux" }
+   | ---
+   |
+   = [HELP] remove the dependency, or add `use qux as _;` if it is only needed for linking
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test(
+    nightly,
+    reason = "`--json=unused-externs` requires `-Zunstable-options`"
+)]
+fn deny() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { path = "bar" }
+
+[lints.cargo]
+unused_dependencies = "deny"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .file("bar/Cargo.toml", &basic_manifest("bar", "0.1.0"))
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[LOCKING] 2 packages to latest compatible versions
+[CHECKING] bar v0.1.0 ([ROOT]/foo/bar)
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[ERROR] unused dependency
+ --> Cargo.toml:8:1
+  |
+8 | bar = { path = "bar" }
+  | ^^^
+  |
+  = [NOTE] `cargo::unused_dependencies` is set to `deny` in `[lints]`
+  = [HELP] remove the dependency, or add `use bar as _;` if it is only needed for linking
+
+"#]])
+        .run();
+}
+
+#[cargo_test(
+    nightly,
+    reason = "`--json=unused-externs` requires `-Zunstable-options`"
+)]
+fn rust_lint_deny() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { path = "bar" }
+
+[lints.rust]
+unused_crate_dependencies = "deny"
+
+[lints.cargo]
+unused_dependencies = "warn"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .file("bar/Cargo.toml", &basic_manifest("bar", "0.1.0"))
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[LOCKING] 2 packages to latest compatible versions
+[CHECKING] bar v0.1.0 ([ROOT]/foo/bar)
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[ERROR] external crate `bar` unused in `foo`: remove the dependency or add `use bar as _;`
+[ERROR] could not compile `foo` (lib) due to 1 previous error
+
+"#]])
+        .run();
+}
@@ -4,6 +4,7 @@
 
 mod advanced_env;
 mod alt_registry;
+mod artifact_cache;
 mod artifact_dep;
 mod artifact_dir;
 mod bad_config;
@@ -12,6 +13,7 @@
 mod binary_name;
 mod build;
 mod build_plan;
+mod build_sandbox;
 mod build_script;
 mod build_script_env;
 mod build_script_extra_link_arg;
@@ -78,6 +80,7 @@
 mod custom_target;
 mod death;
 mod dep_info;
+mod dependency_policy;
 mod diagnostics;
 mod direct_minimal_versions;
 mod directory;
@@ -85,6 +88,7 @@
 mod docscrape;
 mod edition;
 mod error;
+mod explain_rebuilds;
 mod features;
 mod features2;
 mod features_namespaced;
@@ -132,6 +136,7 @@
 mod owner;
 mod package;
 mod package_features;
+mod parallel_tests;
 mod patch;
 mod path;
 mod paths;
@@ -154,6 +159,7 @@
 mod registry_overlay;
 mod rename_deps;
 mod replace;
+mod report_outdated;
 mod required_features;
 mod run;
 mod rust_version;
@@ -164,6 +170,7 @@
 mod rustdocflags;
 mod rustflags;
 mod rustup;
+mod sbom;
 mod script;
 mod search;
 mod shell_quoting;
@@ -0,0 +1,271 @@
+//! Tests for -Zparallel-tests and -Ztest-report.
+
+use cargo_test_support::compare::assert_e2e;
+use cargo_test_support::prelude::*;
+use cargo_test_support::{project, str, Project};
+
+fn tests_project() -> Project {
+    project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.1.0"
+                edition = "2015"
+            "#,
+        )
+        .file("src/lib.rs", "#[test] fn unit() {}")
+        .file("tests/a.rs", "#[test] fn a() {}")
+        .file(
+            "tests/b.rs",
+            "#[test] fn b() {} #[test] #[ignore] fn b_ignored() {}",
+        )
+        .file("tests/c.rs", r#"#[test] fn c() { println!("from c"); }"#)
+        .build()
+}
+
+#[cargo_test]
+fn parallel_tests_requires_a_number() {
+    let p = tests_project();
+    p.cargo("test -Zparallel-tests")
+        .masquerade_as_nightly_cargo(&["parallel-tests"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] flag -Zparallel-tests expects a positive number of test binaries, e.g. `-Zparallel-tests=4`
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn runs_test_binaries_concurrently() {
+    let p = tests_project();
+    p.cargo("test --lib --tests -Zparallel-tests=4")
+        .masquerade_as_nightly_cargo(&["parallel-tests"])
+        .with_stdout_contains("test c ... ok")
+        .with_stderr_contains("[RUNNING] unittests src/lib.rs (target/debug/deps/foo-[HASH][EXE])")
+        .with_stderr_contains("[RUNNING] tests/a.rs (target/debug/deps/a-[HASH][EXE])")
+        .with_stderr_contains("[RUNNING] tests/b.rs (target/debug/deps/b-[HASH][EXE])")
+        .with_stderr_contains("[RUNNING] tests/c.rs (target/debug/deps/c-[HASH][EXE])")
+        .with_stderr_contains(
+            "     Summary 4 test binaries run in [..]s: 4 passed; 0 failed; 1 ignored; \
+             0 measured",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn stops_starting_binaries_after_a_failure() {
+    let p = project()
+        .file("src/lib.rs", "")
+        .file("tests/a.rs", "#[test] fn a() { panic!(\"failed\") }")
+        .file("tests/b.rs", "#[test] fn b() {}")
+        .build();
+
+    // With a single job, the binaries run in order, and `b` is never started.
+    p.cargo("test --tests -Zparallel-tests=1")
+        .masquerade_as_nightly_cargo(&["parallel-tests"])
+        .with_status(101)
+        .with_stderr_contains("[ERROR] test failed, to rerun pass `--test a`")
+        .with_stderr_does_not_contain("[RUNNING] tests/b.rs [..]")
+        .with_stderr_contains(
+            "     Summary 1 test binaries run in [..]s: 0 passed; 1 failed; 0 ignored; \
+             0 measured",
+        )
+        .run();
+
+    p.cargo("test --tests --no-fail-fast -Zparallel-tests=1")
+        .masquerade_as_nightly_cargo(&["parallel-tests"])
+        .with_status(101)
+        .with_stderr_contains("[RUNNING] tests/b.rs [..]")
+        .with_stderr_contains(
+            "     Summary 2 test binaries run in [..]s: 1 passed; 1 failed; 0 ignored; \
+             0 measured",
+        )
+        .with_stderr_contains("[ERROR] test failed, to rerun pass `--test a`")
+        .run();
+}
+
+#[cargo_test]
+fn writes_json_and_junit_reports() {
+    let p = tests_project();
+    p.cargo("test --test b --test c -Ztest-report")
+        .masquerade_as_nightly_cargo(&["test-report"])
+        .with_stderr_contains("      Report saved to [ROOT]/foo/target/cargo-test-report")
+        .run();
+
+    let dir = p.root().join("target/cargo-test-report");
+    assert_e2e().eq(
+        std::fs::read_to_string(dir.join("report.json")).unwrap(),
+        str![[r#"
+{
+  "binaries": [
+    {
+      "duration": "{...}",
+      "executable": "tests/b.rs (target/debug/deps/b-[HASH][EXE])",
+      "failed": 0,
+      "ignored": 1,
+      "measured": 0,
+      "package": "foo",
+      "passed": 1,
+      "stderr": "",
+      "stdout": "{...}",
+      "success": true,
+      "target": "b",
+      "tests": [
+        {
+          "name": "b",
+          "outcome": "ok"
+        },
+        {
+          "name": "b_ignored",
+          "outcome": "ignored"
+        }
+      ]
+    },
+    {
+      "duration": "{...}",
+      "executable": "tests/c.rs (target/debug/deps/c-[HASH][EXE])",
+      "failed": 0,
+      "ignored": 0,
+      "measured": 0,
+      "package": "foo",
+      "passed": 1,
+      "stderr": "",
+      "stdout": "{...}",
+      "success": true,
+      "target": "c",
+      "tests": [
+        {
+          "name": "c",
+          "outcome": "ok"
+        }
+      ]
+    }
+  ],
+  "duration": "{...}",
+  "failed": 0,
+  "filtered_out": 0,
+  "ignored": 1,
+  "measured": 0,
+  "passed": 2
+}
+"#]]
+        .is_json(),
+    );
+
+    let junit = std::fs::read_to_string(dir.join("report.xml")).unwrap();
+    assert!(junit.contains(r#"<testsuites name="cargo test" tests="3" failures="0" skipped="1""#));
+    assert!(junit.contains(r#"<testcase name="b_ignored" classname="b"><skipped/></testcase>"#));
+    assert!(junit.contains(r#"<testcase name="c" classname="c"/>"#));
+    assert!(junit.contains("from c"));
+}
+
+#[cargo_test]
+fn results_are_not_read_from_the_output() {
+    // Neither quiet nor JSON output, nor output looking like libtest's, change
+    // the results.
+    let p = project()
+        .file("src/lib.rs", "")
+        .file(
+            "tests/a.rs",
+            r#"#[test] fn a() { println!("test fake ... FAILED"); }"#,
+        )
+        .build();
+
+    p.cargo("test --test a -Zparallel-tests=2 -- -q --nocapture")
+        .masquerade_as_nightly_cargo(&["parallel-tests"])
+        .with_stderr_contains(
+            "     Summary 1 test binaries run in [..]s: 1 passed; 0 failed; 0 ignored; \
+             0 measured",
+        )
+        .run();
+
+    p.cargo("test --test a -Zparallel-tests=2 -- --nocapture -Zunstable-options --format json")
+        .env("RUSTC_BOOTSTRAP", "1")
+        .masquerade_as_nightly_cargo(&["parallel-tests"])
+        .with_stderr_contains(
+            "     Summary 1 test binaries run in [..]s: 1 passed; 0 failed; 0 ignored; \
+             0 measured",
+        )
+        .run();
+}
+
+#[cargo_test]
+fn no_results_without_harness() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.1.0"
+                edition = "2015"
+
+                [[test]]
+                name = "custom"
+                harness = false
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file("tests/a.rs", "#[test] fn a() {}")
+        .file(
+            "tests/custom.rs",
+            r#"fn main() { println!("test result: ok. 5 passed; 0 failed"); }"#,
+        )
+        .build();
+
+    p.cargo("test --tests -Zparallel-tests=2 -Ztest-report")
+        .masquerade_as_nightly_cargo(&["parallel-tests", "test-report"])
+        .with_stderr_contains(
+            "     Summary 2 test binaries run in [..]s: 1 passed; 0 failed; 0 ignored; \
+             0 measured; no results for 1 of them",
+        )
+        .run();
+
+    let report = p.read_file("target/cargo-test-report/report.json");
+    let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+    let custom = &report["binaries"][1];
+    assert_eq!(custom["target"], "custom");
+    assert!(custom.get("passed").is_none());
+    assert!(custom["tests"].is_null());
+}
+
+#[cargo_test]
+fn junit_failures_have_a_message_and_output() {
+    let p = project()
+        .file("src/lib.rs", "")
+        .file(
+            "tests/a.rs",
+            r#"
+                #[test]
+                fn a() {
+                    println!("about to fail \x1b[31m<here>\x1b[0m");
+                    panic!("oh no");
+                }
+
+                #[test]
+                #[should_panic(expected = "something")]
+                fn b() {
+                    panic!("something else");
+                }
+            "#,
+        )
+        .build();
+
+    p.cargo("test --test a -Ztest-report")
+        .masquerade_as_nightly_cargo(&["test-report"])
+        .with_status(101)
+        .run();
+
+    let junit = p.read_file("target/cargo-test-report/report.xml");
+    assert!(junit.contains(
+        r#"<testcase name="a" classname="a"><failure message="test failed">about to fail [31m&lt;here&gt;[0m"#
+    ));
+    assert!(junit.contains("oh no"));
+    assert!(junit.contains(
+        r#"<testcase name="b" classname="a"><failure message="panic did not contain expected string"#
+    ));
+    assert!(!junit.contains('\x1b'));
+}
@@ -3417,3 +3417,209 @@ fn unused_deps_edition_2024() {
 
     validate_upload_foo();
 }
+
+fn workspace_with_local_deps_project() -> cargo_test_support::Project {
+    project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [workspace]
+                members = ["a", "b", "tools"]
+            "#,
+        )
+        .file(
+            "a/Cargo.toml",
+            r#"
+                [package]
+                name = "a"
+                version = "0.0.1"
+                edition = "2015"
+                license = "MIT"
+                description = "a"
+                repository = "a"
+
+                [dependencies]
+                b = { path = "../b", version = "0.0.1" }
+            "#,
+        )
+        .file("a/src/lib.rs", "pub use b::f;")
+        .file(
+            "b/Cargo.toml",
+            r#"
+                [package]
+                name = "b"
+                version = "0.0.1"
+                edition = "2015"
+                license = "MIT"
+                description = "b"
+                repository = "b"
+            "#,
+        )
+        .file("b/src/lib.rs", "pub fn f() {}")
+        .file(
+            "tools/Cargo.toml",
+            r#"
+                [package]
+                name = "tools"
+                version = "0.0.1"
+                edition = "2015"
+                publish = false
+
+                [dependencies]
+                a = { path = "../a" }
+            "#,
+        )
+        .file("tools/src/main.rs", "fn main() {}")
+        .build()
+}
+
+#[cargo_test]
+fn workspace_requires_unstable_flag() {
+    let registry = registry::init();
+    let p = workspace_with_local_deps_project();
+
+    p.cargo("publish --workspace --dry-run")
+        .masquerade_as_nightly_cargo(&["package-workspace"])
+        .replace_crates_io(registry.index_url())
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] the `--workspace` flag is unstable, pass `-Z package-workspace` to enable it
+See https://github.com/rust-lang/cargo/issues/10948 for more information about the `--workspace` flag.
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn workspace_dry_run_in_dependency_order() {
+    let registry = registry::init();
+    let p = workspace_with_local_deps_project();
+
+    p.cargo("publish -Zpackage-workspace --workspace --dry-run")
+        .masquerade_as_nightly_cargo(&["package-workspace"])
+        .replace_crates_io(registry.index_url())
+        .with_stderr_data(str![[r#"
+[NOTE] skipping `tools`, as `package.publish` is set to `false`
+[UPDATING] crates.io index
+[PACKAGING] b v0.0.1 ([ROOT]/foo/b)
+...
+[PACKAGING] a v0.0.1 ([ROOT]/foo/a)
+...
+[VERIFYING] a v0.0.1 ([ROOT]/foo/a)
+[UNPACKING] b v0.0.1 (registry `[ROOT]/foo/target/package/tmp-registry`)
+...
+[UPLOADING] b v0.0.1 ([ROOT]/foo/b)
+[WARNING] aborting upload due to dry run
+[UPLOADING] a v0.0.1 ([ROOT]/foo/a)
+[WARNING] aborting upload due to dry run
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn workspace_publishes_in_dependency_order() {
+    let registry = RegistryBuilder::new().http_api().http_index().build();
+    let p = workspace_with_local_deps_project();
+
+    p.cargo("publish -Zpackage-workspace --workspace --no-verify")
+        .masquerade_as_nightly_cargo(&["package-workspace"])
+        .replace_crates_io(registry.index_url())
+        .with_stderr_data(str![[r#"
+...
+[UPLOADING] b v0.0.1 ([ROOT]/foo/b)
+[UPLOADED] b v0.0.1 to registry `crates-io`
+[NOTE] waiting for `b v0.0.1` to be available at registry `crates-io`.
+You may press ctrl-c to skip waiting; the crate should be available shortly.
+[PUBLISHED] b v0.0.1 at registry `crates-io`
+[UPLOADING] a v0.0.1 ([ROOT]/foo/a)
+[UPLOADED] a v0.0.1 to registry `crates-io`
+[NOTE] waiting for `a v0.0.1` to be available at registry `crates-io`.
+You may press ctrl-c to skip waiting; the crate should be available shortly.
+[PUBLISHED] a v0.0.1 at registry `crates-io`
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn workspace_exclude() {
+    let registry = registry::init();
+    let p = workspace_with_local_deps_project();
+
+    p.cargo("publish -Zpackage-workspace --workspace --exclude a --exclude tools --dry-run")
+        .masquerade_as_nightly_cargo(&["package-workspace"])
+        .replace_crates_io(registry.index_url())
+        .with_stderr_does_not_contain("[UPLOADING] a v0.0.1 [..]")
+        .with_stderr_contains("[UPLOADING] b v0.0.1 ([ROOT]/foo/b)")
+        .run();
+}
+
+#[cargo_test]
+fn workspace_partial_publish_tells_how_to_resume() {
+    // The upload of `b` goes through, but not the one of `a`.
+    let uploads = Arc::new(Mutex::new(0));
+    let registry = RegistryBuilder::new()
+        .http_api()
+        .http_index()
+        .add_responder("/api/v1/crates/new", move |req, server| {
+            let mut uploads = uploads.lock().unwrap();
+            *uploads += 1;
+            if *uploads == 1 {
+                server.check_authorized_publish(req)
+            } else {
+                Response {
+                    body: br#"{"errors": [{"detail": "service unavailable"}]}"#.to_vec(),
+                    code: 503,
+                    headers: vec![],
+                }
+            }
+        })
+        .build();
+    let p = workspace_with_local_deps_project();
+
+    p.cargo("publish -Zpackage-workspace --workspace --no-verify")
+        .masquerade_as_nightly_cargo(&["package-workspace"])
+        .replace_crates_io(registry.index_url())
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+...
+[PUBLISHED] b v0.0.1 at registry `crates-io`
+[UPLOADING] a v0.0.1 ([ROOT]/foo/a)
+[NOTE] `b v0.0.1` was already published; to publish the remaining packages, run `cargo publish -Zpackage-workspace -p a@0.0.1`
+[ERROR] failed to publish to registry at http://127.0.0.1:[..]/
+
+Caused by:
+...
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn workspace_timeout_waiting_for_last_packages() {
+    let registry = RegistryBuilder::new()
+        .http_api()
+        .delayed_index_update(20)
+        .build();
+    let p = workspace_with_local_deps_project();
+    p.change_file(
+        ".cargo/config.toml",
+        r#"
+            [publish]
+            timeout = 2
+        "#,
+    );
+
+    p.cargo("publish -Zpackage-workspace -Zpublish-timeout -p b --no-verify")
+        .masquerade_as_nightly_cargo(&["package-workspace", "publish-timeout"])
+        .replace_crates_io(registry.index_url())
+        .with_stderr_data(str![[r#"
+...
+[WARNING] timed out waiting for `b v0.0.1` to be available in registry `crates-io`
+[NOTE] the registry may have a backlog that is delaying making the crate available. The crate should be available soon.
+[WARNING] all the packages were uploaded, but waiting for `b v0.0.1` to be available in the registry timed out
+
+"#]])
+        .run();
+}
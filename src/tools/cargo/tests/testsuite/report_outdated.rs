@@ -0,0 +1,215 @@
+//! Tests for `cargo report outdated`.
+
+use std::fs;
+
+use cargo_test_support::paths;
+use cargo_test_support::prelude::*;
+use cargo_test_support::registry::Package;
+use cargo_test_support::{basic_manifest, project, str, t, Project};
+
+fn outdated_project() -> Project {
+    Package::new("bar", "0.1.0").publish();
+    Package::new("baz", "1.0.0").publish();
+    Package::new("qux", "0.3.0").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+
+                [dependencies]
+                bar = "0.1.0"
+                baz = "1.0.0"
+
+                [dev-dependencies]
+                qux = "0.3.0"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+    p.cargo("generate-lockfile").run();
+
+    Package::new("bar", "0.1.1").publish();
+    Package::new("bar", "0.2.0").publish();
+    Package::new("bar", "0.3.0-alpha.1").publish();
+    Package::new("baz", "1.1.0").yanked(true).publish();
+    Package::new("qux", "0.4.0").publish();
+    p
+}
+
+#[cargo_test]
+fn gated() {
+    let p = project().file("src/lib.rs", "").build();
+
+    p.cargo("report outdated")
+        .masquerade_as_nightly_cargo(&["report outdated"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] the `cargo report outdated` command is unstable, pass `-Z unstable-options` to enable it
+See https://github.com/rust-lang/cargo/issues/4309 for more information about the `cargo report outdated` command.
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn human() {
+    let p = outdated_project();
+    let lockfile = p.read_lockfile();
+
+    p.cargo("report outdated -Zunstable-options")
+        .masquerade_as_nightly_cargo(&["report outdated"])
+        .with_stdout_data(str![[r#"
+foo v0.0.1 ([ROOT]/foo)
+  Name  Requirement  Locked  Compatible  Latest  Kind
+  bar   ^0.1.0       0.1.0   0.1.1       0.2.0   dependencies
+  qux   ^0.3.0       0.3.0   -           0.4.0   dev-dependencies
+
+"#]])
+        .run();
+
+    assert_eq!(p.read_lockfile(), lockfile);
+}
+
+#[cargo_test]
+fn up_to_date() {
+    Package::new("bar", "0.1.0").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+
+                [dependencies]
+                bar = "0.1.0"
+                path-dep = { path = "path-dep" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .file("path-dep/Cargo.toml", &basic_manifest("path-dep", "0.1.0"))
+        .file("path-dep/src/lib.rs", "")
+        .build();
+
+    p.cargo("report outdated -Zunstable-options")
+        .masquerade_as_nightly_cargo(&["report outdated"])
+        .with_stdout_data("")
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] [..] packages to latest compatible versions
+[NOTE] all direct registry dependencies are up to date
+
+"#]])
+        .run();
+    assert!(!p.root().join("Cargo.lock").exists());
+}
+
+#[cargo_test]
+fn json_offline_local_registry() {
+    let root = paths::root();
+    t!(fs::create_dir(&root.join(".cargo")));
+    t!(fs::write(
+        root.join(".cargo/config.toml"),
+        r#"
+            [source.crates-io]
+            registry = 'https://wut'
+            replace-with = 'my-awesome-local-registry'
+
+            [source.my-awesome-local-registry]
+            local-registry = 'registry'
+        "#
+    ));
+    Package::new("bar", "0.1.0").local(true).publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+
+                [build-dependencies]
+                renamed = { package = "bar", version = "0.1.0" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+    p.cargo("generate-lockfile").run();
+
+    Package::new("bar", "0.1.5").local(true).publish();
+    Package::new("bar", "1.0.0").local(true).publish();
+
+    p.cargo("report outdated -Zunstable-options --offline --format json")
+        .masquerade_as_nightly_cargo(&["report outdated"])
+        .with_stdout_data(
+            str![[r#"
+[
+  {
+    "dependencies": [
+      {
+        "compatible": "0.1.5",
+        "kind": "build",
+        "latest": "1.0.0",
+        "locked": "0.1.0",
+        "name": "renamed",
+        "package": "bar",
+        "req": "^0.1.0",
+        "source": "registry+[..]",
+        "target": null
+      }
+    ],
+    "package_id": "path+[ROOTURL]/foo#0.0.1"
+  }
+]
+"#]]
+            .is_json(),
+        )
+        .run();
+}
+
+#[cargo_test]
+fn several_versions_of_a_package() {
+    Package::new("bar", "0.1.0").publish();
+    Package::new("bar", "0.2.0").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+
+                [dependencies]
+                bar = "0.1.0"
+                bar2 = { package = "bar", version = "0.2.0" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+    p.cargo("generate-lockfile").run();
+
+    Package::new("bar", "0.1.1").publish();
+    Package::new("bar", "0.2.1").publish();
+
+    p.cargo("report outdated -Zunstable-options")
+        .masquerade_as_nightly_cargo(&["report outdated"])
+        .with_stdout_data(str![[r#"
+foo v0.0.1 ([ROOT]/foo)
+  Name  Requirement  Locked  Compatible  Latest  Kind
+  bar   ^0.1.0       0.1.0   0.1.1       0.2.1   dependencies
+  bar2  ^0.2.0       0.2.0   0.2.1       0.2.1   dependencies
+
+"#]])
+        .run();
+}
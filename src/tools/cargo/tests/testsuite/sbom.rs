@@ -0,0 +1,192 @@
+//! Tests for the `cargo build --sbom` flag.
+
+use cargo_test_support::compare::assert_e2e;
+use cargo_test_support::prelude::*;
+use cargo_test_support::registry::Package;
+use cargo_test_support::str;
+use cargo_test_support::{basic_manifest, project};
+
+#[cargo_test]
+fn sbom_requires_unstable_options() {
+    let p = project()
+        .file("Cargo.toml", &basic_manifest("foo", "0.1.0"))
+        .file("src/main.rs", "fn main() {}")
+        .build();
+
+    p.cargo("build --sbom")
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] the `--sbom` flag is unstable, and only available on the nightly channel of Cargo, but this is the `stable` channel
+See https://doc.rust-lang.org/book/appendix-07-nightly-rust.html for more information about Rust release channels.
+See https://github.com/rust-lang/cargo/issues/13709 for more information about the `--sbom` flag.
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn sbom_lists_linked_packages() {
+    Package::new("baz", "1.0.0")
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "baz"
+                version = "1.0.0"
+                edition = "2015"
+                license = "MIT OR Apache-2.0"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .publish();
+    Package::new("bar", "1.0.0")
+        .dep("baz", "1.0")
+        .feature("feat", &[])
+        .file("src/lib.rs", "")
+        .publish();
+    Package::new("builder", "1.0.0")
+        .file("src/lib.rs", "")
+        .publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.1.0"
+                edition = "2015"
+
+                [dependencies]
+                bar = { version = "1.0", features = ["feat"] }
+
+                [build-dependencies]
+                builder = "1.0"
+            "#,
+        )
+        .file("build.rs", "fn main() {}")
+        .file("src/main.rs", "fn main() {}")
+        .build();
+
+    p.cargo("build --sbom -Zunstable-options")
+        .masquerade_as_nightly_cargo(&["sbom"])
+        .run();
+
+    let sbom = p.root().join(format!(
+        "target/debug/foo{}.cdx.json",
+        std::env::consts::EXE_SUFFIX
+    ));
+    assert_e2e().eq(
+        std::fs::read_to_string(sbom).unwrap(),
+        str![[r#"
+{
+  "bomFormat": "CycloneDX",
+  "specVersion": "1.5",
+  "version": 1,
+  "metadata": {
+    "tools": {
+      "components": [
+        {
+          "type": "application",
+          "name": "cargo",
+          "version": "[..]"
+        }
+      ]
+    },
+    "component": {
+      "type": "application",
+      "bom-ref": "foo@0.1.0-[..]",
+      "name": "foo",
+      "version": "0.1.0",
+      "properties": [
+        {
+          "name": "cargo:source",
+          "value": "path+[ROOTURL]/foo"
+        },
+        {
+          "name": "cargo:target",
+          "value": "[HOST_TARGET]"
+        }
+      ]
+    }
+  },
+  "components": [
+    {
+      "type": "library",
+      "bom-ref": "bar@1.0.0-[..]",
+      "name": "bar",
+      "version": "1.0.0",
+      "purl": "pkg:cargo/bar@1.0.0",
+      "hashes": [
+        {
+          "alg": "SHA-256",
+          "content": "[..]"
+        }
+      ],
+      "properties": [
+        {
+          "name": "cargo:source",
+          "value": "registry+https://github.com/rust-lang/crates.io-index"
+        },
+        {
+          "name": "cargo:target",
+          "value": "[HOST_TARGET]"
+        },
+        {
+          "name": "cargo:features",
+          "value": "feat"
+        }
+      ]
+    },
+    {
+      "type": "library",
+      "bom-ref": "baz@1.0.0-[..]",
+      "name": "baz",
+      "version": "1.0.0",
+      "purl": "pkg:cargo/baz@1.0.0",
+      "hashes": [
+        {
+          "alg": "SHA-256",
+          "content": "[..]"
+        }
+      ],
+      "licenses": [
+        {
+          "expression": "MIT OR Apache-2.0"
+        }
+      ],
+      "properties": [
+        {
+          "name": "cargo:source",
+          "value": "registry+https://github.com/rust-lang/crates.io-index"
+        },
+        {
+          "name": "cargo:target",
+          "value": "[HOST_TARGET]"
+        }
+      ]
+    }
+  ],
+  "dependencies": [
+    {
+      "ref": "bar@1.0.0-[..]",
+      "dependsOn": [
+        "baz@1.0.0-[..]"
+      ]
+    },
+    {
+      "ref": "baz@1.0.0-[..]",
+      "dependsOn": []
+    },
+    {
+      "ref": "foo@0.1.0-[..]",
+      "dependsOn": [
+        "bar@1.0.0-[..]"
+      ]
+    }
+  ]
+}
+"#]]
+        .is_json(),
+    );
+}
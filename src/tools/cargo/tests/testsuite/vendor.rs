@@ -7,11 +7,12 @@
 use std::fs;
 
 use cargo_test_support::compare::assert_e2e;
+use cargo_test_support::cross_compile::{self, alternate};
 use cargo_test_support::git;
 use cargo_test_support::prelude::*;
 use cargo_test_support::registry::{self, Package, RegistryBuilder};
 use cargo_test_support::str;
-use cargo_test_support::{basic_lib_manifest, basic_manifest, paths, project, Project};
+use cargo_test_support::{basic_lib_manifest, basic_manifest, paths, project, rustc_host, Project};
 
 #[cargo_test]
 fn vendor_simple() {
@@ -1884,3 +1885,165 @@ fn vendor_crate_with_ws_inherit() {
 "#]])
         .run();
 }
+
+#[cargo_test]
+fn vendor_platform_stubs_unused_packages() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.1.0"
+
+                [dependencies]
+                log = "0.3.5"
+
+                [target.'cfg(foo_os)'.dependencies]
+                foo-sys = "0.1.0"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    Package::new("log", "0.3.5").publish();
+    Package::new("foo-sys-dep", "0.1.0").publish();
+    Package::new("foo-sys", "0.1.0")
+        .dep("foo-sys-dep", "0.1.0")
+        .file("build.rs", "fn main() {}")
+        .file("src/lib.rs", "pub fn foo() {}")
+        .publish();
+
+    p.cargo(&format!(
+        "vendor --respect-source-config --platform {}",
+        rustc_host()
+    ))
+    .with_stderr_data(
+        str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 3 packages to latest compatible versions
+[DOWNLOADING] crates ...
+[DOWNLOADED] foo-sys v0.1.0 (registry `dummy-registry`)
+[DOWNLOADED] foo-sys-dep v0.1.0 (registry `dummy-registry`)
+[DOWNLOADED] log v0.3.5 (registry `dummy-registry`)
+[STUBBING] foo-sys v0.1.0 (unused on the vendored platforms) to [ROOT]/foo/vendor/foo-sys
+[STUBBING] foo-sys-dep v0.1.0 (unused on the vendored platforms) to [ROOT]/foo/vendor/foo-sys-dep
+[VENDORING] log v0.3.5 ([ROOT]/home/.cargo/registry/src/-[HASH]/log-0.3.5) to [ROOT]/foo/vendor/log
+To use vendored sources, add this to your .cargo/config.toml for this project:
+
+
+"#]]
+        .unordered(),
+    )
+    .run();
+
+    let stub = p.read_file("vendor/foo-sys/Cargo.toml");
+    assert_e2e().eq(
+        stub,
+        str![[r#"
+...
+[package]
+...
+build = false
+...
+[dependencies.foo-sys-dep]
+version = "0.1.0"
+...
+"#]],
+    );
+    assert!(!p.root().join("vendor/foo-sys/build.rs").exists());
+    let lib = p.read_file("vendor/foo-sys/src/lib.rs");
+    assert_e2e().eq(
+        lib,
+        str![[r#"
+// This package is only used on platforms which weren't vendored.
+compile_error!("`foo-sys v0.1.0` was not vendored, as it isn't used on the platforms passed to `cargo vendor --platform`");
+
+"#]],
+    );
+
+    add_crates_io_vendor_config(&p);
+    p.cargo("check --locked").run();
+
+    // Vendoring again for every platform replaces the stubs with the full sources.
+    p.cargo("vendor --respect-source-config").run();
+    let lib = p.read_file("vendor/foo-sys/src/lib.rs");
+    assert_eq!(lib, "pub fn foo() {}");
+}
+
+#[cargo_test]
+fn vendor_platform_keeps_host_dependencies() {
+    // Build dependencies and proc-macros are compiled for the host, so their
+    // dependencies must be vendored even if the host isn't passed to `--platform`.
+    if cross_compile::disabled() {
+        return;
+    }
+    Package::new("build-helper", "0.1.0").publish();
+    Package::new("builder", "0.1.0")
+        .target_dep("build-helper", "0.1.0", rustc_host())
+        .publish();
+    Package::new("host-builder", "0.1.0").publish();
+    Package::new("pm-helper", "0.1.0").publish();
+    Package::new("pm", "0.1.0")
+        .proc_macro(true)
+        .target_dep("pm-helper", "0.1.0", rustc_host())
+        .publish();
+    Package::new("host-only", "0.1.0").publish();
+
+    let p = project()
+        .file(
+            "Cargo.toml",
+            &format!(
+                r#"
+                    [package]
+                    name = "foo"
+                    version = "0.1.0"
+
+                    [dependencies]
+                    pm = "0.1.0"
+
+                    [build-dependencies]
+                    builder = "0.1.0"
+
+                    [target.'{host}'.build-dependencies]
+                    host-builder = "0.1.0"
+
+                    [target.'{host}'.dependencies]
+                    host-only = "0.1.0"
+                "#,
+                host = rustc_host(),
+            ),
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    // Normal dependencies are still checked against the requested platforms.
+    p.cargo(&format!(
+        "vendor --respect-source-config --platform {}",
+        alternate()
+    ))
+    .with_stderr_data(
+        str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 6 packages to latest compatible versions
+[DOWNLOADING] crates ...
+[DOWNLOADED] build-helper v0.1.0 (registry `dummy-registry`)
+[DOWNLOADED] builder v0.1.0 (registry `dummy-registry`)
+[DOWNLOADED] host-builder v0.1.0 (registry `dummy-registry`)
+[DOWNLOADED] host-only v0.1.0 (registry `dummy-registry`)
+[DOWNLOADED] pm v0.1.0 (registry `dummy-registry`)
+[DOWNLOADED] pm-helper v0.1.0 (registry `dummy-registry`)
+[VENDORING] build-helper v0.1.0 ([ROOT]/home/.cargo/registry/src/-[HASH]/build-helper-0.1.0) to [ROOT]/foo/vendor/build-helper
+[VENDORING] builder v0.1.0 ([ROOT]/home/.cargo/registry/src/-[HASH]/builder-0.1.0) to [ROOT]/foo/vendor/builder
+[VENDORING] host-builder v0.1.0 ([ROOT]/home/.cargo/registry/src/-[HASH]/host-builder-0.1.0) to [ROOT]/foo/vendor/host-builder
+[STUBBING] host-only v0.1.0 (unused on the vendored platforms) to [ROOT]/foo/vendor/host-only
+[VENDORING] pm v0.1.0 ([ROOT]/home/.cargo/registry/src/-[HASH]/pm-0.1.0) to [ROOT]/foo/vendor/pm
+[VENDORING] pm-helper v0.1.0 ([ROOT]/home/.cargo/registry/src/-[HASH]/pm-helper-0.1.0) to [ROOT]/foo/vendor/pm-helper
+To use vendored sources, add this to your .cargo/config.toml for this project:
+
+
+"#]]
+        .unordered(),
+    )
+    .run();
+}
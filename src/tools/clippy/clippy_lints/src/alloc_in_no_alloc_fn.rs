@@ -0,0 +1,205 @@
+use clippy_utils::mir::call_graph::{lint_reachable_sites, Call};
+use clippy_utils::ty::{is_type_diagnostic_item, is_type_lang_item};
+use clippy_utils::{def_path_def_ids, fn_has_unsatisfiable_preds, get_attr};
+use rustc_hir::def_id::{DefId, DefIdSet, LocalDefId};
+use rustc_hir::intravisit::FnKind;
+use rustc_hir::{Body, FnDecl, LangItem};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::{sym, Span};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks functions marked `#[clippy::no_alloc]`, and the local functions they call, for
+    /// calls which may allocate on the heap, such as `Box::new`, `Vec::push`, `format!` or
+    /// `to_owned`.
+    ///
+    /// Each allocation is reported with the chain of calls leading to it. Calls in generic
+    /// functions are resolved with the types they are called with where possible, so e.g.
+    /// `T::clone` is reported when `T` is a `Vec`. Functions known to allocate can be added with
+    /// the `allocating-functions` configuration.
+    ///
+    /// Note: Even though this lint is warn-by-default, it will only trigger for functions marked
+    /// `#[clippy::no_alloc]`.
+    ///
+    /// ### Why is this bad?
+    /// Allocating takes an unpredictable amount of time, which latency critical code such as an
+    /// audio callback or an interrupt handler can't afford.
+    ///
+    /// ### Known problems
+    /// Calls are followed as for [`panic_in_no_panic_fn`](#panic_in_no_panic_fn), with the same
+    /// limitations: functions from other crates are assumed not to allocate unless they are known
+    /// to.
+    ///
+    /// ### Example
+    /// ```rust
+    /// #[clippy::no_alloc]
+    /// fn process(samples: &mut [f32], gain: f32) {
+    ///     let mut scaled = Vec::with_capacity(samples.len());
+    ///     for sample in samples.iter() {
+    ///         scaled.push(sample * gain);
+    ///     }
+    ///     samples.copy_from_slice(&scaled);
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// #[clippy::no_alloc]
+    /// fn process(samples: &mut [f32], gain: f32) {
+    ///     for sample in samples.iter_mut() {
+    ///         *sample *= gain;
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub ALLOC_IN_NO_ALLOC_FN,
+    suspicious,
+    "code that may allocate in a function marked `#[clippy::no_alloc]`"
+}
+
+/// Functions which allocate, or may allocate to grow a collection.
+const ALLOCATING_FUNCTIONS: &[&str] = &[
+    "alloc::alloc::alloc",
+    "alloc::alloc::alloc_zeroed",
+    "alloc::alloc::realloc",
+    "alloc::boxed::Box::new",
+    "alloc::boxed::Box::pin",
+    "alloc::rc::Rc::new",
+    "alloc::sync::Arc::new",
+    "alloc::vec::from_elem",
+    "alloc::vec::Vec::with_capacity",
+    "alloc::vec::Vec::push",
+    "alloc::vec::Vec::insert",
+    "alloc::vec::Vec::append",
+    "alloc::vec::Vec::extend_from_slice",
+    "alloc::vec::Vec::resize",
+    "alloc::vec::Vec::reserve",
+    "alloc::vec::Vec::reserve_exact",
+    "alloc::string::String::with_capacity",
+    "alloc::string::String::push",
+    "alloc::string::String::push_str",
+    "alloc::string::String::insert",
+    "alloc::string::String::insert_str",
+    "alloc::string::String::reserve",
+    "alloc::string::String::reserve_exact",
+    "alloc::fmt::format",
+    "alloc::borrow::ToOwned::to_owned",
+    "alloc::string::ToString::to_string",
+];
+
+/// Traits whose implementations for the types owning heap memory allocate.
+const ALLOCATING_TRAITS: &[&str] = &[
+    "core::clone::Clone",
+    "core::convert::From",
+    "core::iter::FromIterator",
+    "core::iter::Extend",
+];
+
+pub struct AllocInNoAllocFn {
+    conf_allocating: Vec<String>,
+    allocating: DefIdSet,
+    allocating_traits: DefIdSet,
+}
+
+impl AllocInNoAllocFn {
+    pub fn new(conf_allocating: Vec<String>) -> Self {
+        Self {
+            conf_allocating,
+            allocating: DefIdSet::default(),
+            allocating_traits: DefIdSet::default(),
+        }
+    }
+
+    /// Why `call` may allocate, if it may.
+    fn call_allocates(&self, cx: &LateContext<'_>, call: &Call<'_>) -> Option<String> {
+        if cx.tcx.lang_items().exchange_malloc_fn() == Some(call.def_id) {
+            Some("heap allocation".to_string())
+        } else if let Some(def_id) = [call.def_id, call.callee()]
+            .into_iter()
+            .find(|&def_id| self.allocating.contains(&def_id) || self.is_allocating_impl(cx, def_id))
+        {
+            Some(format!("`{}` allocates", cx.tcx.def_path_str(def_id)))
+        } else {
+            None
+        }
+    }
+
+    /// Whether `def_id` is a method of an implementation of one of `ALLOCATING_TRAITS` for a type
+    /// owning heap memory, e.g. `<Vec<T> as Clone>::clone`.
+    fn is_allocating_impl(&self, cx: &LateContext<'_>, def_id: DefId) -> bool {
+        let tcx = cx.tcx;
+        if let Some(impl_id) = tcx.impl_of_method(def_id)
+            && let Some(trait_id) = tcx.trait_id_of_impl(impl_id)
+            && self.allocating_traits.contains(&trait_id)
+        {
+            let self_ty = tcx.type_of(impl_id).instantiate_identity();
+            self_ty.is_box()
+                || is_type_lang_item(cx, self_ty, LangItem::String)
+                || [sym::Vec, sym::Rc, sym::Arc]
+                    .into_iter()
+                    .any(|name| is_type_diagnostic_item(cx, self_ty, name))
+        } else {
+            false
+        }
+    }
+}
+
+impl_lint_pass!(AllocInNoAllocFn => [ALLOC_IN_NO_ALLOC_FN]);
+
+fn is_no_alloc(cx: &LateContext<'_>, def_id: DefId) -> bool {
+    get_attr(cx.sess(), cx.tcx.get_attrs_unchecked(def_id), "no_alloc")
+        .next()
+        .is_some()
+}
+
+impl<'tcx> LateLintPass<'tcx> for AllocInNoAllocFn {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        for path in ALLOCATING_FUNCTIONS
+            .iter()
+            .copied()
+            .chain(self.conf_allocating.iter().map(String::as_str))
+        {
+            let segs: Vec<_> = path.split("::").collect();
+            self.allocating.extend(def_path_def_ids(cx, &segs));
+        }
+        for path in ALLOCATING_TRAITS {
+            let segs: Vec<_> = path.split("::").collect();
+            self.allocating_traits.extend(def_path_def_ids(cx, &segs));
+        }
+    }
+
+    fn check_fn(
+        &mut self,
+        cx: &LateContext<'tcx>,
+        kind: FnKind<'tcx>,
+        _: &'tcx FnDecl<'_>,
+        _: &'tcx Body<'_>,
+        span: Span,
+        def_id: LocalDefId,
+    ) {
+        if matches!(kind, FnKind::Closure)
+            || !is_no_alloc(cx, def_id.to_def_id())
+            || in_external_macro(cx.sess(), span)
+            // Building MIR for `fn`s with unsatisfiable preds results in ICE.
+            || fn_has_unsatisfiable_preds(cx, def_id.to_def_id())
+        {
+            return;
+        }
+
+        lint_reachable_sites(
+            cx,
+            ALLOC_IN_NO_ALLOC_FN,
+            def_id,
+            "no_alloc",
+            "allocate",
+            |call| self.call_allocates(cx, call).is_none() && !is_no_alloc(cx, call.callee()),
+            |calls, _| {
+                calls
+                    .iter()
+                    .filter_map(|call| Some((call.span, self.call_allocates(cx, call)?)))
+                    .collect()
+            },
+        );
+    }
+}
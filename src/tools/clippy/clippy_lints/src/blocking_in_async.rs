@@ -0,0 +1,284 @@
+use clippy_utils::diagnostics::span_lint_hir_and_then;
+use clippy_utils::mir::call_graph::{calls, note_call_chain, walk_local_calls, CallStep};
+use clippy_utils::visitors::for_each_expr;
+use clippy_utils::{def_path_def_ids, fn_def_id, fn_has_unsatisfiable_preds};
+use core::ops::ControlFlow;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_errors::Diagnostic;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::{DefId, DefIdMap, LocalDefId};
+use rustc_hir::{Body, ExprKind, GeneratorKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::Span;
+
+use crate::utils::conf::BlockingFunction;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for calls to blocking functions, such as `std::fs::read` or `std::thread::sleep`,
+    /// in async functions and blocks.
+    ///
+    /// Clippy knows about the blocking functions of the standard library. Others, and the async
+    /// alternatives to suggest, can be added with the `blocking-functions` configuration.
+    ///
+    /// ### Why is this bad?
+    /// A blocking call keeps the executor thread from running any other task until it
+    /// returns, which can stall the whole program.
+    ///
+    /// ### Known problems
+    /// Locking a `std::sync::Mutex` is fine if it is never held for long, and such a `Mutex`
+    /// is often preferable to an async one.
+    ///
+    /// ### Example
+    /// ```rust
+    /// async fn load() -> std::io::Result<String> {
+    ///     std::fs::read_to_string("config.toml")
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// async fn load() -> std::io::Result<String> {
+    ///     tokio::fs::read_to_string("config.toml").await
+    /// }
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub BLOCKING_CALL_IN_ASYNC,
+    pedantic,
+    "calls to blocking functions in async functions and blocks"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for calls in async functions and blocks to local functions which, directly or
+    /// through other local functions, call a blocking function. See
+    /// [`blocking_call_in_async`](#blocking_call_in_async) for the functions considered blocking.
+    ///
+    /// ### Why is this bad?
+    /// A blocking call keeps the executor thread from running any other task until it
+    /// returns, however deeply it is nested in helper functions.
+    ///
+    /// ### Known problems
+    /// Only functions in the current crate are followed, and calls through function pointers or
+    /// trait objects are not.
+    ///
+    /// ### Example
+    /// ```rust
+    /// fn load_config() -> std::io::Result<String> {
+    ///     std::fs::read_to_string("config.toml")
+    /// }
+    ///
+    /// async fn start() -> std::io::Result<()> {
+    ///     let config = load_config()?;
+    ///     // ..
+    ///     Ok(())
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// async fn load_config() -> std::io::Result<String> {
+    ///     tokio::fs::read_to_string("config.toml").await
+    /// }
+    ///
+    /// async fn start() -> std::io::Result<()> {
+    ///     let config = load_config().await?;
+    ///     // ..
+    ///     Ok(())
+    /// }
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub INDIRECT_BLOCKING_CALL_IN_ASYNC,
+    pedantic,
+    "calls to functions which block in async functions and blocks"
+}
+
+const BLOCKING_FUNCTIONS: &[&str] = &[
+    "std::thread::sleep",
+    "std::thread::JoinHandle::join",
+    "std::fs::canonicalize",
+    "std::fs::copy",
+    "std::fs::create_dir",
+    "std::fs::create_dir_all",
+    "std::fs::hard_link",
+    "std::fs::metadata",
+    "std::fs::read",
+    "std::fs::read_dir",
+    "std::fs::read_link",
+    "std::fs::read_to_string",
+    "std::fs::remove_dir",
+    "std::fs::remove_dir_all",
+    "std::fs::remove_file",
+    "std::fs::rename",
+    "std::fs::set_permissions",
+    "std::fs::symlink_metadata",
+    "std::fs::write",
+    "std::fs::File::create",
+    "std::fs::File::open",
+    "std::fs::File::set_len",
+    "std::fs::File::sync_all",
+    "std::fs::File::sync_data",
+    "std::fs::OpenOptions::open",
+    "std::io::Stdin::read_line",
+    "std::net::TcpListener::accept",
+    "std::net::TcpStream::connect",
+    "std::net::TcpStream::connect_timeout",
+    "std::net::ToSocketAddrs::to_socket_addrs",
+    "std::net::UdpSocket::recv",
+    "std::net::UdpSocket::recv_from",
+    "std::net::UdpSocket::send",
+    "std::net::UdpSocket::send_to",
+    "std::process::Child::wait",
+    "std::process::Child::wait_with_output",
+    "std::process::Command::output",
+    "std::process::Command::status",
+    "std::sync::Barrier::wait",
+    "std::sync::Condvar::wait",
+    "std::sync::Condvar::wait_timeout",
+    "std::sync::Condvar::wait_while",
+    "std::sync::Mutex::lock",
+    "std::sync::RwLock::read",
+    "std::sync::RwLock::write",
+    "std::sync::mpsc::Receiver::recv",
+    "std::sync::mpsc::Receiver::recv_timeout",
+    "std::sync::mpsc::SyncSender::send",
+];
+
+/// A call to a blocking function found in a local function, with the calls leading to it.
+#[derive(Clone)]
+struct BlockingPath {
+    path: Vec<CallStep>,
+    site: Span,
+    index: usize,
+}
+
+pub struct BlockingInAsync {
+    conf_blocking: Vec<BlockingFunction>,
+    /// The blocking functions, each with its index in `conf_blocking`.
+    blocking: DefIdMap<usize>,
+    /// The local functions checked so far, with the shortest path to a blocking call in each.
+    helpers: FxHashMap<LocalDefId, Option<BlockingPath>>,
+}
+
+impl BlockingInAsync {
+    pub fn new(conf_blocking: Vec<BlockingFunction>) -> Self {
+        Self {
+            conf_blocking: BLOCKING_FUNCTIONS
+                .iter()
+                .map(|path| BlockingFunction::Simple((*path).to_string()))
+                .chain(conf_blocking)
+                .collect(),
+            blocking: DefIdMap::default(),
+            helpers: FxHashMap::default(),
+        }
+    }
+
+    fn blocking(&self, def_id: DefId) -> Option<usize> {
+        self.blocking.get(&def_id).copied()
+    }
+
+    fn blocking_path(&mut self, cx: &LateContext<'_>, def_id: LocalDefId) -> Option<BlockingPath> {
+        if let Some(path) = self.helpers.get(&def_id) {
+            return path.clone();
+        }
+        let mut found = None;
+        if cx.tcx.is_mir_available(def_id) && !fn_has_unsatisfiable_preds(cx, def_id.to_def_id()) {
+            let param_env = cx.tcx.param_env(def_id);
+            walk_local_calls(
+                cx,
+                def_id,
+                |call| self.blocking(call.def_id).is_none() && !cx.tcx.asyncness(call.callee()).is_async(),
+                |path, instance, body| {
+                    if found.is_none() {
+                        found = calls(cx.tcx, param_env, instance, body).into_iter().find_map(|call| {
+                            let index = self.blocking(call.def_id).or_else(|| self.blocking(call.callee()))?;
+                            Some(BlockingPath {
+                                path: path.to_vec(),
+                                site: call.span,
+                                index,
+                            })
+                        });
+                    }
+                },
+            );
+        }
+        self.helpers.insert(def_id, found.clone());
+        found
+    }
+}
+
+impl_lint_pass!(BlockingInAsync => [BLOCKING_CALL_IN_ASYNC, INDIRECT_BLOCKING_CALL_IN_ASYNC]);
+
+fn suggest_replacement(diag: &mut Diagnostic, conf: &BlockingFunction) {
+    if let Some(replacement) = conf.replacement() {
+        diag.help(format!("consider using `{replacement}` instead"));
+    } else {
+        diag.help("consider using an async alternative, or running the call on a thread meant for blocking work");
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for BlockingInAsync {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        for (index, conf) in self.conf_blocking.iter().enumerate() {
+            let segs: Vec<_> = conf.path().split("::").collect();
+            for id in def_path_def_ids(cx, &segs) {
+                self.blocking.insert(id, index);
+            }
+        }
+    }
+
+    fn check_body(&mut self, cx: &LateContext<'tcx>, body: &'tcx Body<'tcx>) {
+        if !matches!(body.generator_kind(), Some(GeneratorKind::Async(_))) {
+            return;
+        }
+        // Closures and nested async blocks are separate bodies, so aren't visited here. A closure
+        // isn't necessarily called on the executor thread, e.g. if it's passed to `spawn_blocking`.
+        for_each_expr(body.value, |expr| {
+            if !matches!(expr.kind, ExprKind::Call(..) | ExprKind::MethodCall(..))
+                || in_external_macro(cx.sess(), expr.span)
+            {
+                return ControlFlow::<()>::Continue(());
+            }
+            let Some(def_id) = fn_def_id(cx, expr) else {
+                return ControlFlow::Continue(());
+            };
+            if let Some(index) = self.blocking(def_id) {
+                let conf = &self.conf_blocking[index];
+                span_lint_hir_and_then(
+                    cx,
+                    BLOCKING_CALL_IN_ASYNC,
+                    expr.hir_id,
+                    expr.span,
+                    &format!("call to blocking function `{}` in an async context", conf.path()),
+                    |diag| suggest_replacement(diag, conf),
+                );
+            } else if let Some(local_id) = def_id.as_local()
+                && matches!(cx.tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+                && !cx.tcx.asyncness(def_id).is_async()
+                && let Some(blocking) = self.blocking_path(cx, local_id)
+            {
+                let conf = &self.conf_blocking[blocking.index];
+                span_lint_hir_and_then(
+                    cx,
+                    INDIRECT_BLOCKING_CALL_IN_ASYNC,
+                    expr.hir_id,
+                    expr.span,
+                    &format!(
+                        "call to `{}` in an async context, which calls blocking function `{}`",
+                        cx.tcx.def_path_str(def_id),
+                        conf.path()
+                    ),
+                    |diag| {
+                        let caller = note_call_chain(cx.tcx, diag, def_id, &blocking.path);
+                        diag.span_note(
+                            blocking.site,
+                            format!("`{}` blocks here", cx.tcx.def_path_str(caller)),
+                        );
+                        suggest_replacement(diag, conf);
+                    },
+                );
+            }
+            ControlFlow::Continue(())
+        });
+    }
+}
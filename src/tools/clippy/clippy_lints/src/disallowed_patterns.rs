@@ -0,0 +1,640 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::source::snippet_with_applicability;
+use clippy_utils::{def_path_def_ids, is_trait_impl_item, path_def_id};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_errors::Applicability;
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::intravisit::FnKind;
+use rustc_hir::{Body, Expr, ExprKind, FnDecl, FnRetTy};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty::{self, GenericArgKind, Ty};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::Span;
+use serde::{de, Deserialize};
+use std::fmt;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Denies the expression and type patterns configured in clippy.toml.
+    ///
+    /// An `expr` pattern is a fully qualified path, a call of one (`path(args)`) or a placeholder,
+    /// followed by any number of method calls, with the fully qualified path of the method
+    /// (`.path::to::method(args)`). The arguments are a comma separated list of patterns, or `..`
+    /// to accept any arguments. `_` matches any expression, and `$name` matches any expression and
+    /// lets `replacement` refer to it.
+    ///
+    /// A `type` pattern is a fully qualified path with optional generic arguments (`path<types>`),
+    /// a reference (`&type` or `&mut type`) or `_` to match any type. The generic arguments only
+    /// need to match the leading arguments of the type, so `std::vec::Vec<u8>` also matches
+    /// vectors with a custom allocator. Types are checked wherever they appear in a function
+    /// signature, or only in the signatures of exported functions with `public-only = true`.
+    ///
+    /// Note: Even though this lint is warn-by-default, it will only trigger if
+    /// patterns are defined in the clippy.toml file.
+    ///
+    /// ### Why is this bad?
+    /// Some code patterns are undesirable in certain contexts, and it's beneficial to
+    /// lint for them as needed.
+    ///
+    /// ### Example
+    /// An example clippy.toml configuration:
+    /// ```toml
+    /// # clippy.toml
+    /// disallowed-patterns = [
+    ///     { expr = "std::fs::File::open(..).std::result::Result::unwrap()", message = "handle the error from opening a file" },
+    ///     { expr = "$m.std::sync::Mutex::lock().std::result::Result::unwrap()", message = "use `lock_or_poisoned`", replacement = "lock_or_poisoned(&$m)" },
+    ///     { type = "std::sync::Arc<std::sync::Mutex<std::vec::Vec<_>>>", message = "use a channel", public-only = true },
+    /// ]
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// let file = File::open("config.toml").unwrap();
+    ///
+    /// let guard = state.lock().unwrap();
+    ///
+    /// pub fn queue() -> Arc<Mutex<Vec<Job>>> { .. }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust,ignore
+    /// let file = File::open("config.toml")?;
+    ///
+    /// let guard = lock_or_poisoned(&state);
+    ///
+    /// pub fn queue() -> Sender<Job> { .. }
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub DISALLOWED_PATTERNS,
+    style,
+    "use of a disallowed code pattern"
+}
+
+/// A pattern matching expressions, parsed from the `expr` key of a `disallowed-patterns` entry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExprPattern {
+    /// `_`, or `$name` to refer to the expression in the replacement.
+    Any(Option<String>),
+    /// A function, constant or static, e.g. `std::process::exit`.
+    Path(Vec<String>),
+    /// `path(args)`
+    Call(Vec<String>, Args),
+    /// `receiver.path::to::method(args)`
+    MethodCall(Box<ExprPattern>, Vec<String>, Args),
+}
+
+impl ExprPattern {
+    /// Collects the names of the `$name` placeholders of the pattern.
+    fn captures<'a>(&'a self, names: &mut Vec<&'a str>) {
+        let args = |args: &'a Args, names: &mut Vec<&'a str>| {
+            if let Args::Exact(args) = args {
+                for arg in args {
+                    arg.captures(names);
+                }
+            }
+        };
+        match self {
+            Self::Any(name) => names.extend(name.as_deref()),
+            Self::Path(_) => {},
+            Self::Call(_, call_args) => args(call_args, names),
+            Self::MethodCall(receiver, _, call_args) => {
+                receiver.captures(names);
+                args(call_args, names);
+            },
+        }
+    }
+}
+
+/// The arguments of a call pattern.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Args {
+    /// `..`
+    Any,
+    Exact(Vec<ExprPattern>),
+}
+
+/// A pattern matching types, parsed from the `type` key of a `disallowed-patterns` entry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TyPattern {
+    /// `_`
+    Any,
+    /// `&type` or `&mut type`
+    Ref(bool, Box<TyPattern>),
+    /// `path<types>`
+    Path(Vec<String>, Vec<TyPattern>),
+}
+
+#[derive(Clone, Debug)]
+pub enum PatternKind {
+    Expr(ExprPattern),
+    Ty(TyPattern),
+}
+
+/// An entry of the `disallowed-patterns` configuration.
+#[derive(Clone, Debug)]
+pub struct DisallowedPattern {
+    pub kind: PatternKind,
+    pub message: String,
+    pub replacement: Option<String>,
+    pub public_only: bool,
+}
+
+impl<'de> Deserialize<'de> for DisallowedPattern {
+    fn deserialize<D>(deser: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields, rename_all = "kebab-case")]
+        struct Raw {
+            expr: Option<String>,
+            #[serde(rename = "type")]
+            ty: Option<String>,
+            message: String,
+            replacement: Option<String>,
+            #[serde(default)]
+            public_only: bool,
+        }
+
+        let raw = Raw::deserialize(deser)?;
+        let kind = match (raw.expr, raw.ty) {
+            (Some(expr), None) => {
+                if raw.public_only {
+                    return Err(de::Error::custom("`public-only` can only be used with `type` patterns"));
+                }
+                let pat = Parser::new(&expr).finish(Parser::expr).map_err(de::Error::custom)?;
+                if let Some(replacement) = &raw.replacement {
+                    let mut captures = Vec::new();
+                    pat.captures(&mut captures);
+                    for (i, _) in replacement.match_indices('$') {
+                        let name = &replacement[i + 1..];
+                        let name = &name[..name
+                            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                            .unwrap_or(name.len())];
+                        if !captures.contains(&name) {
+                            return Err(de::Error::custom(format!(
+                                "`replacement` refers to `${name}`, which isn't captured by the pattern `{pat}`"
+                            )));
+                        }
+                    }
+                }
+                PatternKind::Expr(pat)
+            },
+            (None, Some(ty)) => {
+                if raw.replacement.is_some() {
+                    return Err(de::Error::custom("`replacement` can only be used with `expr` patterns"));
+                }
+                PatternKind::Ty(Parser::new(&ty).finish(Parser::ty).map_err(de::Error::custom)?)
+            },
+            _ => return Err(de::Error::custom("expected exactly one of `expr` or `type`")),
+        };
+        Ok(Self {
+            kind,
+            message: raw.message,
+            replacement: raw.replacement,
+            public_only: raw.public_only,
+        })
+    }
+}
+
+struct Parser<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn new(s: &'a str) -> Self {
+        Self { rest: s }
+    }
+
+    fn finish<T>(mut self, f: impl FnOnce(&mut Self) -> Result<T, String>) -> Result<T, String> {
+        let res = f(&mut self)?;
+        self.skip_ws();
+        if self.rest.is_empty() {
+            Ok(res)
+        } else {
+            Err(format!("unexpected `{}` in pattern", self.rest))
+        }
+    }
+
+    fn skip_ws(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_ws();
+        if let Some(rest) = self.rest.strip_prefix(token) {
+            self.rest = rest;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), String> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(format!("expected `{token}` in pattern"))
+        }
+    }
+
+    /// Eats a `_` that isn't the start of an identifier.
+    fn eat_placeholder(&mut self) -> bool {
+        self.skip_ws();
+        let mut chars = self.rest.chars();
+        if chars.next() == Some('_') && !chars.next().map_or(false, |c| c.is_alphanumeric() || c == '_') {
+            self.rest = &self.rest[1..];
+            true
+        } else {
+            false
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, String> {
+        self.skip_ws();
+        let len = self
+            .rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(self.rest.len());
+        if len == 0 || self.rest.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err("expected an identifier in pattern".to_string());
+        }
+        let (ident, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        Ok(ident.to_string())
+    }
+
+    fn path(&mut self) -> Result<Vec<String>, String> {
+        let mut path = vec![self.ident()?];
+        while self.eat("::") {
+            path.push(self.ident()?);
+        }
+        Ok(path)
+    }
+
+    fn args(&mut self) -> Result<Args, String> {
+        if self.eat("..") {
+            self.expect(")")?;
+            return Ok(Args::Any);
+        }
+        let mut args = Vec::new();
+        while !self.eat(")") {
+            args.push(self.expr()?);
+            if !self.eat(",") {
+                self.expect(")")?;
+                break;
+            }
+        }
+        Ok(Args::Exact(args))
+    }
+
+    fn expr(&mut self) -> Result<ExprPattern, String> {
+        let mut expr = if self.eat("$") {
+            ExprPattern::Any(Some(self.ident()?))
+        } else if self.eat_placeholder() {
+            ExprPattern::Any(None)
+        } else {
+            let path = self.path()?;
+            if self.eat("(") {
+                ExprPattern::Call(path, self.args()?)
+            } else {
+                ExprPattern::Path(path)
+            }
+        };
+        while self.eat(".") {
+            let method = self.path()?;
+            self.expect("(")?;
+            expr = ExprPattern::MethodCall(Box::new(expr), method, self.args()?);
+        }
+        Ok(expr)
+    }
+
+    fn ty(&mut self) -> Result<TyPattern, String> {
+        if self.eat("&") {
+            let mutable = self.eat("mut ");
+            return Ok(TyPattern::Ref(mutable, Box::new(self.ty()?)));
+        }
+        if self.eat_placeholder() {
+            return Ok(TyPattern::Any);
+        }
+        let path = self.path()?;
+        let mut args = Vec::new();
+        if self.eat("<") {
+            loop {
+                args.push(self.ty()?);
+                if !self.eat(",") {
+                    self.expect(">")?;
+                    break;
+                }
+            }
+        }
+        Ok(TyPattern::Path(path, args))
+    }
+}
+
+impl fmt::Display for ExprPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let args = |f: &mut fmt::Formatter<'_>, args: &Args| match args {
+            Args::Any => write!(f, "(..)"),
+            Args::Exact(args) => {
+                write!(f, "(")?;
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{arg}")?;
+                }
+                write!(f, ")")
+            },
+        };
+        match self {
+            Self::Any(None) => write!(f, "_"),
+            Self::Any(Some(name)) => write!(f, "${name}"),
+            Self::Path(path) => write!(f, "{}", path.join("::")),
+            Self::Call(path, call_args) => {
+                write!(f, "{}", path.join("::"))?;
+                args(f, call_args)
+            },
+            Self::MethodCall(receiver, method, call_args) => {
+                write!(f, "{receiver}.{}", method.join("::"))?;
+                args(f, call_args)
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DisallowedPatterns {
+    conf_disallowed: Vec<DisallowedPattern>,
+    def_ids: FxHashMap<Vec<String>, Vec<DefId>>,
+}
+
+impl DisallowedPatterns {
+    pub fn new(conf_disallowed: Vec<DisallowedPattern>) -> Self {
+        Self {
+            conf_disallowed,
+            def_ids: FxHashMap::default(),
+        }
+    }
+
+    fn resolve(&mut self, cx: &LateContext<'_>, path: &[String]) {
+        if !self.def_ids.contains_key(path) {
+            let segs: Vec<_> = path.iter().map(String::as_str).collect();
+            self.def_ids
+                .insert(path.to_vec(), def_path_def_ids(cx, &segs).collect());
+        }
+    }
+
+    fn resolve_expr(&mut self, cx: &LateContext<'_>, pat: &ExprPattern) {
+        match pat {
+            ExprPattern::Any(_) => {},
+            ExprPattern::Path(path) => self.resolve(cx, path),
+            ExprPattern::Call(path, args) => {
+                self.resolve(cx, path);
+                self.resolve_args(cx, args);
+            },
+            ExprPattern::MethodCall(receiver, method, args) => {
+                self.resolve_expr(cx, receiver);
+                self.resolve(cx, method);
+                self.resolve_args(cx, args);
+            },
+        }
+    }
+
+    fn resolve_args(&mut self, cx: &LateContext<'_>, args: &Args) {
+        if let Args::Exact(args) = args {
+            for arg in args {
+                self.resolve_expr(cx, arg);
+            }
+        }
+    }
+
+    fn resolve_ty(&mut self, cx: &LateContext<'_>, pat: &TyPattern) {
+        match pat {
+            TyPattern::Any => {},
+            TyPattern::Ref(_, inner) => self.resolve_ty(cx, inner),
+            TyPattern::Path(path, args) => {
+                self.resolve(cx, path);
+                for arg in args {
+                    self.resolve_ty(cx, arg);
+                }
+            },
+        }
+    }
+
+    fn is_path(&self, path: &[String], def_id: Option<DefId>) -> bool {
+        def_id.map_or(false, |def_id| self.def_ids[path].contains(&def_id))
+    }
+
+    fn match_expr<'tcx>(
+        &self,
+        cx: &LateContext<'tcx>,
+        pat: &ExprPattern,
+        expr: &'tcx Expr<'tcx>,
+        captures: &mut Vec<(&'tcx Expr<'tcx>, String)>,
+    ) -> bool {
+        match (pat, expr.kind) {
+            (ExprPattern::Any(name), _) => {
+                if let Some(name) = name {
+                    captures.push((expr, name.clone()));
+                }
+                true
+            },
+            (ExprPattern::Path(path), ExprKind::Path(_)) => self.is_path(path, path_def_id(cx, expr)),
+            (ExprPattern::Call(path, args), ExprKind::Call(callee, call_args)) => {
+                self.is_path(path, path_def_id(cx, callee)) && self.match_args(cx, args, call_args, captures)
+            },
+            (ExprPattern::MethodCall(receiver, method, args), ExprKind::MethodCall(_, recv, call_args, _)) => {
+                self.is_path(method, cx.typeck_results().type_dependent_def_id(expr.hir_id))
+                    && self.match_expr(cx, receiver, recv, captures)
+                    && self.match_args(cx, args, call_args, captures)
+            },
+            _ => false,
+        }
+    }
+
+    fn match_args<'tcx>(
+        &self,
+        cx: &LateContext<'tcx>,
+        args: &Args,
+        call_args: &'tcx [Expr<'tcx>],
+        captures: &mut Vec<(&'tcx Expr<'tcx>, String)>,
+    ) -> bool {
+        match args {
+            Args::Any => true,
+            Args::Exact(args) => {
+                args.len() == call_args.len()
+                    && args
+                        .iter()
+                        .zip(call_args)
+                        .all(|(arg, call_arg)| self.match_expr(cx, arg, call_arg, captures))
+            },
+        }
+    }
+
+    fn match_ty<'tcx>(&self, pat: &TyPattern, ty: Ty<'tcx>) -> bool {
+        match (pat, ty.kind()) {
+            (TyPattern::Any, _) => true,
+            (TyPattern::Ref(mutable, inner), ty::Ref(_, ty, mutbl)) => {
+                *mutable == mutbl.is_mut() && self.match_ty(inner, *ty)
+            },
+            (TyPattern::Path(path, args), ty::Adt(adt, ty_args)) => {
+                self.def_ids[path].contains(&adt.did())
+                    && args.len() <= ty_args.types().count()
+                    && args.iter().zip(ty_args.types()).all(|(arg, ty)| self.match_ty(arg, ty))
+            },
+            (TyPattern::Path(path, args), _) => {
+                // Primitives can't be resolved to a `DefId`, so compare them by name instead
+                args.is_empty()
+                    && (ty.is_primitive() || ty.is_str())
+                    && matches!(&path[..], [name] if *name == ty.to_string())
+            },
+            _ => false,
+        }
+    }
+
+    fn check_ty<'tcx>(&self, cx: &LateContext<'tcx>, hir_ty: &rustc_hir::Ty<'_>, ty: Ty<'tcx>, exported: bool) {
+        for conf in &self.conf_disallowed {
+            if let PatternKind::Ty(pat) = &conf.kind
+                && (exported || !conf.public_only)
+                && ty.walk().any(|arg| matches!(arg.unpack(), GenericArgKind::Type(ty) if self.match_ty(pat, ty)))
+            {
+                span_lint_and_then(cx, DISALLOWED_PATTERNS, hir_ty.span, &conf.message, |diag| {
+                    diag.note("this pattern is disallowed in clippy.toml");
+                });
+            }
+        }
+    }
+}
+
+impl_lint_pass!(DisallowedPatterns => [DISALLOWED_PATTERNS]);
+
+impl<'tcx> LateLintPass<'tcx> for DisallowedPatterns {
+    fn check_crate(&mut self, cx: &LateContext<'_>) {
+        for conf in self.conf_disallowed.clone() {
+            match &conf.kind {
+                PatternKind::Expr(pat) => self.resolve_expr(cx, pat),
+                PatternKind::Ty(pat) => self.resolve_ty(cx, pat),
+            }
+        }
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+        if in_external_macro(cx.sess(), expr.span) {
+            return;
+        }
+        for conf in &self.conf_disallowed {
+            let PatternKind::Expr(pat) = &conf.kind else {
+                continue;
+            };
+            let mut captures = Vec::new();
+            if !self.match_expr(cx, pat, expr, &mut captures) {
+                continue;
+            }
+            span_lint_and_then(cx, DISALLOWED_PATTERNS, expr.span, &conf.message, |diag| {
+                diag.note(format!("the pattern `{pat}` is disallowed in clippy.toml"));
+                if let Some(replacement) = &conf.replacement {
+                    let mut app = Applicability::MaybeIncorrect;
+                    let mut sugg = replacement.clone();
+                    // Substitute the longest names first, so `$a` doesn't replace part of `$ab`
+                    captures.sort_by_key(|(_, name)| std::cmp::Reverse(name.len()));
+                    for (capture, name) in &captures {
+                        let snippet = snippet_with_applicability(cx, capture.span, "..", &mut app);
+                        sugg = sugg.replace(&format!("${name}"), &snippet);
+                    }
+                    diag.span_suggestion(expr.span, "try", sugg, app);
+                }
+            });
+        }
+    }
+
+    fn check_fn(
+        &mut self,
+        cx: &LateContext<'tcx>,
+        kind: FnKind<'tcx>,
+        decl: &'tcx FnDecl<'_>,
+        _: &'tcx Body<'_>,
+        span: Span,
+        def_id: LocalDefId,
+    ) {
+        if matches!(kind, FnKind::Closure)
+            || in_external_macro(cx.sess(), span)
+            || is_trait_impl_item(cx, cx.tcx.hir().local_def_id_to_hir_id(def_id))
+            || !self
+                .conf_disallowed
+                .iter()
+                .any(|conf| matches!(conf.kind, PatternKind::Ty(_)))
+        {
+            return;
+        }
+        let exported = cx.effective_visibilities.is_exported(def_id);
+        let sig = cx.tcx.fn_sig(def_id).instantiate_identity().skip_binder();
+        for (hir_ty, ty) in decl.inputs.iter().zip(sig.inputs()) {
+            self.check_ty(cx, hir_ty, *ty, exported);
+        }
+        if let FnRetTy::Return(hir_ty) = decl.output {
+            self.check_ty(cx, hir_ty, sig.output(), exported);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Args, ExprPattern, Parser, TyPattern};
+
+    fn path(path: &str) -> Vec<String> {
+        path.split("::").map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn parse_expr() {
+        let pat = Parser::new("std::fs::File::open(..).std::result::Result::unwrap()")
+            .finish(Parser::expr)
+            .unwrap();
+        assert_eq!(
+            pat,
+            ExprPattern::MethodCall(
+                Box::new(ExprPattern::Call(path("std::fs::File::open"), Args::Any)),
+                path("std::result::Result::unwrap"),
+                Args::Exact(vec![]),
+            )
+        );
+        assert_eq!(pat.to_string(), "std::fs::File::open(..).std::result::Result::unwrap()");
+
+        let pat = Parser::new(" $m . std :: sync :: Mutex :: lock ( ) . std::result::Result::map ( _ , $f ) ")
+            .finish(Parser::expr)
+            .unwrap();
+        assert_eq!(
+            pat.to_string(),
+            "$m.std::sync::Mutex::lock().std::result::Result::map(_, $f)"
+        );
+        let mut captures = Vec::new();
+        pat.captures(&mut captures);
+        assert_eq!(captures, ["m", "f"]);
+
+        assert!(Parser::new("_.unwrap(").finish(Parser::expr).is_err());
+        assert!(Parser::new("_.unwrap() extra").finish(Parser::expr).is_err());
+        assert!(Parser::new("$").finish(Parser::expr).is_err());
+    }
+
+    #[test]
+    fn parse_ty() {
+        assert_eq!(
+            Parser::new("std::sync::Arc<std::sync::Mutex<std::vec::Vec<_>>>")
+                .finish(Parser::ty)
+                .unwrap(),
+            TyPattern::Path(
+                path("std::sync::Arc"),
+                vec![TyPattern::Path(
+                    path("std::sync::Mutex"),
+                    vec![TyPattern::Path(path("std::vec::Vec"), vec![TyPattern::Any])],
+                )],
+            )
+        );
+        assert_eq!(
+            Parser::new("&mut str").finish(Parser::ty).unwrap(),
+            TyPattern::Ref(true, Box::new(TyPattern::Path(path("str"), vec![])))
+        );
+        assert!(Parser::new("Vec<u8").finish(Parser::ty).is_err());
+    }
+}
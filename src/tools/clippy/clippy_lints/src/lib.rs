@@ -65,6 +65,7 @@
 mod renamed_lints;
 
 // begin lints modules, do not remove this comment, it’s used in `update_lints`
+mod alloc_in_no_alloc_fn;
 mod allow_attributes;
 mod almost_complete_range;
 mod approx_const;
@@ -76,6 +77,7 @@
 mod async_yields_async;
 mod attrs;
 mod await_holding_invalid;
+mod blocking_in_async;
 mod blocks_in_if_conditions;
 mod bool_assert_comparison;
 mod bool_to_int_with_if;
@@ -105,6 +107,7 @@
 mod disallowed_macros;
 mod disallowed_methods;
 mod disallowed_names;
+mod disallowed_patterns;
 mod disallowed_script_idents;
 mod disallowed_types;
 mod doc;
@@ -249,6 +252,7 @@
 mod option_env_unwrap;
 mod option_if_let_else;
 mod overflow_check_conditional;
+mod panic_in_no_panic_fn;
 mod panic_in_result_fn;
 mod panic_unimplemented;
 mod partial_pub_fields;
@@ -603,6 +607,18 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     store.register_early_pass(|| Box::new(utils::format_args_collector::FormatArgsCollector));
     store.register_late_pass(|_| Box::new(utils::dump_hir::DumpHir));
     store.register_late_pass(|_| Box::new(utils::author::Author));
+    if let Some(dir) = std::env::var_os("CLIPPY_UNSAFE_INVENTORY") {
+        let dir = (dir != "-").then(|| PathBuf::from(dir));
+        let accept_comment_above_statement = conf.accept_comment_above_statement;
+        let accept_comment_above_attributes = conf.accept_comment_above_attributes;
+        store.register_late_pass(move |_| {
+            Box::new(utils::unsafe_inventory::UnsafeInventory::new(
+                dir.clone(),
+                accept_comment_above_statement,
+                accept_comment_above_attributes,
+            ))
+        });
+    }
     let await_holding_invalid_types = conf.await_holding_invalid_types.clone();
     store.register_late_pass(move |_| {
         Box::new(await_holding_invalid::AwaitHolding::new(
@@ -1078,6 +1094,24 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     store.register_early_pass(|| Box::new(visibility::Visibility));
     store.register_late_pass(move |_| Box::new(tuple_array_conversions::TupleArrayConversions { msrv: msrv() }));
     store.register_late_pass(|_| Box::new(manual_float_methods::ManualFloatMethods));
+    let disallowed_patterns = conf.disallowed_patterns.clone();
+    store.register_late_pass(move |_| {
+        Box::new(disallowed_patterns::DisallowedPatterns::new(
+            disallowed_patterns.clone(),
+        ))
+    });
+    let panicking_functions = conf.panicking_functions.clone();
+    store.register_late_pass(move |_| {
+        Box::new(panic_in_no_panic_fn::PanicInNoPanicFn::new(panicking_functions.clone()))
+    });
+    let blocking_functions = conf.blocking_functions.clone();
+    store.register_late_pass(move |_| Box::new(blocking_in_async::BlockingInAsync::new(blocking_functions.clone())));
+    let allocating_functions = conf.allocating_functions.clone();
+    store.register_late_pass(move |_| {
+        Box::new(alloc_in_no_alloc_fn::AllocInNoAllocFn::new(
+            allocating_functions.clone(),
+        ))
+    });
     // add lints here, do not remove this comment, it's used in `new_lint`
 }
 
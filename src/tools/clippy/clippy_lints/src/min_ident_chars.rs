@@ -1,10 +1,11 @@
+use clippy_utils::baseline::BaselineContext;
 use clippy_utils::diagnostics::span_lint;
 use clippy_utils::is_from_proc_macro;
 use rustc_data_structures::fx::FxHashSet;
 use rustc_hir::def::{DefKind, Res};
 use rustc_hir::intravisit::{walk_item, Visitor};
 use rustc_hir::{GenericParamKind, HirId, Item, ItemKind, ItemLocalId, Node, Pat, PatKind};
-use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::lint::in_external_macro;
 use rustc_session::{declare_tool_lint, impl_lint_pass};
 use rustc_span::Span;
@@ -138,7 +139,7 @@ fn visit_id(&mut self, hir_id: HirId) {
     }
 }
 
-fn emit_min_ident_chars(conf: &MinIdentChars, cx: &impl LintContext, ident: &str, span: Span) {
+fn emit_min_ident_chars(conf: &MinIdentChars, cx: &impl BaselineContext, ident: &str, span: Span) {
     let help = if conf.min_ident_chars_threshold == 1 {
         Cow::Borrowed("this ident consists of a single char")
     } else {
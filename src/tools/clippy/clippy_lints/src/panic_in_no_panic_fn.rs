@@ -0,0 +1,191 @@
+use clippy_utils::mir::call_graph::lint_reachable_sites;
+use clippy_utils::{def_path_def_ids, fn_has_unsatisfiable_preds, get_attr};
+use rustc_hir::def_id::{DefId, DefIdSet, LocalDefId};
+use rustc_hir::intravisit::FnKind;
+use rustc_hir::{Body, FnDecl};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::mir::{AssertKind, TerminatorKind};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::Span;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks functions marked `#[clippy::no_panic]`, and the local functions they call, for
+    /// code that may panic: calls to functions known to panic such as `Option::unwrap`, `panic!`
+    /// and assertions, indexing out of bounds, division by zero and, when overflow checks are
+    /// enabled, arithmetic overflow.
+    ///
+    /// Each way to panic is reported with the chain of calls leading to it. Functions known to
+    /// panic can be added with the `panicking-functions` configuration.
+    ///
+    /// Note: Even though this lint is warn-by-default, it will only trigger for functions marked
+    /// `#[clippy::no_panic]`.
+    ///
+    /// ### Why is this bad?
+    /// Panicking is not an option in some code, e.g. where unwinding across an FFI boundary is
+    /// undefined behavior, or in embedded code without a meaningful panic handler.
+    ///
+    /// ### Known problems
+    /// Only the MIR of functions in the current crate is analyzed. Functions from other crates
+    /// are assumed not to panic unless they are known to, and calls through function pointers or
+    /// trait objects are not followed. Drop glue is not checked.
+    ///
+    /// Functions which are themselves marked `#[clippy::no_panic]` are checked on their own and
+    /// not followed.
+    ///
+    /// ### Example
+    /// ```rust
+    /// #[clippy::no_panic]
+    /// extern "C" fn first(data: *const u8, len: usize) -> u8 {
+    ///     let data = unsafe { std::slice::from_raw_parts(data, len) };
+    ///     data[0]
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// #[clippy::no_panic]
+    /// extern "C" fn first(data: *const u8, len: usize) -> u8 {
+    ///     let data = unsafe { std::slice::from_raw_parts(data, len) };
+    ///     data.first().copied().unwrap_or(0)
+    /// }
+    /// ```
+    #[clippy::version = "1.73.0"]
+    pub PANIC_IN_NO_PANIC_FN,
+    suspicious,
+    "code that may panic in a function marked `#[clippy::no_panic]`"
+}
+
+/// The functions `panic!`, assertions and the like expand to.
+const PANIC_FUNCTIONS: &[&str] = &[
+    "core::panicking::panic",
+    "core::panicking::panic_fmt",
+    "core::panicking::panic_nounwind",
+    "core::panicking::panic_display",
+    "core::panicking::panic_str",
+    "core::panicking::unreachable_display",
+    "core::panicking::assert_failed",
+    "std::rt::begin_panic",
+    "std::panicking::begin_panic",
+    "std::panic::panic_any",
+];
+
+/// Functions which panic for some inputs.
+const PANICKING_FUNCTIONS: &[&str] = &[
+    "core::option::Option::unwrap",
+    "core::option::Option::expect",
+    "core::result::Result::unwrap",
+    "core::result::Result::expect",
+    "core::result::Result::unwrap_err",
+    "core::result::Result::expect_err",
+    "core::cell::RefCell::borrow",
+    "core::cell::RefCell::borrow_mut",
+    "core::ops::Index::index",
+    "core::ops::IndexMut::index_mut",
+];
+
+pub struct PanicInNoPanicFn {
+    conf_panicking: Vec<String>,
+    panics: DefIdSet,
+    panicking: DefIdSet,
+}
+
+impl PanicInNoPanicFn {
+    pub fn new(conf_panicking: Vec<String>) -> Self {
+        Self {
+            conf_panicking,
+            panics: DefIdSet::default(),
+            panicking: DefIdSet::default(),
+        }
+    }
+
+    /// Why calling `def_id` may panic, if it may.
+    fn call_panics(&self, cx: &LateContext<'_>, def_id: DefId) -> Option<String> {
+        if self.panics.contains(&def_id) {
+            Some("explicit panic".to_string())
+        } else if self.panicking.contains(&def_id) {
+            Some(format!("`{}` may panic", cx.tcx.def_path_str(def_id)))
+        } else {
+            None
+        }
+    }
+}
+
+impl_lint_pass!(PanicInNoPanicFn => [PANIC_IN_NO_PANIC_FN]);
+
+fn is_no_panic(cx: &LateContext<'_>, def_id: DefId) -> bool {
+    get_attr(cx.sess(), cx.tcx.get_attrs_unchecked(def_id), "no_panic")
+        .next()
+        .is_some()
+}
+
+impl<'tcx> LateLintPass<'tcx> for PanicInNoPanicFn {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        for path in PANIC_FUNCTIONS {
+            let segs: Vec<_> = path.split("::").collect();
+            self.panics.extend(def_path_def_ids(cx, &segs));
+        }
+        for path in PANICKING_FUNCTIONS
+            .iter()
+            .copied()
+            .chain(self.conf_panicking.iter().map(String::as_str))
+        {
+            let segs: Vec<_> = path.split("::").collect();
+            self.panicking.extend(def_path_def_ids(cx, &segs));
+        }
+    }
+
+    fn check_fn(
+        &mut self,
+        cx: &LateContext<'tcx>,
+        kind: FnKind<'tcx>,
+        _: &'tcx FnDecl<'_>,
+        _: &'tcx Body<'_>,
+        span: Span,
+        def_id: LocalDefId,
+    ) {
+        if matches!(kind, FnKind::Closure)
+            || !is_no_panic(cx, def_id.to_def_id())
+            || in_external_macro(cx.sess(), span)
+            // Building MIR for `fn`s with unsatisfiable preds results in ICE.
+            || fn_has_unsatisfiable_preds(cx, def_id.to_def_id())
+        {
+            return;
+        }
+
+        let overflow_checks = cx.sess().overflow_checks();
+        lint_reachable_sites(
+            cx,
+            PANIC_IN_NO_PANIC_FN,
+            def_id,
+            "no_panic",
+            "panic",
+            |call| self.call_panics(cx, call.def_id).is_none() && !is_no_panic(cx, call.callee()),
+            |calls, body| {
+                let mut sites = Vec::new();
+                for call in calls {
+                    if let Some(reason) = self
+                        .call_panics(cx, call.def_id)
+                        .or_else(|| self.call_panics(cx, call.callee()))
+                    {
+                        sites.push((call.span, reason));
+                    }
+                }
+                for data in body.basic_blocks.iter() {
+                    let terminator = data.terminator();
+                    if let TerminatorKind::Assert { msg, .. } = &terminator.kind
+                        && (overflow_checks || !msg.is_optional_overflow_check())
+                    {
+                        let reason = match &**msg {
+                            AssertKind::BoundsCheck { .. } => "index out of bounds",
+                            AssertKind::MisalignedPointerDereference { .. } => "misaligned pointer dereference",
+                            msg => msg.description(),
+                        };
+                        sites.push((terminator.source_info.span, reason.to_string()));
+                    }
+                }
+                sites
+            },
+        );
+    }
+}
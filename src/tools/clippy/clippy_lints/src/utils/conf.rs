@@ -68,6 +68,31 @@ pub fn reason(&self) -> Option<String> {
     }
 }
 
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum BlockingFunction {
+    Simple(String),
+    WithReplacement { path: String, replacement: Option<String> },
+}
+
+impl BlockingFunction {
+    pub fn path(&self) -> &str {
+        let (Self::Simple(path) | Self::WithReplacement { path, .. }) = self;
+
+        path
+    }
+
+    pub fn replacement(&self) -> Option<&str> {
+        match self {
+            Self::WithReplacement {
+                replacement: Some(replacement),
+                ..
+            } => Some(replacement),
+            _ => None,
+        }
+    }
+}
+
 /// Conf with parse errors
 #[derive(Default)]
 pub struct TryConf {
@@ -424,6 +449,10 @@ pub fn get_configuration_metadata() -> Vec<ClippyConfiguration> {
     ///
     /// The list of disallowed types, written as fully qualified paths.
     (disallowed_types: Vec<crate::utils::conf::DisallowedPath> = Vec::new()),
+    /// Lint: DISALLOWED_PATTERNS.
+    ///
+    /// The list of disallowed expression and type patterns, each with a `message`.
+    (disallowed_patterns: Vec<crate::disallowed_patterns::DisallowedPattern> = Vec::new()),
     /// Lint: UNREADABLE_LITERAL.
     ///
     /// Should the fraction of a decimal be linted to include separators.
@@ -551,6 +580,21 @@ pub fn get_configuration_metadata() -> Vec<ClippyConfiguration> {
     ///
     /// Whether to allow `r#""#` when `r""` can be used
     (allow_one_hash_in_raw_strings: bool = false),
+    /// Lint: PANIC_IN_NO_PANIC_FN.
+    ///
+    /// Additional functions that may panic, written as fully qualified paths, which functions marked
+    /// `#[clippy::no_panic]` must not call.
+    (panicking_functions: Vec<String> = Vec::new()),
+    /// Lint: BLOCKING_CALL_IN_ASYNC, INDIRECT_BLOCKING_CALL_IN_ASYNC.
+    ///
+    /// Additional blocking functions, written as fully qualified paths, optionally with an async
+    /// `replacement` to suggest, e.g. `{ path = "std::fs::read", replacement = "tokio::fs::read" }`.
+    (blocking_functions: Vec<crate::utils::conf::BlockingFunction> = Vec::new()),
+    /// Lint: ALLOC_IN_NO_ALLOC_FN.
+    ///
+    /// Additional functions that may allocate, written as fully qualified paths, which functions
+    /// marked `#[clippy::no_alloc]` must not call.
+    (allocating_functions: Vec<String> = Vec::new()),
 }
 
 /// Search for the configuration file.
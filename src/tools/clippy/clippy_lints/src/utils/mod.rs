@@ -4,6 +4,7 @@
 pub mod format_args_collector;
 #[cfg(feature = "internal")]
 pub mod internal_lints;
+pub mod unsafe_inventory;
 #[cfg(feature = "internal")]
 use itertools::Itertools;
 
@@ -0,0 +1,438 @@
+//! Collects an inventory of the unsafe code in a crate for security reviews.
+//!
+//! `cargo clippy --unsafe-inventory <dir>` passes `<dir>` on to `clippy-driver` in the
+//! `CLIPPY_UNSAFE_INVENTORY` environment variable, and this pass then writes
+//! `<dir>/<crate>.<lib|bin|proc-macro|test>.json` for every crate checked. Setting the variable to
+//! `-` writes the inventory to stdout instead.
+//!
+//! Cargo doesn't run Clippy on crates that are up to date, so only a clean build writes the
+//! inventory of every crate.
+//!
+//! The inventory lists every `unsafe` block, `unsafe fn`, `unsafe impl` and `unsafe trait`, every
+//! `#[no_mangle]` or `#[export_name]` item, function with a non-Rust ABI and item in an `extern`
+//! block, whether it has a `// SAFETY:` comment as accepted by `undocumented_unsafe_blocks`, and
+//! the unsafe operations performed by unsafe blocks and functions.
+
+use crate::undocumented_unsafe_blocks::{
+    block_has_safety_comment, block_parents_have_safety_comment, item_has_safety_comment, HasSafetyComment,
+};
+use rustc_hir::def_id::{DefId, LOCAL_CRATE};
+use rustc_hir::intravisit::{walk_block, walk_expr, FnKind, Visitor};
+use rustc_hir::{
+    self as hir, Block, BlockCheckMode, Body, Expr, ExprKind, FnDecl, ForeignItemKind, HirId, ItemKind, Node, UnOp,
+    UnsafeSource, Unsafety,
+};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::hir::nested_filter;
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty;
+use rustc_session::config::CrateType;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::def_id::LocalDefId;
+use rustc_span::{sym, Span};
+use rustc_target::spec::abi::Abi;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+
+struct Inventory {
+    krate: String,
+    entries: Vec<Entry>,
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct Entry {
+    file: String,
+    line: usize,
+    column: usize,
+    kind: &'static str,
+    item: String,
+    /// `None` if Clippy doesn't know where to look for a safety comment.
+    safety_comment: Option<bool>,
+    /// The unsafe operations performed directly, i.e. outside of nested `unsafe` blocks, and how
+    /// often each is performed.
+    operations: BTreeMap<&'static str, usize>,
+}
+
+impl Inventory {
+    /// Formats the inventory as pretty-printed JSON. It's written by hand so Clippy doesn't need
+    /// `serde_json` outside of the internal lints.
+    fn to_json(&self) -> String {
+        let mut json = format!("{{\n  \"crate\": {},\n  \"entries\": [", json_string(&self.krate));
+        for (i, entry) in self.entries.iter().enumerate() {
+            json.push_str(if i == 0 { "\n    {\n" } else { ",\n    {\n" });
+            let safety_comment = match entry.safety_comment {
+                Some(true) => "true",
+                Some(false) => "false",
+                None => "null",
+            };
+            let fields = [
+                ("file", json_string(&entry.file)),
+                ("line", entry.line.to_string()),
+                ("column", entry.column.to_string()),
+                ("kind", json_string(entry.kind)),
+                ("item", json_string(&entry.item)),
+                ("safety_comment", safety_comment.to_string()),
+            ];
+            for (key, value) in fields {
+                let _ = writeln!(json, "      \"{key}\": {value},");
+            }
+            json.push_str("      \"operations\": {");
+            for (j, (operation, count)) in entry.operations.iter().enumerate() {
+                let separator = if j == 0 { "\n" } else { ",\n" };
+                let _ = write!(json, "{separator}        {}: {count}", json_string(operation));
+            }
+            if !entry.operations.is_empty() {
+                json.push_str("\n      ");
+            }
+            json.push_str("}\n    }");
+        }
+        if !self.entries.is_empty() {
+            json.push_str("\n  ");
+        }
+        json.push_str("]\n}");
+        json
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c < ' ' => {
+                let _ = write!(json, "\\u{:04x}", u32::from(c));
+            },
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+pub struct UnsafeInventory {
+    /// The directory to write the inventory to, or `None` for stdout.
+    dir: Option<PathBuf>,
+    accept_comment_above_statement: bool,
+    accept_comment_above_attributes: bool,
+    entries: Vec<Entry>,
+}
+
+impl UnsafeInventory {
+    pub fn new(
+        dir: Option<PathBuf>,
+        accept_comment_above_statement: bool,
+        accept_comment_above_attributes: bool,
+    ) -> Self {
+        Self {
+            dir,
+            accept_comment_above_statement,
+            accept_comment_above_attributes,
+            entries: Vec::new(),
+        }
+    }
+
+    fn push(
+        &mut self,
+        cx: &LateContext<'_>,
+        span: Span,
+        kind: &'static str,
+        item: DefId,
+        safety_comment: Option<bool>,
+        operations: BTreeMap<&'static str, usize>,
+    ) {
+        let source_map = cx.sess().source_map();
+        let loc = source_map.lookup_char_pos(span.source_callsite().lo());
+        self.entries.push(Entry {
+            file: loc.file.name.prefer_local().to_string().replace('\\', "/"),
+            line: loc.line,
+            column: loc.col.0 + 1,
+            kind,
+            item: cx.tcx.def_path_str(item),
+            safety_comment,
+            operations,
+        });
+    }
+
+    /// Records the `#[no_mangle]`, `#[export_name]` and non-Rust ABI functions and statics.
+    fn check_exported(
+        &mut self,
+        cx: &LateContext<'_>,
+        hir_id: HirId,
+        span: Span,
+        def_id: LocalDefId,
+        abi: Option<Abi>,
+    ) {
+        let safety_comment = item_safety_comment(cx, hir_id);
+        let attrs = cx.tcx.hir().attrs(hir_id);
+        if attrs.iter().any(|attr| attr.has_name(sym::no_mangle)) {
+            self.push(
+                cx,
+                span,
+                "no_mangle",
+                def_id.to_def_id(),
+                safety_comment,
+                BTreeMap::new(),
+            );
+        }
+        if attrs.iter().any(|attr| attr.has_name(sym::export_name)) {
+            self.push(
+                cx,
+                span,
+                "export_name",
+                def_id.to_def_id(),
+                safety_comment,
+                BTreeMap::new(),
+            );
+        }
+        if abi.map_or(false, |abi| abi != Abi::Rust) {
+            self.push(
+                cx,
+                span,
+                "extern_fn",
+                def_id.to_def_id(),
+                safety_comment,
+                BTreeMap::new(),
+            );
+        }
+    }
+}
+
+impl_lint_pass!(UnsafeInventory => []);
+
+fn item_safety_comment(cx: &LateContext<'_>, hir_id: HirId) -> Option<bool> {
+    match cx.tcx.hir().find(hir_id) {
+        Some(Node::Item(item)) => match item_has_safety_comment(cx, item) {
+            HasSafetyComment::Yes(_) => Some(true),
+            HasSafetyComment::No => Some(false),
+            HasSafetyComment::Maybe => None,
+        },
+        _ => None,
+    }
+}
+
+fn crate_kind(cx: &LateContext<'_>) -> &'static str {
+    if cx.sess().is_test_crate() {
+        "test"
+    } else {
+        match cx.sess().crate_types().first() {
+            Some(CrateType::Executable) => "bin",
+            Some(CrateType::ProcMacro) => "proc-macro",
+            _ => "lib",
+        }
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for UnsafeInventory {
+    fn check_block(&mut self, cx: &LateContext<'tcx>, block: &'tcx Block<'tcx>) {
+        if block.rules == BlockCheckMode::UnsafeBlock(UnsafeSource::UserProvided)
+            && !in_external_macro(cx.sess(), block.span)
+        {
+            let safety_comment = block_has_safety_comment(cx, block.span)
+                || block_parents_have_safety_comment(
+                    self.accept_comment_above_statement,
+                    self.accept_comment_above_attributes,
+                    cx,
+                    block.hir_id,
+                );
+            let mut v = UnsafeOperations::new(cx);
+            walk_block(&mut v, block);
+            let owner = cx.tcx.hir().enclosing_body_owner(block.hir_id);
+            self.push(
+                cx,
+                block.span,
+                "unsafe_block",
+                owner.to_def_id(),
+                Some(safety_comment),
+                v.operations,
+            );
+        }
+    }
+
+    fn check_fn(
+        &mut self,
+        cx: &LateContext<'tcx>,
+        kind: FnKind<'tcx>,
+        _: &'tcx FnDecl<'_>,
+        body: &'tcx Body<'_>,
+        span: Span,
+        def_id: LocalDefId,
+    ) {
+        let header = match kind {
+            FnKind::ItemFn(_, _, header) => header,
+            FnKind::Method(_, sig) => sig.header,
+            FnKind::Closure => return,
+        };
+        if header.unsafety == Unsafety::Unsafe && !in_external_macro(cx.sess(), span) {
+            let hir_id = cx.tcx.hir().local_def_id_to_hir_id(def_id);
+            let mut v = UnsafeOperations::new(cx);
+            v.visit_expr(body.value);
+            self.push(
+                cx,
+                span,
+                "unsafe_fn",
+                def_id.to_def_id(),
+                item_safety_comment(cx, hir_id),
+                v.operations,
+            );
+        }
+    }
+
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::Item<'_>) {
+        if in_external_macro(cx.sess(), item.span) {
+            return;
+        }
+        let kind = match item.kind {
+            ItemKind::Impl(impl_) if impl_.unsafety == Unsafety::Unsafe => "unsafe_impl",
+            ItemKind::Trait(_, Unsafety::Unsafe, ..) => "unsafe_trait",
+            ItemKind::Fn(sig, ..) => {
+                self.check_exported(cx, item.hir_id(), item.span, item.owner_id.def_id, Some(sig.header.abi));
+                return;
+            },
+            ItemKind::Static(..) => {
+                self.check_exported(cx, item.hir_id(), item.span, item.owner_id.def_id, None);
+                return;
+            },
+            _ => return,
+        };
+        let safety_comment = item_safety_comment(cx, item.hir_id());
+        self.push(
+            cx,
+            item.span,
+            kind,
+            item.owner_id.to_def_id(),
+            safety_comment,
+            BTreeMap::new(),
+        );
+    }
+
+    fn check_impl_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::ImplItem<'_>) {
+        if let hir::ImplItemKind::Fn(sig, _) = item.kind
+            && !in_external_macro(cx.sess(), item.span)
+        {
+            self.check_exported(cx, item.hir_id(), item.span, item.owner_id.def_id, Some(sig.header.abi));
+        }
+    }
+
+    fn check_foreign_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::ForeignItem<'_>) {
+        if in_external_macro(cx.sess(), item.span) {
+            return;
+        }
+        let kind = match item.kind {
+            ForeignItemKind::Fn(..) => "foreign_fn",
+            ForeignItemKind::Static(..) => "foreign_static",
+            ForeignItemKind::Type => "foreign_type",
+        };
+        self.push(cx, item.span, kind, item.owner_id.to_def_id(), None, BTreeMap::new());
+    }
+
+    fn check_crate_post(&mut self, cx: &LateContext<'tcx>) {
+        let mut entries = std::mem::take(&mut self.entries);
+        entries.sort();
+        let inventory = Inventory {
+            krate: cx.tcx.crate_name(LOCAL_CRATE).to_string(),
+            entries,
+        };
+        let json = inventory.to_json();
+        match &self.dir {
+            None => println!("{json}"),
+            Some(dir) => {
+                let path = dir.join(format!("{}.{}.json", inventory.krate, crate_kind(cx)));
+                if let Err(e) = fs::write(&path, json + "\n") {
+                    cx.sess()
+                        .err(format!("failed to write unsafe inventory `{}`: {e}", path.display()));
+                }
+            },
+        }
+    }
+}
+
+/// Counts the unsafe operations in an unsafe block or function, skipping nested `unsafe` blocks
+/// as they are recorded separately.
+struct UnsafeOperations<'a, 'tcx> {
+    cx: &'a LateContext<'tcx>,
+    operations: BTreeMap<&'static str, usize>,
+}
+
+impl<'a, 'tcx> UnsafeOperations<'a, 'tcx> {
+    fn new(cx: &'a LateContext<'tcx>) -> Self {
+        Self {
+            cx,
+            operations: BTreeMap::new(),
+        }
+    }
+
+    fn add(&mut self, operation: &'static str) {
+        *self.operations.entry(operation).or_default() += 1;
+    }
+
+    fn add_call(&mut self, def_id: DefId) {
+        if self.cx.tcx.is_foreign_item(def_id) {
+            self.add("ffi_call");
+        } else if self.cx.tcx.fn_sig(def_id).skip_binder().unsafety() == Unsafety::Unsafe {
+            self.add("unsafe_call");
+        }
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for UnsafeOperations<'_, 'tcx> {
+    type NestedFilter = nested_filter::OnlyBodies;
+
+    fn nested_visit_map(&mut self) -> Self::Map {
+        self.cx.tcx.hir()
+    }
+
+    fn visit_expr(&mut self, e: &'tcx Expr<'_>) {
+        let typeck = self.cx.typeck_results();
+        match e.kind {
+            ExprKind::Unary(UnOp::Deref, inner) if typeck.expr_ty(inner).is_unsafe_ptr() => {
+                self.add("raw_pointer_deref");
+            },
+            ExprKind::MethodCall(..) => {
+                if let Some(def_id) = typeck.type_dependent_def_id(e.hir_id) {
+                    self.add_call(def_id);
+                }
+            },
+            ExprKind::Call(func, _) => match *typeck.expr_ty(func).peel_refs().kind() {
+                ty::FnDef(def_id, _) => self.add_call(def_id),
+                ty::FnPtr(sig) if sig.unsafety() == Unsafety::Unsafe => self.add("unsafe_call"),
+                _ => {},
+            },
+            ExprKind::Path(ref path) => {
+                if let Some(def_id) = self.cx.qpath_res(path, e.hir_id).opt_def_id() {
+                    if self.cx.tcx.is_mutable_static(def_id) {
+                        self.add("static_mut");
+                    } else if self.cx.tcx.is_foreign_item(def_id) && self.cx.tcx.is_static(def_id) {
+                        self.add("extern_static");
+                    }
+                }
+            },
+            // Writing to a union field is safe, reading from it isn't
+            ExprKind::Assign(lhs, rhs, _) => {
+                if let ExprKind::Field(base, _) = lhs.kind
+                    && typeck.expr_ty(base).is_union()
+                {
+                    self.visit_expr(base);
+                    self.visit_expr(rhs);
+                    return;
+                }
+            },
+            ExprKind::Field(base, _) if typeck.expr_ty(base).is_union() => self.add("union_field"),
+            ExprKind::InlineAsm(_) => self.add("inline_asm"),
+            _ => {},
+        }
+        walk_expr(self, e);
+    }
+
+    fn visit_block(&mut self, b: &'tcx Block<'_>) {
+        if b.rules != BlockCheckMode::UnsafeBlock(UnsafeSource::UserProvided) {
+            walk_block(self, b);
+        }
+    }
+}
@@ -22,6 +22,8 @@ pub enum DeprecationStatus {
     ("dump",                  DeprecationStatus::None),
     ("msrv",                  DeprecationStatus::None),
     ("has_significant_drop",  DeprecationStatus::None),
+    ("no_panic",              DeprecationStatus::None),
+    ("no_alloc",              DeprecationStatus::None),
 ];
 
 pub struct LimitStack {
@@ -0,0 +1,246 @@
+//! Support for a warnings baseline, so stricter lints can be adopted without fixing every existing
+//! warning first.
+//!
+//! `cargo clippy --write-baseline <file>` records every emitted Clippy lint in `<file>`, and
+//! `cargo clippy --baseline <file>` then only reports the lints not recorded there. `cargo clippy`
+//! passes the file on to `clippy-driver` in the `CLIPPY_WRITE_BASELINE` and `CLIPPY_BASELINE`
+//! environment variables respectively.
+//!
+//! A finding is recorded by its lint name, file, item path and a fingerprint of the linted code,
+//! one finding per line. Line numbers are left out so the baseline still applies after unrelated
+//! edits move code around.
+//!
+//! Only lints emitted through the helpers in [`crate::diagnostics`] go through the baseline, so
+//! Clippy lints must not call `struct_span_lint` and friends directly.
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_errors::MultiSpan;
+use rustc_hir::HirId;
+use rustc_lint::{EarlyContext, LateContext, Level, Lint, LintContext};
+use rustc_session::Session;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
+use std::{env, fmt};
+
+/// Lint contexts that can tell which item a lint is emitted in.
+pub trait BaselineContext: LintContext {
+    /// The path of the item the lint is emitted in, if known.
+    fn baseline_item(&self) -> Option<String>;
+}
+
+impl BaselineContext for EarlyContext<'_> {
+    fn baseline_item(&self) -> Option<String> {
+        None
+    }
+}
+
+impl BaselineContext for LateContext<'_> {
+    fn baseline_item(&self) -> Option<String> {
+        Some(item_path(self, self.last_node_with_lint_attrs))
+    }
+}
+
+fn item_path(cx: &LateContext<'_>, hir_id: HirId) -> String {
+    cx.tcx.def_path_str(hir_id.owner.to_def_id())
+}
+
+/// A single recorded finding.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Finding {
+    pub lint: String,
+    pub file: String,
+    pub item: String,
+    pub fingerprint: u64,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{:016x}",
+            self.lint, self.file, self.item, self.fingerprint
+        )
+    }
+}
+
+impl FromStr for Finding {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut fields = line.split('\t');
+        if let (Some(lint), Some(file), Some(item), Some(fingerprint), None) = (
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+        ) && let Ok(fingerprint) = u64::from_str_radix(fingerprint, 16)
+        {
+            Ok(Self {
+                lint: lint.to_string(),
+                file: file.to_string(),
+                item: item.to_string(),
+                fingerprint,
+            })
+        } else {
+            Err(format!("malformed baseline entry `{line}`"))
+        }
+    }
+}
+
+/// Hashes `code` ignoring all whitespace, so reformatting doesn't invalidate the baseline.
+///
+/// This is FNV-1a, which unlike the hashers used in the compiler is stable across platforms and
+/// releases.
+pub fn fingerprint(code: &str) -> u64 {
+    code.bytes()
+        .filter(|b| !b.is_ascii_whitespace())
+        .fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+            (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+        })
+}
+
+/// Parses the contents of a baseline file into the number of times each finding was recorded.
+pub fn parse(contents: &str) -> Result<FxHashMap<Finding, usize>, String> {
+    let mut findings = FxHashMap::default();
+    for line in contents.lines() {
+        let line = line.trim_end_matches('\r');
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        *findings.entry(line.parse()?).or_default() += 1;
+    }
+    Ok(findings)
+}
+
+enum Baseline {
+    /// Findings still to be suppressed.
+    Check(Mutex<FxHashMap<Finding, usize>>),
+    /// Emitted findings are appended to this file.
+    Write(Mutex<File>),
+}
+
+static BASELINE: OnceLock<Option<Baseline>> = OnceLock::new();
+
+fn load(sess: &Session) -> Option<Baseline> {
+    if let Some(path) = env::var_os("CLIPPY_WRITE_BASELINE") {
+        match OpenOptions::new().create(true).append(true).open(&path) {
+            Ok(file) => Some(Baseline::Write(Mutex::new(file))),
+            Err(e) => {
+                sess.err(format!("failed to open baseline `{}`: {e}", path.to_string_lossy()));
+                None
+            },
+        }
+    } else if let Some(path) = env::var_os("CLIPPY_BASELINE") {
+        match fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|s| parse(&s))
+        {
+            Ok(findings) => Some(Baseline::Check(Mutex::new(findings))),
+            Err(e) => {
+                sess.err(format!("failed to read baseline `{}`: {e}", path.to_string_lossy()));
+                None
+            },
+        }
+    } else {
+        None
+    }
+}
+
+/// Returns `true` if the lint is recorded in the baseline and should not be emitted.
+pub fn is_baselined<T: BaselineContext>(cx: &T, lint: &'static Lint, span: &MultiSpan) -> bool {
+    check(cx.sess(), lint, cx.get_lint_level(lint), || cx.baseline_item(), span)
+}
+
+/// Like [`is_baselined`], for a lint emitted at `hir_id` rather than at the current node.
+pub fn is_baselined_hir(cx: &LateContext<'_>, lint: &'static Lint, hir_id: HirId, span: &MultiSpan) -> bool {
+    let level = cx.tcx.lint_level_at_node(lint, hir_id).0;
+    check(cx.sess(), lint, level, || Some(item_path(cx, hir_id)), span)
+}
+
+fn check(
+    sess: &Session,
+    lint: &'static Lint,
+    level: Level,
+    item: impl FnOnce() -> Option<String>,
+    span: &MultiSpan,
+) -> bool {
+    let Some(baseline) = BASELINE.get_or_init(|| load(sess)) else {
+        return false;
+    };
+    // Allowed and expected lints are never emitted, so there is nothing to record or suppress.
+    if matches!(level, Level::Allow | Level::Expect(_)) {
+        return false;
+    }
+    let Some(span) = span.primary_span() else {
+        return false;
+    };
+    let span = span.source_callsite();
+    let source_map = sess.source_map();
+    let finding = Finding {
+        lint: lint.name_lower(),
+        file: source_map
+            .span_to_filename(span)
+            .prefer_local()
+            .to_string()
+            .replace('\\', "/"),
+        item: item().unwrap_or_default(),
+        fingerprint: fingerprint(&source_map.span_to_snippet(span).unwrap_or_default()),
+    };
+
+    match baseline {
+        Baseline::Check(findings) => {
+            let mut findings = findings.lock().unwrap();
+            match findings.get_mut(&finding) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    true
+                },
+                _ => false,
+            }
+        },
+        Baseline::Write(file) => {
+            // A single `write` per finding, as several `clippy-driver`s may append concurrently.
+            if let Err(e) = file.lock().unwrap().write_all(format!("{finding}\n").as_bytes()) {
+                sess.err(format!("failed to write baseline: {e}"));
+            }
+            false
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{fingerprint, parse, Finding};
+
+    #[test]
+    fn fingerprint_ignores_whitespace() {
+        assert_eq!(fingerprint("let x = 1;"), fingerprint("let  x\n    = 1;"));
+        assert_ne!(fingerprint("let x = 1;"), fingerprint("let y = 1;"));
+    }
+
+    #[test]
+    fn round_trip() {
+        let finding = Finding {
+            lint: "clippy::needless_return".to_string(),
+            file: "src/lib.rs".to_string(),
+            item: "foo::bar".to_string(),
+            fingerprint: 0x0123_4567_89ab_cdef,
+        };
+        let line = finding.to_string();
+        assert_eq!(line, "clippy::needless_return\tsrc/lib.rs\tfoo::bar\t0123456789abcdef");
+        assert_eq!(line.parse::<Finding>(), Ok(finding));
+    }
+
+    #[test]
+    fn parse_counts_duplicates() {
+        let contents = "# comment\n\na\tsrc/lib.rs\t\t01\na\tsrc/lib.rs\t\t01\r\nb\tsrc/main.rs\tmain\t02\n";
+        let findings = parse(contents).unwrap();
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings.values().sum::<usize>(), 3);
+        assert!(parse("a\tsrc/lib.rs\t01").is_err());
+        assert!(parse("a\tsrc/lib.rs\t\tnot-hex").is_err());
+    }
+}
@@ -8,9 +8,10 @@
 //! Thank you!
 //! ~The `INTERNAL_METADATA_COLLECTOR` lint
 
+use crate::baseline::{is_baselined, is_baselined_hir, BaselineContext};
 use rustc_errors::{Applicability, Diagnostic, MultiSpan};
 use rustc_hir::HirId;
-use rustc_lint::{LateContext, Lint, LintContext};
+use rustc_lint::{LateContext, Lint};
 use rustc_span::source_map::Span;
 use std::env;
 
@@ -45,7 +46,11 @@ fn docs_link(diag: &mut Diagnostic, lint: &'static Lint) {
 /// 17 |     std::mem::forget(seven);
 ///    |     ^^^^^^^^^^^^^^^^^^^^^^^
 /// ```
-pub fn span_lint<T: LintContext>(cx: &T, lint: &'static Lint, sp: impl Into<MultiSpan>, msg: &str) {
+pub fn span_lint<T: BaselineContext>(cx: &T, lint: &'static Lint, sp: impl Into<MultiSpan>, msg: &str) {
+    let sp = sp.into();
+    if is_baselined(cx, lint, &sp) {
+        return;
+    }
     cx.struct_span_lint(lint, sp, msg.to_string(), |diag| {
         docs_link(diag, lint);
         diag
@@ -72,7 +77,7 @@ pub fn span_lint<T: LintContext>(cx: &T, lint: &'static Lint, sp: impl Into<Mult
 ///    |
 ///    = help: consider using `f64::NAN` if you would like a constant representing NaN
 /// ```
-pub fn span_lint_and_help<T: LintContext>(
+pub fn span_lint_and_help<T: BaselineContext>(
     cx: &T,
     lint: &'static Lint,
     span: impl Into<MultiSpan>,
@@ -80,6 +85,10 @@ pub fn span_lint_and_help<T: LintContext>(
     help_span: Option<Span>,
     help: &str,
 ) {
+    let span = span.into();
+    if is_baselined(cx, lint, &span) {
+        return;
+    }
     cx.struct_span_lint(lint, span, msg.to_string(), |diag| {
         let help = help.to_string();
         if let Some(help_span) = help_span {
@@ -115,7 +124,7 @@ pub fn span_lint_and_help<T: LintContext>(
 /// 10 |     forget(&SomeStruct);
 ///    |            ^^^^^^^^^^^
 /// ```
-pub fn span_lint_and_note<T: LintContext>(
+pub fn span_lint_and_note<T: BaselineContext>(
     cx: &T,
     lint: &'static Lint,
     span: impl Into<MultiSpan>,
@@ -123,6 +132,10 @@ pub fn span_lint_and_note<T: LintContext>(
     note_span: Option<Span>,
     note: &str,
 ) {
+    let span = span.into();
+    if is_baselined(cx, lint, &span) {
+        return;
+    }
     cx.struct_span_lint(lint, span, msg.to_string(), |diag| {
         let note = note.to_string();
         if let Some(note_span) = note_span {
@@ -141,10 +154,14 @@ pub fn span_lint_and_note<T: LintContext>(
 /// If you change the signature, remember to update the internal lint `CollapsibleCalls`
 pub fn span_lint_and_then<C, S, F>(cx: &C, lint: &'static Lint, sp: S, msg: &str, f: F)
 where
-    C: LintContext,
+    C: BaselineContext,
     S: Into<MultiSpan>,
     F: FnOnce(&mut Diagnostic),
 {
+    let sp = sp.into();
+    if is_baselined(cx, lint, &sp) {
+        return;
+    }
     cx.struct_span_lint(lint, sp, msg.to_string(), |diag| {
         f(diag);
         docs_link(diag, lint);
@@ -153,6 +170,9 @@ pub fn span_lint_and_then<C, S, F>(cx: &C, lint: &'static Lint, sp: S, msg: &str
 }
 
 pub fn span_lint_hir(cx: &LateContext<'_>, lint: &'static Lint, hir_id: HirId, sp: Span, msg: &str) {
+    if is_baselined_hir(cx, lint, hir_id, &sp.into()) {
+        return;
+    }
     cx.tcx.struct_span_lint_hir(lint, hir_id, sp, msg.to_string(), |diag| {
         docs_link(diag, lint);
         diag
@@ -167,6 +187,10 @@ pub fn span_lint_hir_and_then(
     msg: &str,
     f: impl FnOnce(&mut Diagnostic),
 ) {
+    let sp = sp.into();
+    if is_baselined_hir(cx, lint, hir_id, &sp) {
+        return;
+    }
     cx.tcx.struct_span_lint_hir(lint, hir_id, sp, msg.to_string(), |diag| {
         f(diag);
         docs_link(diag, lint);
@@ -194,7 +218,7 @@ pub fn span_lint_hir_and_then(
 ///     = note: `-D fold-any` implied by `-D warnings`
 /// ```
 #[cfg_attr(feature = "internal", allow(clippy::collapsible_span_lint_calls))]
-pub fn span_lint_and_sugg<T: LintContext>(
+pub fn span_lint_and_sugg<T: BaselineContext>(
     cx: &T,
     lint: &'static Lint,
     sp: Span,
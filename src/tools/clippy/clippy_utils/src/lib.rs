@@ -44,6 +44,7 @@
 
 pub mod ast_utils;
 pub mod attrs;
+pub mod baseline;
 mod check_proc_macro;
 pub mod comparisons;
 pub mod consts;
@@ -0,0 +1,179 @@
+//! Walking the calls a function makes, and transitively the calls made by the local functions it
+//! calls.
+
+use crate::diagnostics::span_lint_and_then;
+use crate::fn_has_unsatisfiable_preds;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_errors::Diagnostic;
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_lint::{LateContext, Lint};
+use rustc_middle::mir::{Body, Location, TerminatorKind};
+use rustc_middle::ty::{self, EarlyBinder, GenericArgs, Instance, InstanceDef, ParamEnv, TyCtxt};
+use rustc_span::Span;
+use std::collections::VecDeque;
+
+/// A call found in a MIR body.
+#[derive(Clone, Copy, Debug)]
+pub struct Call<'tcx> {
+    /// The function as written, e.g. the trait method for a call through a trait.
+    pub def_id: DefId,
+    /// The function actually called, if it could be resolved.
+    pub instance: Option<Instance<'tcx>>,
+    pub location: Location,
+    pub span: Span,
+}
+
+impl Call<'_> {
+    /// The resolved function if known, otherwise the function as written.
+    pub fn callee(&self) -> DefId {
+        self.instance.map_or(self.def_id, |instance| instance.def_id())
+    }
+}
+
+/// One call on the path from the function the walk started at to a function it reaches.
+#[derive(Clone, Copy, Debug)]
+pub struct CallStep {
+    pub callee: DefId,
+    pub span: Span,
+}
+
+/// Returns the calls to statically known functions in `body`, the MIR of `instance`.
+///
+/// Generic arguments are substituted with those of `instance`, so calls through traits are
+/// resolved to the implementation whenever the types involved are known.
+pub fn calls<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ParamEnv<'tcx>,
+    instance: Instance<'tcx>,
+    body: &Body<'tcx>,
+) -> Vec<Call<'tcx>> {
+    let mut calls = Vec::new();
+    for (block, data) in body.basic_blocks.iter_enumerated() {
+        let terminator = data.terminator();
+        let TerminatorKind::Call { func, .. } = &terminator.kind else {
+            continue;
+        };
+        let func_ty =
+            instance.subst_mir_and_normalize_erasing_regions(tcx, param_env, EarlyBinder::bind(func.ty(body, tcx)));
+        if let ty::FnDef(def_id, args) = *func_ty.kind() {
+            calls.push(Call {
+                def_id,
+                instance: Instance::resolve(tcx, param_env, def_id, args).ok().flatten(),
+                location: body.terminator_loc(block),
+                span: terminator.source_info.span,
+            });
+        }
+    }
+    calls
+}
+
+/// Visits the MIR of `root` and of every local function reachable from it through calls for which
+/// `descend` returns `true`.
+///
+/// Functions are visited breadth first and only once each, so the path passed to `visit` is the
+/// shortest chain of calls leading from `root` to the visited function.
+pub fn walk_local_calls<'tcx>(
+    cx: &LateContext<'tcx>,
+    root: LocalDefId,
+    mut descend: impl FnMut(&Call<'tcx>) -> bool,
+    mut visit: impl FnMut(&[CallStep], Instance<'tcx>, &'tcx Body<'tcx>),
+) {
+    let tcx = cx.tcx;
+    let param_env = tcx.param_env(root);
+    let root = Instance::new(root.to_def_id(), GenericArgs::identity_for_item(tcx, root));
+    let mut seen = FxHashSet::default();
+    let mut queue = VecDeque::from([(root, Vec::new())]);
+    seen.insert(root);
+
+    while let Some((instance, path)) = queue.pop_front() {
+        let body = tcx.instance_mir(instance.def);
+        visit(&path, instance, body);
+        for call in calls(tcx, param_env, instance, body) {
+            if let Some(callee @ Instance {
+                def: InstanceDef::Item(def_id),
+                ..
+            }) = call.instance
+                && def_id.is_local()
+                && tcx.is_mir_available(def_id)
+                && !fn_has_unsatisfiable_preds(cx, def_id)
+                && !seen.contains(&callee)
+                && descend(&call)
+            {
+                seen.insert(callee);
+                let mut path = path.clone();
+                path.push(CallStep {
+                    callee: def_id,
+                    span: call.span,
+                });
+                queue.push_back((callee, path));
+            }
+        }
+    }
+}
+
+/// Adds a note to `diag` for each call in `path`, a chain of calls starting in `caller`. Returns
+/// the function called last, or `caller` if `path` is empty.
+pub fn note_call_chain(tcx: TyCtxt<'_>, diag: &mut Diagnostic, caller: DefId, path: &[CallStep]) -> DefId {
+    let mut caller = caller;
+    for step in path {
+        diag.span_note(
+            step.span.source_callsite(),
+            format!(
+                "`{}` calls `{}` here",
+                tcx.def_path_str(caller),
+                tcx.def_path_str(step.callee)
+            ),
+        );
+        caller = step.callee;
+    }
+    caller
+}
+
+/// Lints the sites `sites` finds in `root` and in the local functions reachable from it, as
+/// walked by [`walk_local_calls`], for functions marked with an attribute such as
+/// `#[clippy::no_panic]`. `sites` is given the calls and the MIR of each function, and returns
+/// the span of each site with the reason it's linted.
+///
+/// A site in `root` is linted where it is, with the reason as a note. A site in another function
+/// is linted at the call in `root` leading to it, with notes for the following calls and the site.
+pub fn lint_reachable_sites<'tcx>(
+    cx: &LateContext<'tcx>,
+    lint: &'static Lint,
+    root: LocalDefId,
+    attr: &str,
+    what: &str,
+    descend: impl FnMut(&Call<'tcx>) -> bool,
+    mut sites: impl FnMut(&[Call<'tcx>], &'tcx Body<'tcx>) -> Vec<(Span, String)>,
+) {
+    let tcx = cx.tcx;
+    let param_env = tcx.param_env(root);
+    let root_path = tcx.def_path_str(root.to_def_id());
+    walk_local_calls(cx, root, descend, |path, instance, body| {
+        let mut found = sites(&calls(tcx, param_env, instance, body), body);
+        found.sort_by_key(|(span, _)| span.lo());
+        for (site, reason) in found {
+            let site = site.source_callsite();
+            match path {
+                [] => span_lint_and_then(
+                    cx,
+                    lint,
+                    site,
+                    &format!("this may {what}, but `{root_path}` is marked `#[clippy::{attr}]`"),
+                    |diag| {
+                        diag.note(reason);
+                    },
+                ),
+                [first, rest @ ..] => span_lint_and_then(
+                    cx,
+                    lint,
+                    first.span.source_callsite(),
+                    &format!("this call may {what}, but `{root_path}` is marked `#[clippy::{attr}]`"),
+                    |diag| {
+                        let caller = note_call_chain(tcx, diag, first.callee, rest);
+                        diag.span_note(site, format!("{reason} in `{}`", tcx.def_path_str(caller)));
+                    },
+                ),
+            }
+        }
+    });
+}
@@ -5,6 +5,8 @@
 };
 use rustc_middle::ty::TyCtxt;
 
+pub mod call_graph;
+
 mod possible_borrower;
 pub use possible_borrower::PossibleBorrowerMap;
 
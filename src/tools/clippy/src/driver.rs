@@ -83,6 +83,16 @@ fn track_files(parse_sess: &mut ParseSess) {
 
     // `clippy.toml` will be automatically tracked as it's loaded with `sess.source_map().load_file()`
 
+    // The baseline is read and written with `std::fs`, so track it explicitly. A rewritten baseline
+    // then also makes cargo re-run clippy on every crate, so none are missing from it
+    for var in ["CLIPPY_BASELINE", "CLIPPY_WRITE_BASELINE"] {
+        if let Ok(baseline) = env::var(var)
+            && Path::new(&baseline).exists()
+        {
+            file_depinfo.insert(Symbol::intern(&baseline));
+        }
+    }
+
     // During development track the `clippy-driver` executable so that cargo will re-run clippy whenever
     // it is rebuilt
     #[expect(
@@ -137,6 +147,12 @@ fn config(&mut self, config: &mut interface::Config) {
                 Symbol::intern("CLIPPY_CONF_DIR"),
                 env::var("CLIPPY_CONF_DIR").ok().map(|dir| Symbol::intern(&dir)),
             ));
+            for var in ["CLIPPY_BASELINE", "CLIPPY_WRITE_BASELINE", "CLIPPY_UNSAFE_INVENTORY"] {
+                parse_sess.env_depinfo.get_mut().insert((
+                    Symbol::intern(var),
+                    env::var(var).ok().map(|path| Symbol::intern(&path)),
+                ));
+            }
         }));
         config.register_lints = Some(Box::new(move |sess, lint_store| {
             // technically we're ~guaranteed that this is none but might as well call anything that
@@ -2,9 +2,9 @@
 // warn on lints, that are included in `rust-lang/rust`s bootstrap
 #![warn(rust_2018_idioms, unused_lifetimes)]
 
-use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{self, Command};
+use std::{env, fs};
 
 const CARGO_CLIPPY_HELP: &str = "Checks a package to catch common mistakes and improve your Rust code.
 
@@ -17,6 +17,9 @@
     -h, --help               Print this message
     -V, --version            Print version info and exit
     --explain LINT           Print the documentation for a given lint
+    --baseline FILE          Only report lints that are not recorded in the baseline FILE
+    --write-baseline FILE    Record all reported lints in the baseline FILE
+    --unsafe-inventory DIR   Write an inventory of the unsafe code in each crate to DIR as JSON
 
 For the other options see `cargo check --help`.
 
@@ -75,6 +78,9 @@ struct ClippyCmd {
     cargo_subcommand: &'static str,
     args: Vec<String>,
     clippy_args: Vec<String>,
+    baseline: Option<PathBuf>,
+    write_baseline: Option<PathBuf>,
+    unsafe_inventory: Option<PathBuf>,
 }
 
 impl ClippyCmd {
@@ -85,9 +91,37 @@ fn new<I>(mut old_args: I) -> Self
         let mut cargo_subcommand = "check";
         let mut args = vec![];
         let mut clippy_args: Vec<String> = vec![];
+        let mut baseline = None;
+        let mut write_baseline = None;
+        let mut unsafe_inventory = None;
+
+        while let Some(arg) = old_args.next() {
+            if let Some(path) = arg.strip_prefix("--baseline=") {
+                baseline = Some(path.into());
+                continue;
+            }
+            if let Some(path) = arg.strip_prefix("--write-baseline=") {
+                write_baseline = Some(path.into());
+                continue;
+            }
+            if let Some(path) = arg.strip_prefix("--unsafe-inventory=") {
+                unsafe_inventory = Some(path.into());
+                continue;
+            }
 
-        for arg in old_args.by_ref() {
             match arg.as_str() {
+                "--baseline" => {
+                    baseline = old_args.next().map(PathBuf::from);
+                    continue;
+                },
+                "--write-baseline" => {
+                    write_baseline = old_args.next().map(PathBuf::from);
+                    continue;
+                },
+                "--unsafe-inventory" => {
+                    unsafe_inventory = old_args.next().map(PathBuf::from);
+                    continue;
+                },
                 "--fix" => {
                     cargo_subcommand = "fix";
                     continue;
@@ -112,6 +146,9 @@ fn new<I>(mut old_args: I) -> Self
             cargo_subcommand,
             args,
             clippy_args,
+            baseline,
+            write_baseline,
+            unsafe_inventory,
         }
     }
 
@@ -144,16 +181,78 @@ fn into_std_cmd(self) -> Command {
             .arg(self.cargo_subcommand)
             .args(&self.args);
 
+        // Cargo may run `clippy-driver` in a different directory, so pass an absolute path
+        if let Some(baseline) = self.baseline {
+            cmd.env("CLIPPY_BASELINE", absolute(baseline));
+        }
+        if let Some(baseline) = self.write_baseline {
+            cmd.env("CLIPPY_WRITE_BASELINE", absolute(baseline));
+        }
+        if let Some(dir) = self.unsafe_inventory {
+            cmd.env("CLIPPY_UNSAFE_INVENTORY", absolute(dir));
+        }
+
         cmd
     }
 }
 
+fn absolute(path: PathBuf) -> PathBuf {
+    if path.is_absolute() {
+        path
+    } else {
+        env::current_dir().expect("current directory invalid").join(path)
+    }
+}
+
+const BASELINE_HEADER: &str = "\
+# Clippy baseline, generated by `cargo clippy --write-baseline`.
+# Lints recorded here are not reported by `cargo clippy --baseline`.
+# Each line is a lint name, file, item path and code fingerprint, separated by tabs.
+";
+
+/// Sorts the findings appended by the `clippy-driver`s, so the baseline is stable across runs.
+fn finish_baseline(path: &Path) -> Result<(), String> {
+    let contents = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut findings: Vec<&str> = contents
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect();
+    findings.sort_unstable();
+    let mut contents = BASELINE_HEADER.to_string();
+    for finding in findings {
+        contents.push_str(finding);
+        contents.push('\n');
+    }
+    fs::write(path, contents).map_err(|e| e.to_string())
+}
+
 fn process<I>(old_args: I) -> Result<(), i32>
 where
     I: Iterator<Item = String>,
 {
     let cmd = ClippyCmd::new(old_args);
 
+    if cmd.baseline.is_some() && cmd.write_baseline.is_some() {
+        eprintln!("error: `--baseline` and `--write-baseline` cannot be used together");
+        return Err(1);
+    }
+    let write_baseline = cmd.write_baseline.clone().map(absolute);
+    if let Some(path) = &write_baseline {
+        if let Err(e) = fs::write(path, BASELINE_HEADER) {
+            eprintln!("error: failed to write baseline `{}`: {e}", path.display());
+            return Err(1);
+        }
+    }
+    if let Some(dir) = &cmd.unsafe_inventory {
+        if let Err(e) = fs::create_dir_all(dir) {
+            eprintln!(
+                "error: failed to create unsafe inventory directory `{}`: {e}",
+                dir.display()
+            );
+            return Err(1);
+        }
+    }
+
     let mut cmd = cmd.into_std_cmd();
 
     let exit_status = cmd
@@ -162,6 +261,13 @@ fn process<I>(old_args: I) -> Result<(), i32>
         .wait()
         .expect("failed to wait for cargo?");
 
+    if let Some(path) = &write_baseline {
+        if let Err(e) = finish_baseline(path) {
+            eprintln!("error: failed to write baseline `{}`: {e}", path.display());
+            return Err(1);
+        }
+    }
+
     if exit_status.success() {
         Ok(())
     } else {
@@ -172,6 +278,7 @@ fn process<I>(old_args: I) -> Result<(), i32>
 #[cfg(test)]
 mod tests {
     use super::ClippyCmd;
+    use std::path::PathBuf;
 
     #[test]
     fn fix() {
@@ -203,4 +310,36 @@ fn check() {
         let cmd = ClippyCmd::new(args);
         assert_eq!("check", cmd.cargo_subcommand);
     }
+
+    #[test]
+    fn baseline() {
+        let args = "cargo clippy --baseline clippy.baseline -- -W clippy::pedantic"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert_eq!(cmd.baseline, Some(PathBuf::from("clippy.baseline")));
+        assert_eq!(cmd.write_baseline, None);
+        assert!(!cmd.args.iter().any(|arg| arg.contains("baseline")));
+        assert_eq!(cmd.clippy_args, ["-W", "clippy::pedantic"]);
+    }
+
+    #[test]
+    fn write_baseline() {
+        let args = "cargo clippy --write-baseline=clippy.baseline"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert_eq!(cmd.write_baseline, Some(PathBuf::from("clippy.baseline")));
+        assert!(cmd.args.is_empty());
+    }
+
+    #[test]
+    fn unsafe_inventory() {
+        let args = "cargo clippy --unsafe-inventory target/unsafe --all-targets"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert_eq!(cmd.unsafe_inventory, Some(PathBuf::from("target/unsafe")));
+        assert_eq!(cmd.args, ["--all-targets"]);
+    }
 }
@@ -0,0 +1,15 @@
+#![warn(clippy::alloc_in_no_alloc_fn)]
+
+// Doesn't allocate itself, but is listed in clippy.toml
+fn intern(s: &str) -> usize {
+    s.len()
+}
+
+#[clippy::no_alloc]
+fn key(s: &str) -> usize {
+    intern(s)
+}
+
+fn main() {
+    let _ = key("a");
+}
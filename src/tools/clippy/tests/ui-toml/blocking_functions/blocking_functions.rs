@@ -0,0 +1,21 @@
+#![warn(clippy::blocking_call_in_async)]
+
+fn wait_for_device() {}
+
+fn read_sensor() -> u32 {
+    0
+}
+
+async fn read_sensor_async() -> u32 {
+    0
+}
+
+async fn poll() -> u32 {
+    wait_for_device();
+    read_sensor()
+}
+
+fn main() {
+    let _ = poll();
+    let _ = read_sensor_async();
+}
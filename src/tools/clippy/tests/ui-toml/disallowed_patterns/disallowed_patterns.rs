@@ -0,0 +1,51 @@
+#![warn(clippy::disallowed_patterns)]
+
+use std::fs::File;
+use std::sync::{Arc, Mutex};
+
+fn lock_or_poisoned<T>(m: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    m.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+pub fn queue() -> Arc<Mutex<Vec<u32>>> {
+    Arc::new(Mutex::new(Vec::new()))
+}
+
+fn private_queue() -> Arc<Mutex<Vec<u32>>> {
+    Arc::new(Mutex::new(Vec::new()))
+}
+
+struct FakeMutex;
+
+impl FakeMutex {
+    fn lock(&self) -> Result<u32, ()> {
+        Ok(1)
+    }
+}
+
+pub fn push(s: &mut String) {
+    s.push('a');
+}
+
+pub fn push_str(s: &mut str) {
+    s.make_ascii_uppercase();
+}
+
+fn main() {
+    let _ = File::open("foo.txt").unwrap();
+    let _ = File::open("foo.txt").expect("no foo");
+    let _ = File::create("foo.txt").unwrap();
+
+    let m = Mutex::new(1);
+    let _guard = m.lock().unwrap();
+    drop(_guard);
+    let _guard = lock_or_poisoned(&m);
+    // Not `std::sync::Mutex::lock`
+    let _ = FakeMutex.lock().unwrap();
+
+    std::mem::forget(String::new());
+    std::mem::drop(String::new());
+
+    let _ = queue();
+    let _ = private_queue();
+}
@@ -0,0 +1,3 @@
+//@error-in-other-file: `replacement` refers to `$mutex`, which isn't captured by the pattern `$m.std::sync::Mutex::lock()`
+
+fn main() {}
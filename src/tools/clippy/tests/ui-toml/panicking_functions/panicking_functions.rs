@@ -0,0 +1,15 @@
+#![warn(clippy::panic_in_no_panic_fn)]
+
+// Doesn't panic itself, but is listed in clippy.toml
+fn validate(x: u32) -> u32 {
+    x
+}
+
+#[clippy::no_panic]
+fn checked(x: u32) -> u32 {
+    validate(x)
+}
+
+fn main() {
+    let _ = checked(1);
+}
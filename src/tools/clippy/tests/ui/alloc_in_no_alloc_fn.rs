@@ -0,0 +1,59 @@
+#![warn(clippy::alloc_in_no_alloc_fn)]
+#![allow(clippy::ptr_arg)]
+
+fn dup<T: Clone>(x: &T) -> T {
+    x.clone()
+}
+
+fn label(n: u32) -> String {
+    format!("#{n}")
+}
+
+fn describe(n: u32) -> usize {
+    label(n).len()
+}
+
+#[clippy::no_alloc]
+fn direct(v: &mut Vec<u8>, s: &str) -> usize {
+    let b = Box::new(1u8);
+    v.push(*b);
+    s.to_owned().len()
+}
+
+#[clippy::no_alloc]
+fn transitive(n: u32) -> usize {
+    describe(n)
+}
+
+#[clippy::no_alloc]
+fn generic(v: &Vec<u8>, n: &u8) -> usize {
+    // `dup::<u8>` doesn't allocate
+    usize::from(dup(n)) + dup(v).len()
+}
+
+#[clippy::no_alloc]
+fn no_alloc(v: &mut [u8]) -> usize {
+    // `direct` is checked on its own
+    v.fill(0);
+    direct(&mut Vec::new(), "")
+}
+
+#[clippy::no_alloc]
+#[allow(clippy::alloc_in_no_alloc_fn)]
+fn allowed(v: &mut Vec<u8>) {
+    v.push(0);
+}
+
+fn not_marked(v: &mut Vec<u8>) {
+    v.push(0);
+}
+
+fn main() {
+    let mut v = vec![1, 2, 3];
+    direct(&mut v, "");
+    transitive(1);
+    generic(&v, &1);
+    no_alloc(&mut [0]);
+    allowed(&mut v);
+    not_marked(&mut v);
+}
@@ -0,0 +1,14 @@
+//@rustc-env:CLIPPY_BASELINE=tests/ui/baseline.clippy-baseline
+#![allow(clippy::needless_if)]
+
+// recorded in the baseline
+fn recorded(x: bool) {
+    if x == true {}
+}
+
+// the same code, but in an item that isn't recorded
+fn added(x: bool) {
+    if x == true {}
+}
+
+fn main() {}
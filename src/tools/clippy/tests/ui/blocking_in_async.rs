@@ -0,0 +1,45 @@
+#![warn(clippy::blocking_call_in_async, clippy::indirect_blocking_call_in_async)]
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+fn read_config() -> std::io::Result<String> {
+    std::fs::read_to_string("config.toml")
+}
+
+fn load() -> std::io::Result<String> {
+    read_config()
+}
+
+fn pure(x: u32) -> u32 {
+    x + 1
+}
+
+async fn nap() {}
+
+async fn direct(m: &Mutex<u32>) {
+    std::thread::sleep(Duration::from_millis(10));
+    let _ = std::fs::read("data.bin");
+    *m.lock().unwrap() += 1;
+    nap().await;
+}
+
+async fn indirect() {
+    let _ = load();
+    let _ = pure(1);
+}
+
+fn not_async() {
+    std::thread::sleep(Duration::from_millis(10));
+    let _ = load();
+}
+
+fn closures() -> impl std::future::Future<Output = ()> {
+    async {
+        // not necessarily run on the executor
+        let _f = || std::thread::sleep(Duration::from_millis(10));
+        let _ = async { std::thread::sleep(Duration::from_millis(10)) };
+    }
+}
+
+fn main() {}
@@ -0,0 +1,59 @@
+#![warn(clippy::panic_in_no_panic_fn)]
+
+fn get(v: &[u8], i: usize) -> u8 {
+    v[i]
+}
+
+fn first(v: &[u8]) -> u8 {
+    get(v, 0)
+}
+
+fn checked_first(v: &[u8]) -> u8 {
+    v.first().copied().unwrap_or(0)
+}
+
+#[clippy::no_panic]
+fn direct(x: Option<u8>, v: &[u8], i: usize) -> u8 {
+    let a = x.unwrap();
+    let b = v[i];
+    if a == 0 {
+        panic!("zero");
+    }
+    a.wrapping_add(b)
+}
+
+#[clippy::no_panic]
+fn arithmetic(a: u32, b: u32) -> u32 {
+    a / b + 1
+}
+
+#[clippy::no_panic]
+fn transitive(v: &[u8]) -> u8 {
+    first(v)
+}
+
+#[clippy::no_panic]
+fn no_panic(v: &[u8], x: Option<u8>) -> u8 {
+    // `direct` is checked on its own
+    checked_first(v).wrapping_add(x.unwrap_or_default()).wrapping_add(direct(x, v, 0))
+}
+
+#[clippy::no_panic]
+#[allow(clippy::panic_in_no_panic_fn)]
+fn allowed(x: Option<u8>) -> u8 {
+    x.unwrap()
+}
+
+fn not_marked(x: Option<u8>) -> u8 {
+    x.unwrap()
+}
+
+fn main() {
+    let v = [1, 2, 3];
+    direct(Some(1), &v, 0);
+    arithmetic(1, 1);
+    transitive(&v);
+    no_panic(&v, None);
+    allowed(Some(1));
+    not_marked(Some(1));
+}
@@ -0,0 +1,39 @@
+//@rustc-env:CLIPPY_UNSAFE_INVENTORY=-
+
+extern "C" {
+    fn abs(x: i32) -> i32;
+    static ERRNO: i32;
+}
+
+static mut COUNTER: u32 = 0;
+
+union IntOrFloat {
+    i: u32,
+    f: f32,
+}
+
+unsafe fn unchecked(p: *const u8) -> u8 {
+    *p
+}
+
+fn operations(p: *const u8, u: IntOrFloat) -> u32 {
+    // SAFETY: `abs` is always safe to call
+    let a = unsafe { abs(-1) };
+    unsafe {
+        COUNTER += 1;
+        let _ = unchecked(p);
+        u.i + *p as u32 + a as u32
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn exported() {}
+
+struct Wrapper(*const u8);
+
+// SAFETY: the pointer is never dereferenced
+unsafe impl Send for Wrapper {}
+
+unsafe trait Zeroable {}
+
+fn main() {}
@@ -10,6 +10,7 @@
     ("unused", "Lints that detect things being declared but not used, or excess syntax"),
     ("let-underscore", "Lints that detect wildcard let bindings that are likely to be invalid"),
     ("rustdoc", "Rustdoc-specific lints"),
+    ("semver-hazards", "Lints that detect public APIs which are easy to break by accident"),
     ("rust-2018-idioms", "Lints to nudge you toward idiomatic features of Rust 2018"),
     ("nonstandard-style", "Violation of standard naming conventions"),
     ("future-incompatible", "Lints that detect code that has future-compatibility problems"),
@@ -42,13 +42,14 @@ fn main() {
     // - We set RUSTDOC to ourselves, which ends up in `phase_rustdoc`. There we call regular
     //   rustdoc with some extra flags, and we set `MIRI_CALLED_FROM_RUSTDOC` to recognize this
     //   phase in our recursive invocations:
-    //   - We set the `--test-builder` flag of rustdoc to ourselves, which ends up in `phase_rustc`
-    //     with `RustcPhase::Rustdoc`. There we perform a check-build (needed to get the expected
+    //   - We set the `--doctest-interpreter` flag of rustdoc to ourselves. rustdoc first invokes
+    //     the interpreter to build each doctest, which ends up in `phase_rustc` with
+    //     `RustcPhase::Rustdoc`. There we perform a check-build (needed to get the expected
     //     build failures for `compile_fail` doctests) and then store a JSON file with the
     //     information needed to run this test.
-    //   - We also set `--runtool` to ourselves, which ends up in `phase_runner` with
-    //     `RunnerPhase::Rustdoc`. There we parse the JSON file written in `phase_rustc` and invoke
-    //     the Miri driver for interpretation.
+    //   - rustdoc then invokes the interpreter again to run the doctest, which ends up in
+    //     `phase_runner` with `RunnerPhase::Rustdoc`. There we parse the JSON file written in
+    //     `phase_rustc` and invoke the Miri driver for interpretation.
 
     // Dispatch running as part of sysroot compilation.
     if env::var_os("MIRI_CALLED_FROM_SETUP").is_some() {
@@ -59,10 +60,10 @@ fn main() {
     // The way rustdoc invokes rustc is indistuingishable from the way cargo invokes rustdoc by the
     // arguments alone. `phase_cargo_rustdoc` sets this environment variable to let us disambiguate.
     if env::var_os("MIRI_CALLED_FROM_RUSTDOC").is_some() {
-        // ...however, we then also see this variable when rustdoc invokes us as the testrunner!
-        // The runner is invoked as `$runtool ($runtool-arg)* output_file`;
-        // since we don't specify any runtool-args, and rustdoc supplies multiple arguments to
-        // the test-builder unconditionally, we can just check the number of remaining arguments:
+        // ...however, we then also see this variable when rustdoc invokes us to run a doctest!
+        // The doctest is run as `$interpreter ($interpreter-arg)* output_file`;
+        // since we don't specify any interpreter args, and rustdoc supplies multiple arguments
+        // when building the doctest, we can just check the number of remaining arguments:
         if args.len() == 1 {
             phase_runner(args, RunnerPhase::Rustdoc);
         } else {
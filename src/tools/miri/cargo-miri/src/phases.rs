@@ -601,8 +601,8 @@ pub fn phase_rustdoc(mut args: impl Iterator<Item = String>) {
     // to let phase_cargo_rustc know to expect that. We'll use this environment variable as a flag:
     cmd.env("MIRI_CALLED_FROM_RUSTDOC", "1");
 
-    // The `--test-builder` and `--runtool` arguments are unstable rustdoc features,
-    // which are disabled by default. We first need to enable them explicitly:
+    // The `--doctest-interpreter` argument is an unstable rustdoc feature,
+    // which is disabled by default. We first need to enable it explicitly:
     cmd.arg("-Z").arg("unstable-options");
 
     // rustdoc needs to know the right sysroot.
@@ -612,8 +612,9 @@ pub fn phase_rustdoc(mut args: impl Iterator<Item = String>) {
 
     // Make rustdoc call us back.
     let cargo_miri_path = std::env::current_exe().expect("current executable path invalid");
-    cmd.arg("--test-builder").arg(&cargo_miri_path); // invoked by forwarding most arguments
-    cmd.arg("--runtool").arg(&cargo_miri_path); // invoked with just a single path argument
+    // The interpreter is invoked by forwarding most arguments to build each doctest,
+    // then with just a single path argument to run it.
+    cmd.arg("--doctest-interpreter").arg(&cargo_miri_path);
 
     debug_cmd("[cargo-miri rustdoc]", verbose, &cmd);
     exec(cmd)
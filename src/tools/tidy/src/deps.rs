@@ -261,6 +261,7 @@
     "tinystr",
     "tinyvec",
     "tinyvec_macros",
+    "toml",
     "tracing",
     "tracing-attributes",
     "tracing-core",
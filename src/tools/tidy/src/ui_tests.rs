@@ -36,6 +36,8 @@
     "tests/ui/unused-crate-deps/test.mk", // why would you use make
     "tests/ui/proc-macro/auxiliary/included-file.txt", // more include
     "tests/ui/invalid/foo.natvis.xml", // sample debugger visualizer
+    "tests/ui/lint/lint-config/lints.toml", // lint levels passed with `-Z lint-config`
+    "tests/ui/lint/lint-config/lints-cli.toml", // lint levels passed with `-Z lint-config`
 ];
 
 fn check_entries(tests_path: &Path, bad: &mut bool) {
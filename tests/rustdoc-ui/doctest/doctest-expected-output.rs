@@ -0,0 +1,15 @@
+// compile-flags:--test --test-args=--test-threads=1
+// normalize-stdout-test: "tests/rustdoc-ui/doctest" -> "$$DIR"
+// normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+// check-pass
+
+/// ```
+/// println!("| a | b |");
+/// println!("| 1 | 2 |");
+/// ```
+///
+/// ```text,output
+/// | a | b |
+/// | 1 | 2 |
+/// ```
+pub struct Foo;
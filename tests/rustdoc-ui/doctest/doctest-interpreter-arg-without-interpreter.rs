@@ -0,0 +1,6 @@
+// check that `--doctest-interpreter-arg` can't be used without `--doctest-interpreter`
+
+// compile-flags:-Z unstable-options --test --doctest-interpreter-arg=-Zmiri-strict-provenance
+// error-pattern: `--doctest-interpreter-arg` requires `--doctest-interpreter`
+
+pub fn f() {}
@@ -0,0 +1,6 @@
+// check that `--doctest-interpreter` can't be used together with `--runtool`
+
+// compile-flags:-Z unstable-options --test --doctest-interpreter=miri --runtool=valgrind
+// error-pattern: `--doctest-interpreter` cannot be used with `--test-builder` or `--runtool`
+
+pub fn f() {}
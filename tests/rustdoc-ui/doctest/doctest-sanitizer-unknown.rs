@@ -0,0 +1,6 @@
+// check that `--doctest-sanitizer` rejects unknown sanitizers
+
+// compile-flags:-Z unstable-options --test --doctest-sanitizer=undefined
+// error-pattern: unknown sanitizer `undefined`
+
+pub fn f() {}
@@ -0,0 +1,15 @@
+// compile-flags:--test
+// normalize-stdout-test: "tests/rustdoc-ui/doctest" -> "$$DIR"
+// normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+// failure-status: 101
+
+/// ```
+/// println!("| a | b |");
+/// println!("| 1 | 3 |");
+/// ```
+///
+/// ```text,output
+/// | a | b |
+/// | 1 | 2 |
+/// ```
+pub struct Foo;
@@ -0,0 +1,13 @@
+// compile-flags:--test
+// normalize-stdout-test: "tests/rustdoc-ui/doctest" -> "$$DIR"
+// normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+// failure-status: 101
+
+/// ```no_run
+/// println!("hello");
+/// ```
+///
+/// ```text,output
+/// hello
+/// ```
+pub struct Foo;
@@ -0,0 +1,19 @@
+// compile-flags: -Z unstable-options --generate-api-history
+
+#![crate_name = "foo"]
+#![feature(doc_since)]
+
+// @has foo/history.html '//h2[@id="version-0.3.0"]' '0.3.0'
+// @has - '//a[@href="fn.added.html"]' 'added'
+#[doc(since = "0.3.0")]
+pub fn added() {}
+
+// @has foo/history.html '//a[@href="bar/struct.Old.html"]' 'bar::Old'
+pub mod bar {
+    #[doc(since = "0.1.0")]
+    #[deprecated(since = "0.3.0")]
+    pub struct Old;
+}
+
+// @!has foo/history.html '//a[@href="fn.undated.html"]' 'undated'
+pub fn undated() {}
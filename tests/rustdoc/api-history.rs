@@ -0,0 +1,47 @@
+// compile-flags: -Z unstable-options --generate-api-history
+
+#![crate_name = "foo"]
+#![feature(staged_api)]
+#![stable(feature = "rust1", since = "1.0.0")]
+
+// @has foo/history.html
+// @has foo/history.json
+// @has - '//h2[@id="version-1.10.0"]' '1.10.0'
+// @has - '//h2[@id="version-1.2.0"]' '1.2.0'
+// @has - '//h2[@id="version-1.0.0"]' '1.0.0'
+
+// @has foo/history.html '//ul[@class="all-items"]//a[@href="struct.Foo.html"]' 'Foo'
+#[stable(feature = "rust1", since = "1.0.0")]
+pub struct Foo;
+
+impl Foo {
+    // @has foo/history.html '//a[@href="struct.Foo.html#method.bar"]' 'Foo::bar'
+    #[stable(feature = "foo_bar", since = "1.2.0")]
+    #[rustc_const_stable(feature = "const_foo_bar", since = "1.10.0")]
+    pub const fn bar() {}
+}
+
+// @has foo/history.html '//a[@href="trait.Trait.html"]' 'Trait'
+// @has foo/history.html '//a[@href="trait.Trait.html#tymethod.required"]' 'Trait::required'
+#[stable(feature = "rust1", since = "1.0.0")]
+pub trait Trait {
+    #[stable(feature = "rust1", since = "1.0.0")]
+    #[deprecated(since = "1.2.0", note = "no longer needed")]
+    fn required();
+}
+
+// @!has foo/history.html '//a[@href="fn.unstable.html"]' 'unstable'
+#[unstable(feature = "unstable", issue = "none")]
+pub fn unstable() {}
+
+// @!has foo/history.html '//h2[@id="version-TBD"]' 'TBD'
+// @count foo/history.html '//a[@href="fn.soon_deprecated.html"]' 1
+#[stable(feature = "soon_deprecated", since = "1.2.0")]
+#[deprecated(since = "TBD", note = "will be replaced")]
+pub fn soon_deprecated() {}
+
+// @!has foo/history.html '//h2[@id="version-99.99.99"]' '99.99.99'
+// @count foo/history.html '//a[@href="fn.future_deprecated.html"]' 1
+#[stable(feature = "future_deprecated", since = "1.2.0")]
+#[deprecated(since = "99.99.99", note = "will be replaced")]
+pub fn future_deprecated() {}
@@ -0,0 +1,5 @@
+pub struct Shape;
+
+pub fn area(_shape: &Shape) -> f64 {
+    0.
+}
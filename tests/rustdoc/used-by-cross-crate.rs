@@ -0,0 +1,30 @@
+// aux-build:used-by-aux.rs
+// build-aux-docs
+// ignore-cross-compile
+// compile-flags: --show-used-by -Z unstable-options
+
+#![crate_name = "foo"]
+
+extern crate used_by_aux;
+
+use used_by_aux::Shape;
+
+// @hasraw used-by/used_by_aux.js '"used_by_aux":{}'
+// @hasraw - '"foo":{"used_by_aux/struct.Shape":[["foo::measure","foo/fn.measure.html"],\
+//     ["impl foo::Describe","foo/trait.Describe.html#implementors"]]}'
+pub fn measure(_shape: &Shape) -> f64 {
+    0.
+}
+
+pub trait Describe {}
+
+impl Describe for Shape {}
+
+// @hasraw used-by/foo.js '"foo":{}'
+// @has foo/struct.Local.html
+// @has - '//*[@id="used-by-list"]//a[@href="fn.build.html"]' 'foo::build'
+pub struct Local;
+
+pub fn build() -> Local {
+    Local
+}
@@ -0,0 +1,62 @@
+// compile-flags: --show-used-by -Z unstable-options
+
+#![crate_name = "foo"]
+
+// @has foo/struct.Config.html
+// @has - '//*[@id="used-by"]/a[@href="#used-by"]' ''
+// @has - '//*[@id="used-by-list"]//a[@href="fn.load.html"]' 'foo::load'
+// @has - '//*[@id="used-by-list"]//a[@href="trait.Backend.html#tymethod.open"]' \
+//     'foo::Backend::open'
+// @has - '//*[@id="used-by-list"]//a[@href="struct.Handle.html#method.reload"]' \
+//     'foo::Handle::reload'
+// @has - '//*[@id="used-by-list"]//a[@href="inner/fn.configs.html"]' 'foo::inner::configs'
+// @count - '//*[@id="used-by-list"]//a' 4
+// @has - '//script/@src' '../used-by/foo.js'
+// @has - '//script/@data-used-by-item' 'foo/struct.Config'
+pub struct Config;
+
+// @has foo/struct.Handle.html
+// @has - '//*[@id="used-by-list"]//a[@href="trait.Backend.html#tymethod.open"]' \
+//     'foo::Backend::open'
+// @!has - '//*[@id="used-by-list"]//a[@href="struct.Handle.html#method.reload"]' ''
+pub struct Handle;
+
+impl Handle {
+    pub fn reload(&mut self, _config: Config) {}
+}
+
+// @has foo/trait.Backend.html
+// @has - '//*[@id="used-by-list"]//a[@href="fn.run.html"]' 'foo::run'
+pub trait Backend {
+    fn open(&self, config: &Config) -> Handle;
+}
+
+// @has foo/struct.Unused.html
+// @!has - '//*[@id="used-by"]' ''
+// @!has - '//*[@id="used-by-list"]' ''
+// @has - '//script/@data-used-by-item' 'foo/struct.Unused'
+pub struct Unused;
+
+pub fn load() -> Option<Config> {
+    None
+}
+
+pub fn run<B>(_backend: B)
+where
+    B: Backend,
+{
+}
+
+pub mod inner {
+    pub fn configs() -> Vec<crate::Config> {
+        Vec::new()
+    }
+}
+
+// Items from hidden modules aren't listed.
+#[doc(hidden)]
+pub mod hidden {
+    pub fn config() -> crate::Config {
+        crate::Config
+    }
+}
@@ -0,0 +1,4 @@
+#[doc(since = "1.0.0")] //~ ERROR: `#[doc(since)]` is experimental
+pub fn foo() {}
+
+fn main() {}
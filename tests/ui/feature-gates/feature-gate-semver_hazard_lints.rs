@@ -0,0 +1,12 @@
+// check-pass
+
+#![warn(exhaustive_public_structs)] //~ WARN unknown lint
+                                    //~| WARN unknown lint
+                                    //~| WARN unknown lint
+#![warn(exhaustive_public_enums)] //~ WARN unknown lint
+                                  //~| WARN unknown lint
+                                  //~| WARN unknown lint
+#![warn(auto_traits_from_private_fields)] //~ WARN unknown lint
+                                          //~| WARN unknown lint
+                                          //~| WARN unknown lint
+fn main() {}
@@ -0,0 +1,6 @@
+// check-pass
+
+#![warn(unnecessary_visibility)] //~ WARN unknown lint
+                                 //~| WARN unknown lint
+                                 //~| WARN unknown lint
+fn main() {}
@@ -0,0 +1,8 @@
+// compile-flags: -Zworkspace-dead-code=workspace_dead_code_lib_and_bin
+// no-prefer-dynamic
+
+#![crate_type = "rlib"]
+
+pub fn used() {}
+
+pub fn unused() {}
@@ -0,0 +1,43 @@
+// compile-flags: -Zworkspace-dead-code=workspace_dead_code
+// no-prefer-dynamic
+
+#![crate_type = "rlib"]
+
+pub fn used() {
+    helper();
+}
+
+fn helper() {
+    used_by_helper();
+}
+
+pub fn used_by_helper() {}
+
+pub fn unused() {
+    used_by_unused();
+}
+
+pub fn used_by_unused() {}
+
+pub struct Used;
+
+impl Used {
+    pub fn new() -> Used {
+        Used
+    }
+
+    pub fn unused_method(&self) {}
+}
+
+pub struct Unused;
+
+pub trait UnusedTrait {}
+
+pub mod inner {
+    pub const UNUSED: u32 = 0;
+}
+
+#[allow(dead_code)]
+pub fn allowed() {}
+
+pub fn _ignored() {}
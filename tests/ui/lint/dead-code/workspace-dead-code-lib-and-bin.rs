@@ -0,0 +1,12 @@
+// Checks that `-Zworkspace-dead-code` doesn't treat a library as a final crate when it has the
+// name of a final crate, as the library and the binaries of a Cargo package do.
+//
+// check-pass
+// aux-build:workspace-dead-code-lib-and-bin.rs
+// compile-flags: -Zworkspace-dead-code=workspace_dead_code_lib_and_bin
+
+extern crate workspace_dead_code_lib_and_bin;
+
+fn main() {
+    workspace_dead_code_lib_and_bin::used();
+}
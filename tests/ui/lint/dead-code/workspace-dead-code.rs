@@ -0,0 +1,13 @@
+// Checks that `-Zworkspace-dead-code` reports the public items of the workspace libraries which
+// no crate uses, in the final crates it lists.
+//
+// check-pass
+// aux-build:workspace-dead-code-lib.rs
+// compile-flags: -Zworkspace-dead-code=workspace_dead_code
+
+extern crate workspace_dead_code_lib;
+
+fn main() {
+    workspace_dead_code_lib::used();
+    let _ = workspace_dead_code_lib::Used::new();
+}
@@ -0,0 +1,17 @@
+// Checks that lint levels set on the command line override those `-Z lint-config` sets, for the
+// crate and for the modules matching the patterns in the file.
+//
+// check-pass
+// compile-flags: -Zlint-config={{src-base}}/lint/lint-config/lints-cli.toml
+// compile-flags: -W unsafe-code -W dead-code
+
+fn main() {
+    unsafe {} //~ WARN usage of an `unsafe` block
+}
+
+mod ffi {
+    fn call() {
+        //~^ WARN function `call` is never used
+        unsafe {} //~ WARN usage of an `unsafe` block
+    }
+}
@@ -0,0 +1,27 @@
+// Checks that `-Z lint-config` sets lint levels for the crate and for the modules matching the
+// patterns in the file, and that attributes in the source override them.
+//
+// compile-flags: -Zlint-config={{src-base}}/lint/lint-config/lints.toml
+
+fn main() {
+    unsafe {} //~ ERROR usage of an `unsafe` block
+}
+
+mod ffi {
+    fn call() {
+        unsafe {}
+    }
+
+    mod sys {
+        fn call() {
+            unsafe {}
+        }
+    }
+}
+
+mod parser {
+    #[warn(unsafe_code)]
+    fn parse() {
+        unsafe {} //~ WARN usage of an `unsafe` block
+    }
+}
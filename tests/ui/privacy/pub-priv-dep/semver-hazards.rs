@@ -0,0 +1,23 @@
+// Checks that `exported_private_dependencies` is not part of the `semver_hazards` lint group.
+//
+// aux-crate:priv:priv_dep=priv_dep.rs
+// compile-flags: -Zunstable-options
+// check-pass
+#![feature(semver_hazard_lints)]
+#![crate_type = "lib"]
+#![deny(semver_hazards)]
+
+extern crate priv_dep;
+
+use priv_dep::OtherType;
+
+#[non_exhaustive]
+pub struct PublicType {
+    pub field: OtherType,
+    //~^ WARN type `OtherType` from private dependency 'priv_dep' in public interface
+}
+
+// Allowing the group doesn't allow the private dependency lint.
+#[allow(semver_hazards)]
+pub fn pub_fn(_param: OtherType) {}
+//~^ WARN type `OtherType` from private dependency 'priv_dep' in public interface
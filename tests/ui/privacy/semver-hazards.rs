@@ -0,0 +1,85 @@
+#![crate_type = "lib"]
+#![feature(semver_hazard_lints)]
+#![deny(semver_hazards)]
+#![allow(dead_code)]
+
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+pub struct Point { //~ ERROR struct `Point` is exhaustive
+    pub x: i32,
+    pub y: i32,
+}
+
+pub struct Meters(pub f64); //~ ERROR struct `Meters` is exhaustive
+
+#[non_exhaustive]
+pub struct Config {
+    pub verbose: bool,
+}
+
+pub struct Opaque {
+    len: usize,
+}
+
+pub enum Mode { //~ ERROR enum `Mode` is exhaustive
+    Fast,
+    Slow,
+}
+
+#[non_exhaustive]
+pub enum Error {
+    Io,
+}
+
+pub enum Never {}
+
+pub struct Handle {
+    inner: Rc<u8>,
+    //~^ ERROR struct `Handle` is not `Send` because of its private field `inner`
+    //~| ERROR struct `Handle` is not `Sync` because of its private field `inner`
+}
+
+pub struct Counter {
+    pub name: String,
+    count: Cell<u32>, //~ ERROR struct `Counter` is not `Sync` because of its private field `count`
+}
+
+pub struct Local {
+    ptr: Rc<u8>,
+    _marker: PhantomData<*const ()>,
+}
+
+pub struct Shared {
+    ptr: *const u8,
+}
+
+unsafe impl Send for Shared {}
+unsafe impl Sync for Shared {}
+
+pub struct Wrapper<T> {
+    value: T,
+}
+
+struct Private {
+    pub rc: Rc<u8>,
+}
+
+mod hidden {
+    pub struct Unreachable(pub u8);
+}
+
+pub trait Storage {
+    type Cell;
+}
+
+impl Storage for u8 {
+    type Cell = Rc<u8>;
+}
+
+pub struct Projected {
+    cell: <u8 as Storage>::Cell,
+    //~^ ERROR struct `Projected` is not `Send` because of its private field `cell`
+    //~| ERROR struct `Projected` is not `Sync` because of its private field `cell`
+}
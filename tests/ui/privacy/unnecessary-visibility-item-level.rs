@@ -0,0 +1,15 @@
+// Checks that the lint is emitted when only an item-level attribute enables it.
+
+#![feature(unnecessary_visibility_lint)]
+#![allow(dead_code)]
+
+#[deny(unnecessary_visibility)]
+mod parser {
+    pub fn parse() {} //~ ERROR function `parser::parse` is `pub` but is not used outside of its module
+}
+
+pub fn helper() {}
+
+fn main() {
+    helper();
+}
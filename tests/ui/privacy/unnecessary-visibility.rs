@@ -0,0 +1,81 @@
+// run-rustfix
+
+#![feature(unnecessary_visibility_lint)]
+#![deny(unnecessary_visibility)]
+#![allow(dead_code)]
+
+mod app {
+    pub mod parser { //~ ERROR module `app::parser` is `pub` but is not used outside of its module
+        macro_rules! version {
+            () => {
+                VERSION
+            };
+        }
+
+        // Used from the output of a macro.
+        pub(crate) const VERSION: u32 = 1;
+
+        pub(crate) fn parse(input: &str) -> u32 {
+            //~^ ERROR function `app::parser::parse` is `pub(crate)` but is not used outside of its parent module
+            skip_whitespace(input).len() as u32 + version!()
+        }
+
+        pub fn skip_whitespace(input: &str) -> &str {
+            //~^ ERROR function `app::parser::skip_whitespace` is `pub` but is not used outside of its module
+            input.trim_start()
+        }
+
+        pub(crate) struct Lexer { //~ ERROR struct `app::parser::Lexer` is `pub(crate)` but is not used outside of its parent module
+            pos: usize,
+        }
+
+        impl Lexer {
+            pub(crate) fn new() -> Lexer {
+                //~^ ERROR associated function `app::parser::Lexer::new` is `pub(crate)` but is not used outside of its parent module
+                Lexer { pos: 0 }
+            }
+
+            pub fn advance(&mut self) {
+                //~^ ERROR method `app::parser::Lexer::advance` is `pub` but is not used outside of its module
+                self.pos += 1;
+            }
+        }
+
+        pub(crate) fn lex() -> usize {
+            //~^ ERROR function `app::parser::lex` is `pub(crate)` but is not used outside of its module
+            let mut lexer = Lexer::new();
+            lexer.advance();
+            lexer.pos
+        }
+
+        // Needed by the interface of `next_token`.
+        pub(crate) struct Token;
+
+        pub(crate) fn next_token() -> Token {
+            //~^ ERROR function `app::parser::next_token` is `pub(crate)` but is not used outside of its parent module
+            Token
+        }
+
+        #[derive(Debug)]
+        pub(crate) struct Keyword; //~ ERROR struct `app::parser::Keyword` is `pub(crate)` but is not used outside of its module
+
+        // Re-exported from `app`.
+        pub(crate) struct Span;
+    }
+
+    pub(crate) use self::parser::Span;
+
+    pub(crate) fn run(input: &str) -> u32 {
+        let _lexer = parser::Lexer::new();
+        let _token = parser::next_token();
+        parser::parse(input)
+    }
+}
+
+pub fn helper() {} //~ ERROR function `helper` is `pub` but is not used outside of its module
+
+fn main() {
+    app::run(" 42");
+    helper();
+    let _span = app::Span;
+}